@@ -1,4 +1,4 @@
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use crate::models::config::Config;
 use crate::commands::project::AppState;
 use std::fs;
@@ -19,7 +19,13 @@ pub async fn save_config(
     config: Config,
 ) -> Result<(), String> {
     // 更新内存中的配置
-    *state.config.lock().unwrap() = config.clone();
+    let sort_changed = {
+        let mut current = state.config.lock().unwrap();
+        let changed = current.project_sort_by != config.project_sort_by
+            || current.frecency_buckets != config.frecency_buckets;
+        *current = config.clone();
+        changed
+    };
 
     // 保存到磁盘
     let config_path = app.path()
@@ -38,6 +44,11 @@ pub async fn save_config(
     fs::write(config_path, json)
         .map_err(|e| e.to_string())?;
 
+    // 项目列表的顺序由后端计算，排序设置变化时通知各窗口重新加载
+    if sort_changed {
+        let _ = app.emit("projects-updated", ());
+    }
+
     Ok(())
 }
 
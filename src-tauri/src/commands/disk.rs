@@ -0,0 +1,128 @@
+use tauri::{AppHandle, Emitter, State};
+use std::collections::HashMap;
+use crate::commands::project::AppState;
+use crate::commands::job::{spawn_project_job, JobEvents, ProjectJob};
+use crate::models::disk::{CleanupOptions, CleanupReport, DiskUsageSummary, DiskUsageUpdate};
+use crate::models::project::Project;
+use crate::services::disk_usage::DiskUsageService;
+
+/// 磁盘统计并发数（以 IO 为主，过高反而更慢）
+const DISK_USAGE_CONCURRENCY: usize = 2;
+
+/// 后台计算项目磁盘占用（`project_paths` 为空时计算全部项目），立即返回任务 ID
+///
+/// 结果通过 `disk-usage-results` 事件批量推送并写入缓存，
+/// 结束时发送带总大小/可回收大小摘要的 `disk-usage-finished`。
+#[tauri::command]
+pub async fn calculate_disk_usage(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    project_paths: Option<Vec<String>>,
+) -> Result<String, String> {
+    let types: HashMap<String, Option<String>> = load_projects(&state)?
+        .into_iter()
+        .filter(|p| project_paths.as_ref().is_none_or(|paths| paths.contains(&p.path)))
+        .map(|p| (p.path, p.project_type))
+        .collect();
+    let paths: Vec<String> = types.keys().cloned().collect();
+
+    let (job_id, token) = state.jobs.start("disk-usage")?;
+
+    spawn_project_job(
+        app,
+        ProjectJob {
+            job_id: job_id.clone(),
+            token,
+            paths,
+            events: JobEvents {
+                progress: "disk-usage-progress",
+                results: "disk-usage-results",
+                finished: "disk-usage-finished",
+            },
+            concurrency: Some(DISK_USAGE_CONCURRENCY),
+        },
+        move |path| {
+            let project_type = types.get(path).cloned().flatten();
+            Some(DiskUsageUpdate {
+                path: path.to_string(),
+                disk_usage: DiskUsageService::measure(path, project_type.as_deref()),
+            })
+        },
+        |project, update| {
+            project.disk_usage = Some(update.disk_usage.clone());
+        },
+        |results| {
+            let summary = DiskUsageSummary {
+                total: results.iter().map(|r| r.disk_usage.total).sum(),
+                reclaimable: results.iter().map(|r| r.disk_usage.reclaimable()).sum(),
+            };
+            serde_json::to_value(summary).ok()
+        },
+    );
+
+    Ok(job_id)
+}
+
+/// 删除所选项目的构建产物目录，`dry_run` 时只返回可回收大小
+///
+/// 实际删除时必须通过 `project_paths` 明确选择项目。
+#[tauri::command]
+pub async fn cleanup_artifacts(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    options: CleanupOptions,
+) -> Result<CleanupReport, String> {
+    if !options.dry_run && options.project_paths.as_ref().is_none_or(|paths| paths.is_empty()) {
+        return Err("删除构建产物前请先选择项目".to_string());
+    }
+    let now = chrono::Utc::now();
+    let selected: Vec<Project> = load_projects(&state)?
+        .into_iter()
+        .filter(|p| options.project_paths.as_ref().is_none_or(|paths| paths.contains(&p.path)))
+        .filter(|p| options.not_opened_days.is_none_or(|days| DiskUsageService::not_opened_within(p, days, now)))
+        .collect();
+
+    let dry_run = options.dry_run;
+    let projects = tauri::async_runtime::spawn_blocking(move || {
+        use rayon::prelude::*;
+        selected
+            .par_iter()
+            .map(|p| DiskUsageService::cleanup(&p.path, &p.name, p.project_type.as_deref(), dry_run))
+            .filter(|c| !c.artifacts.is_empty() || !c.errors.is_empty())
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if !dry_run && !projects.is_empty() {
+        // 已删除的目录从缓存的占用统计中移除
+        let _ = state.cache_manager.lock().unwrap().update(|cached| {
+            for cleanup in &projects {
+                let usage = cached
+                    .iter_mut()
+                    .find(|p| p.path == cleanup.path)
+                    .and_then(|p| p.disk_usage.as_mut());
+                if let Some(usage) = usage {
+                    usage.total = usage.total.saturating_sub(cleanup.reclaimed);
+                    usage.artifacts.retain(|a| !cleanup.artifacts.iter().any(|c| c.path == a.path));
+                }
+            }
+        });
+        let _ = app.emit("projects-updated", ());
+    }
+
+    Ok(CleanupReport {
+        dry_run,
+        total_bytes: projects.iter().map(|p| p.reclaimed).sum(),
+        projects,
+    })
+}
+
+fn load_projects(state: &AppState) -> Result<Vec<Project>, String> {
+    let cache_manager = state.cache_manager.lock().unwrap();
+    Ok(cache_manager
+        .load_instant()
+        .map_err(|e| e.to_string())?
+        .map(|c| c.projects)
+        .unwrap_or_default())
+}
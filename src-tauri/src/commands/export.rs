@@ -1,6 +1,7 @@
 use std::fs;
 use tauri::{AppHandle, Manager, State};
 use crate::commands::project::AppState;
+use crate::commands::launcher::refresh_launcher_health;
 use crate::models::export::{ExportData, ExportOptions, GeneralSettings, WorkspaceSettings};
 
 /// 导出设置
@@ -27,6 +28,7 @@ pub async fn export_settings(
             autostart: config.autostart,
             theme: config.theme.clone(),
             project_sort_by: config.project_sort_by.clone(),
+            frecency_buckets: Some(config.frecency_buckets.clone()),
         });
     }
 
@@ -71,6 +73,9 @@ pub async fn import_settings(
                 config.autostart = general.autostart;
                 config.theme = general.theme.clone();
                 config.project_sort_by = general.project_sort_by.clone();
+                if let Some(buckets) = &general.frecency_buckets {
+                    config.frecency_buckets = buckets.clone();
+                }
             }
         }
 
@@ -123,6 +128,11 @@ pub async fn import_settings(
         }
     }
 
+    // 导入的启动器可能指向本机不存在的程序
+    if options.launchers {
+        refresh_launcher_health(&state, &app);
+    }
+
     Ok(())
 }
 
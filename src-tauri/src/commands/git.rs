@@ -0,0 +1,457 @@
+use tauri::{AppHandle, Emitter, State};
+use std::path::Path;
+use std::time::Duration;
+use crate::commands::project::{register_project, AppState};
+use crate::commands::job::{spawn_project_job, JobEvents, ProjectJob};
+use crate::commands::launcher::spawn_launch;
+use crate::models::git::{
+    BranchList, BranchSwitchOptions, BranchSwitchResult, CloneOptions, CloneProgress, GitInfo, GitInfoUpdate,
+    ProjectWebLinks, RepositoryReport, WebPage,
+};
+use crate::models::project::{Project, VersionControl};
+use crate::models::launcher::LaunchTarget;
+use crate::services::git_service::GitService;
+use crate::services::repository_report::RepositoryReporter;
+use crate::services::git_sync::GitSync;
+use crate::services::git_branch::GitBranchService;
+use crate::services::git_hosting::GitHosting;
+use crate::services::git_clone::GitClone;
+use crate::services::job_manager::Throttle;
+
+/// Git 状态默认有效期（秒），超过后才会重新读取
+const GIT_INFO_MAX_AGE_SECS: u64 = 300;
+
+/// 批量同步默认并发数
+const GIT_SYNC_CONCURRENCY: usize = 4;
+
+/// 克隆进度事件的最小间隔
+const CLONE_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 获取单个项目的 Git 状态（缓存未过期时直接返回）
+#[tauri::command]
+pub async fn get_git_info(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    project_path: String,
+    force: Option<bool>,
+) -> Result<GitInfo, String> {
+    if !force.unwrap_or(false) {
+        let cache_manager = state.cache_manager.lock().unwrap();
+        let cached = cache_manager
+            .load_instant()
+            .ok()
+            .flatten()
+            .and_then(|c| c.projects.into_iter().find(|p| p.path == project_path))
+            .and_then(|p| p.git);
+        if let Some(git) = cached.filter(|g| g.is_fresh(GIT_INFO_MAX_AGE_SECS)) {
+            return Ok(git);
+        }
+    }
+
+    let path = project_path.clone();
+    let git = tauri::async_runtime::spawn_blocking(move || GitService::read_info(&path))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+    state.cache_manager.lock().unwrap()
+        .update(|projects| {
+            if let Some(project) = projects.iter_mut().find(|p| p.path == project_path) {
+                project.git = Some(git.clone());
+            }
+        })
+        .map_err(|e| e.to_string())?;
+
+    let _ = app.emit("git-info-updated", vec![GitInfoUpdate {
+        path: project_path,
+        git: git.clone(),
+    }]);
+
+    Ok(git)
+}
+
+/// 后台刷新过期的 Git 状态
+///
+/// 不传 `project_paths` 时刷新所有 Git 项目；结果通过 `git-info-updated` 事件批量推送。
+/// 没有需要刷新的项目或已有刷新任务在运行时返回 None，否则返回任务 ID。
+#[tauri::command]
+pub async fn refresh_git_info(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    project_paths: Option<Vec<String>>,
+    max_age_secs: Option<u64>,
+) -> Result<Option<String>, String> {
+    let max_age = max_age_secs.unwrap_or(GIT_INFO_MAX_AGE_SECS);
+
+    let paths: Vec<String> = {
+        let cache_manager = state.cache_manager.lock().unwrap();
+        cache_manager
+            .load_instant()
+            .map_err(|e| e.to_string())?
+            .map(|c| c.projects)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|p| matches!(p.version_control, VersionControl::Git))
+            .filter(|p| project_paths.as_ref().is_none_or(|paths| paths.contains(&p.path)))
+            .filter(|p| !p.git.as_ref().is_some_and(|g| g.is_fresh(max_age)))
+            .map(|p| p.path)
+            .collect()
+    };
+
+    if paths.is_empty() || state.jobs.is_running("git-refresh") {
+        return Ok(None);
+    }
+
+    let (job_id, token) = state.jobs.start("git-refresh")?;
+
+    spawn_project_job(
+        app,
+        ProjectJob {
+            job_id: job_id.clone(),
+            token,
+            paths,
+            events: JobEvents {
+                progress: "git-refresh-progress",
+                results: "git-info-updated",
+                finished: "git-refresh-finished",
+            },
+            concurrency: None,
+        },
+        |path| {
+            GitService::read_info(path)
+                .map(|git| GitInfoUpdate { path: path.to_string(), git })
+                .map_err(|e| eprintln!("读取 Git 状态失败 [{}]: {}", path, e))
+                .ok()
+        },
+        |project, update| project.git = Some(update.git.clone()),
+        |_| None,
+    );
+
+    Ok(Some(job_id))
+}
+
+/// 生成所有 Git 项目的状态报告（并行评估，并同步更新缓存中的 Git 状态）
+#[tauri::command]
+pub async fn get_repository_report(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    needs_attention_only: Option<bool>,
+) -> Result<RepositoryReport, String> {
+    let report = build_repository_report(&state, needs_attention_only.unwrap_or(false)).await?;
+
+    let updates: Vec<GitInfoUpdate> = report
+        .repositories
+        .iter()
+        .filter_map(|r| Some(GitInfoUpdate { path: r.path.clone(), git: r.git.clone()? }))
+        .collect();
+    if !updates.is_empty() {
+        state.cache_manager.lock().unwrap()
+            .update(|projects| {
+                for project in projects.iter_mut() {
+                    if let Some(update) = updates.iter().find(|u| u.path == project.path) {
+                        project.git = Some(update.git.clone());
+                    }
+                }
+            })
+            .map_err(|e| e.to_string())?;
+        let _ = app.emit("git-info-updated", &updates);
+    }
+
+    Ok(report)
+}
+
+/// 导出仓库状态报告
+///
+/// `format` 为 "json" 或 "markdown"；传入 `output_path` 时同时写入文件
+#[tauri::command]
+pub async fn export_repository_report(
+    state: State<'_, AppState>,
+    format: String,
+    needs_attention_only: Option<bool>,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    let report = build_repository_report(&state, needs_attention_only.unwrap_or(false)).await?;
+
+    let content = match format.as_str() {
+        "json" => serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?,
+        "markdown" | "md" => RepositoryReporter::to_markdown(&report),
+        other => return Err(format!("不支持的导出格式: {}", other)),
+    };
+
+    if let Some(path) = output_path {
+        std::fs::write(path, &content).map_err(|e| e.to_string())?;
+    }
+
+    Ok(content)
+}
+
+async fn build_repository_report(
+    state: &State<'_, AppState>,
+    needs_attention_only: bool,
+) -> Result<RepositoryReport, String> {
+    let projects = {
+        let cache_manager = state.cache_manager.lock().unwrap();
+        cache_manager
+            .load_instant()
+            .map_err(|e| e.to_string())?
+            .map(|c| c.projects)
+            .unwrap_or_default()
+    };
+
+    tauri::async_runtime::spawn_blocking(move || RepositoryReporter::build(&projects, needs_attention_only))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 批量 fetch（可选 fast-forward pull）选中的 Git 项目
+///
+/// 每个仓库开始时发送 `git-sync-repo-started`，结果通过 `git-sync-results` 批量推送，
+/// 结束时 `git-sync-finished` 的 `summary` 中列出跳过和失败的仓库。返回任务 ID。
+#[tauri::command]
+pub async fn sync_projects(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    project_paths: Vec<String>,
+    pull: Option<bool>,
+    concurrency: Option<usize>,
+) -> Result<String, String> {
+    let pull = pull.unwrap_or(false);
+
+    let paths: Vec<String> = {
+        let cache_manager = state.cache_manager.lock().unwrap();
+        cache_manager
+            .load_instant()
+            .map_err(|e| e.to_string())?
+            .ok_or("缓存为空")?
+            .projects
+            .into_iter()
+            .filter(|p| matches!(p.version_control, VersionControl::Git))
+            .filter(|p| project_paths.contains(&p.path))
+            .map(|p| p.path)
+            .collect()
+    };
+
+    if paths.is_empty() {
+        return Err("没有可同步的 Git 项目".to_string());
+    }
+
+    let (job_id, token) = state.jobs.start("git-sync")?;
+
+    let started_app = app.clone();
+    spawn_project_job(
+        app,
+        ProjectJob {
+            job_id: job_id.clone(),
+            token,
+            paths,
+            events: JobEvents {
+                progress: "git-sync-progress",
+                results: "git-sync-results",
+                finished: "git-sync-finished",
+            },
+            concurrency: Some(concurrency.unwrap_or(GIT_SYNC_CONCURRENCY)),
+        },
+        move |path| {
+            let _ = started_app.emit("git-sync-repo-started", path);
+            Some(GitSync::sync_one(path, pull))
+        },
+        |project, result| {
+            if let Some(git) = &result.git {
+                project.git = Some(git.clone());
+            }
+        },
+        |results| {
+            let summary = GitSync::summarize(results);
+            for failure in &summary.failures {
+                eprintln!("同步失败 [{}]: {}", failure.path, failure.message.as_deref().unwrap_or(""));
+            }
+            serde_json::to_value(summary).ok()
+        },
+    );
+
+    Ok(job_id)
+}
+
+/// 列出项目的本地分支、最近切换过的分支和工作树
+#[tauri::command]
+pub async fn list_project_branches(project_path: String) -> Result<BranchList, String> {
+    tauri::async_runtime::spawn_blocking(move || GitBranchService::list(&project_path))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// 切换项目分支（或为分支创建工作树），可选切换后直接启动
+///
+/// 工作区有修改且 `dirty_strategy` 为 refuse 时返回 `switched: false, dirty: true`，
+/// 由前端询问是否 stash 后重试。启动时使用结果中的 `project_path`。
+#[tauri::command]
+pub async fn switch_project_branch(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    project_path: String,
+    branch: String,
+    options: Option<BranchSwitchOptions>,
+) -> Result<BranchSwitchResult, String> {
+    let options = options.unwrap_or_default();
+    let repo = project_path.clone();
+    let (strategy, worktree) = (options.dirty_strategy, options.worktree);
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        GitBranchService::switch(&repo, &branch, strategy, worktree)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    if !result.switched {
+        return Ok(result);
+    }
+
+    // 刷新原仓库的 Git 状态
+    if let Ok(git) = GitService::read_info(&project_path) {
+        let _ = state.cache_manager.lock().unwrap().update(|projects| {
+            if let Some(project) = projects.iter_mut().find(|p| p.path == project_path) {
+                project.git = Some(git.clone());
+            }
+        });
+        let _ = app.emit("git-info-updated", vec![GitInfoUpdate { path: project_path, git }]);
+    }
+
+    if options.launch {
+        spawn_launch(app.clone(), result.project_path.clone(), options.launcher_id, LaunchTarget::default()).await?;
+    }
+
+    Ok(result)
+}
+
+/// 获取项目各远程对应的托管平台网页地址（origin 排在最前）
+#[tauri::command]
+pub async fn get_project_web_links(
+    state: State<'_, AppState>,
+    project_path: String,
+) -> Result<Vec<ProjectWebLinks>, String> {
+    let mappings = state.config.lock().unwrap().git_hosts.clone();
+
+    let cached = state.cache_manager.lock().unwrap()
+        .load_instant()
+        .ok()
+        .flatten()
+        .and_then(|c| c.projects.into_iter().find(|p| p.path == project_path))
+        .and_then(|p| p.git);
+    let git = match cached {
+        Some(git) if !git.remotes.is_empty() => git,
+        _ => {
+            let path = project_path.clone();
+            tauri::async_runtime::spawn_blocking(move || GitService::read_info(&path))
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string())?
+        }
+    };
+
+    let branch = if git.detached { None } else { git.branch.as_deref() };
+    let mut links: Vec<ProjectWebLinks> = git
+        .remotes
+        .iter()
+        .filter_map(|r| GitHosting::links(&r.name, &r.url, branch, &mappings))
+        .collect();
+    links.sort_by_key(|l| l.remote != "origin");
+    Ok(links)
+}
+
+/// 在浏览器中打开项目的托管平台页面
+///
+/// `remote` 为空时使用 origin（没有 origin 时使用第一个可识别的远程）
+#[tauri::command]
+pub async fn open_project_web_page(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    project_path: String,
+    page: WebPage,
+    remote: Option<String>,
+) -> Result<String, String> {
+    use tauri_plugin_shell::ShellExt;
+
+    let links = get_project_web_links(state, project_path).await?;
+    let target = match &remote {
+        Some(name) => links.iter().find(|l| &l.remote == name),
+        None => links.first(),
+    }
+    .ok_or_else(|| "没有可识别的远程仓库地址".to_string())?;
+
+    let url = GitHosting::page_url(target, page)
+        .ok_or_else(|| format!("无法识别 {} 的托管平台，请在设置中添加主机映射", target.repo))?
+        .to_string();
+
+    #[allow(deprecated)]
+    app.shell().open(&url, None).map_err(|e| e.to_string())?;
+    Ok(url)
+}
+
+/// 克隆仓库到工作区并加入项目列表
+///
+/// 进度通过 `clone-progress` 事件推送；完成后检测项目类型并写入缓存，
+/// 不在已配置工作区扫描范围内的目录作为自定义项目保存。
+#[tauri::command]
+pub async fn clone_project(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    url: String,
+    workspace: String,
+    options: Option<CloneOptions>,
+) -> Result<Project, String> {
+    let options = options.unwrap_or_default();
+    let workspace_path = Path::new(&workspace);
+    if !workspace_path.is_dir() {
+        return Err("工作区目录不存在".to_string());
+    }
+
+    let target = GitClone::target_dir(workspace_path, &url, options.layout, options.folder_name.as_deref())
+        .map_err(|e| e.to_string())?;
+    let target_path = target.to_string_lossy().into_owned();
+
+    {
+        let cache_manager = state.cache_manager.lock().unwrap();
+        let exists = cache_manager
+            .load_instant()
+            .ok()
+            .flatten()
+            .is_some_and(|c| c.projects.iter().any(|p| p.path == target_path));
+        if exists {
+            return Err("项目已存在".to_string());
+        }
+    }
+
+    let progress_app = app.clone();
+    let progress_url = url.clone();
+    let progress_path = target_path.clone();
+    let clone_target = target.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut throttle = Throttle::new(CLONE_PROGRESS_INTERVAL);
+        let mut last_phase = String::new();
+        GitClone::clone(&progress_url, &clone_target, |line| {
+            // 阶段切换时立即发送，同一阶段内节流
+            if line.phase == last_phase && line.percent != Some(100) && !throttle.ready() {
+                return;
+            }
+            last_phase = line.phase.clone();
+            let _ = progress_app.emit("clone-progress", CloneProgress {
+                url: progress_url.clone(),
+                path: progress_path.clone(),
+                phase: line.phase,
+                percent: line.percent,
+            });
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    let project = register_project(&state, &app, &target)?;
+
+    if options.launch {
+        spawn_launch(app.clone(), target_path, options.launcher_id, LaunchTarget::default()).await?;
+    }
+
+    Ok(project)
+}
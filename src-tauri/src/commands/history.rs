@@ -0,0 +1,56 @@
+use tauri::State;
+use crate::commands::project::AppState;
+use crate::models::launcher::{LaunchLogEntry, LauncherLaunchCount, ProjectSuggestion};
+use crate::services::project_suggestions::ProjectSuggestions;
+
+/// 未指定数量时返回的记录数
+const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+/// 未指定数量时返回的推荐数
+const DEFAULT_SUGGESTION_LIMIT: usize = 5;
+
+/// 获取最近的启动记录（新的在前）
+#[tauri::command]
+pub async fn get_recent_launches(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<LaunchLogEntry>, String> {
+    Ok(state.launch_log.recent(limit.unwrap_or(DEFAULT_HISTORY_LIMIT)))
+}
+
+/// 获取项目的启动记录（新的在前）
+#[tauri::command]
+pub async fn get_project_launch_history(
+    state: State<'_, AppState>,
+    project_path: String,
+    limit: Option<usize>,
+) -> Result<Vec<LaunchLogEntry>, String> {
+    Ok(state.launch_log.project_history(&project_path, limit.unwrap_or(DEFAULT_HISTORY_LIMIT)))
+}
+
+/// 按启动器统计启动次数
+#[tauri::command]
+pub async fn get_launcher_launch_counts(state: State<'_, AppState>) -> Result<Vec<LauncherLaunchCount>, String> {
+    Ok(state.launch_log.launcher_counts())
+}
+
+/// 根据启动记录推荐项目（当前时段常用的、通常与刚打开的项目一起打开的）
+#[tauri::command]
+pub async fn get_suggestions(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<ProjectSuggestion>, String> {
+    let projects = state.cache_manager.lock().unwrap()
+        .load_instant()
+        .map_err(|e| e.to_string())?
+        .map(|cache| cache.projects)
+        .unwrap_or_default();
+    let history = state.launch_log.entries();
+
+    Ok(ProjectSuggestions::suggest(
+        &history,
+        &projects,
+        chrono::Local::now().fixed_offset(),
+        limit.unwrap_or(DEFAULT_SUGGESTION_LIMIT),
+    ))
+}
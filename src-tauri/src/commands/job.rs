@@ -0,0 +1,150 @@
+use tauri::{AppHandle, Emitter, Manager, State};
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::time::Duration;
+use serde::Serialize;
+use crate::commands::project::AppState;
+use crate::models::job::{JobFinished, JobProgress, ProjectUpdate};
+use crate::models::project::Project;
+use crate::services::job_manager::{CancelToken, Throttle};
+
+/// 项目批处理任务的事件/落盘间隔
+const PROJECT_JOB_FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 项目批处理任务使用的事件名
+pub struct JobEvents {
+    pub progress: &'static str,
+    pub results: &'static str,
+    pub finished: &'static str,
+}
+
+/// 项目批处理任务描述
+pub struct ProjectJob {
+    pub job_id: String,
+    pub token: CancelToken,
+    pub paths: Vec<String>,
+    pub events: JobEvents,
+    /// 最大并发数（None 表示使用全局 rayon 线程池）
+    pub concurrency: Option<usize>,
+}
+
+/// 在后台并行处理一批项目
+///
+/// `work` 在 rayon 线程池中逐个计算结果；结果按节流间隔批量写入缓存（`apply`）
+/// 并通过 `events.results` 推送，进度通过 `events.progress` 推送。
+/// 取消后保留已写入的结果，最后发送带 `summarize` 摘要的 `events.finished`
+/// 并广播 `projects-updated`。
+pub fn spawn_project_job<T, W, A, S>(app: AppHandle, job: ProjectJob, work: W, apply: A, summarize: S)
+where
+    T: ProjectUpdate + Serialize + Clone + Send + 'static,
+    W: Fn(&str) -> Option<T> + Send + Sync + 'static,
+    A: Fn(&mut Project, &T) + Send + 'static,
+    S: FnOnce(&[T]) -> Option<serde_json::Value> + Send + 'static,
+{
+    let ProjectJob { job_id, token, paths, events, concurrency } = job;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        use rayon::prelude::*;
+
+        let total = paths.len();
+        let (tx, rx) = mpsc::channel::<Option<T>>();
+
+        // 并行计算，结果经 channel 汇总到当前线程统一处理
+        let worker_token = token.clone();
+        std::thread::spawn(move || {
+            let run = move || {
+                paths.par_iter().for_each_with(tx, |tx, path| {
+                    if worker_token.is_cancelled() {
+                        return;
+                    }
+                    let _ = tx.send(work(path));
+                });
+            };
+
+            // 指定并发数时使用独立线程池限制同时运行的数量
+            match concurrency.map(|n| rayon::ThreadPoolBuilder::new().num_threads(n.max(1)).build()) {
+                Some(Ok(pool)) => pool.install(run),
+                _ => run(),
+            }
+        });
+
+        let state = app.state::<AppState>();
+        let mut throttle = Throttle::new(PROJECT_JOB_FLUSH_INTERVAL);
+        let mut pending: Vec<T> = Vec::new();
+        let mut completed: Vec<T> = Vec::new();
+        let mut done = 0;
+        let mut error = None;
+
+        let flush = |pending: &[T], done: usize| -> Result<(), String> {
+            if !pending.is_empty() {
+                let updates: HashMap<&str, &T> = pending
+                    .iter()
+                    .map(|u| (u.path(), u))
+                    .collect();
+
+                state.cache_manager.lock().unwrap()
+                    .update(|projects| {
+                        for project in projects.iter_mut() {
+                            if let Some(update) = updates.get(project.path.as_str()) {
+                                apply(project, update);
+                            }
+                        }
+                    })
+                    .map_err(|e| e.to_string())?;
+
+                let _ = app.emit(events.results, pending);
+            }
+            let _ = app.emit(events.progress, JobProgress::new(&job_id, done, total));
+            Ok(())
+        };
+
+        for result in rx {
+            if token.is_cancelled() {
+                break;
+            }
+            done += 1;
+            pending.extend(result);
+
+            if throttle.ready() {
+                let flushed = flush(&pending, done);
+                completed.append(&mut pending);
+                if let Err(e) = flushed {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+
+        // 写入剩余结果（取消时也保留已完成的部分）
+        if error.is_none() {
+            if let Err(e) = flush(&pending, done) {
+                error = Some(e);
+            }
+        }
+        completed.append(&mut pending);
+
+        state.jobs.finish(&job_id);
+        let _ = app.emit(events.finished, JobFinished {
+            job_id: job_id.clone(),
+            cancelled: token.is_cancelled(),
+            done,
+            total,
+            error,
+            summary: summarize(&completed),
+        });
+        let _ = app.emit("projects-updated", ());
+    });
+}
+
+/// 取消后台任务
+#[tauri::command]
+pub async fn cancel_job(
+    state: State<'_, AppState>,
+    job_id: String,
+) -> Result<(), String> {
+    if state.jobs.cancel(&job_id) {
+        Ok(())
+    } else {
+        Err("任务不存在或已结束".to_string())
+    }
+}
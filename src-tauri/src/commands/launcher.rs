@@ -1,12 +1,21 @@
 use tauri::{AppHandle, Emitter, Manager, State};
-use crate::models::launcher::Launcher;
-use crate::services::launcher_service::LauncherService;
+use crate::models::launcher::{HealthStatus, LaunchFailure, LaunchLogEntry, LaunchReport, LaunchTarget, Launcher, LauncherHealth, LauncherSuggestion, ToolEnv};
+use crate::models::project::Project;
+use crate::services::command_template::{CommandTemplate, TemplateContext};
+use crate::services::launcher_service::{LaunchEvent, LauncherService};
+use crate::services::launcher_health::LauncherHealthCheck;
 use crate::commands::project::AppState;
 use uuid::Uuid;
+use std::collections::HashMap;
 use std::fs;
+use std::time::{Duration, Instant};
+use tauri_plugin_notification::NotificationExt;
+
+/// 启动的进程的 stderr 临时文件所在目录（数据目录下）
+pub(crate) const LAUNCH_STDERR_DIR: &str = "launch_stderr";
 
 /// Save config to disk
-fn save_config_to_disk(app: &AppHandle, config: &crate::models::config::Config) -> Result<(), String> {
+pub(crate) fn save_config_to_disk(app: &AppHandle, config: &crate::models::config::Config) -> Result<(), String> {
     let config_path = app.path()
         .app_data_dir()
         .map_err(|e| e.to_string())?
@@ -26,27 +35,247 @@ fn save_config_to_disk(app: &AppHandle, config: &crate::models::config::Config)
 }
 
 /// 启动项目
+///
+/// 返回启动前钩子的执行结果；启动后钩子失败时发送 `launch-hook-failed` 事件，
+/// 进程在观察期内以失败状态退出时发送 `launch-failed` 事件并弹出系统通知。
 #[tauri::command]
 pub async fn launch_project(
+    app: AppHandle,
+    project_path: String,
+    launcher_id: Option<String>,
+    target: Option<LaunchTarget>,
+) -> Result<LaunchReport, String> {
+    spawn_launch(app, project_path, launcher_id, target.unwrap_or_default()).await
+}
+
+/// 在阻塞线程中执行 `launch_path`：计算工具链环境（如首次求值 flake）和钩子都可能运行较长时间
+pub(crate) async fn spawn_launch(
+    app: AppHandle,
+    project_path: String,
+    launcher_id: Option<String>,
+    target: LaunchTarget,
+) -> Result<LaunchReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        launch_path(&app.state::<AppState>(), &app, &project_path, launcher_id, &target)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 预览启动器对某个项目实际执行的参数列表（占位符已展开）
+#[tauri::command]
+pub async fn preview_launcher_command(
     state: State<'_, AppState>,
     project_path: String,
     launcher_id: Option<String>,
-) -> Result<(), String> {
-    let config = state.config.lock().unwrap();
+    target: Option<LaunchTarget>,
+) -> Result<Vec<String>, String> {
+    let target = target.unwrap_or_default();
+    let resolved = resolve_launch(&state, &project_path, launcher_id, &target)?;
+    // 预览不计算工具链环境（可能较慢），它不影响参数列表
+    LauncherService::prepare(&resolved.launcher, &project_path, &resolved.vars, resolved.terminal.as_deref(), None)
+        .map(|plan| plan.settings.argv)
+        .map_err(|e| e.to_string())
+}
 
-    let launcher = if let Some(id) = launcher_id {
-        config.launchers.iter()
-            .find(|l| l.id == id)
-            .ok_or("启动器不存在")?
-    } else {
-        config.launchers.first()
-            .ok_or("没有配置启动器")?
+/// 使用指定（或默认）启动器打开目录，执行启动器和项目上配置的钩子
+///
+/// 每次启动（无论成功与否）都会把实际使用的参数、工作目录、环境设置和耗时写入启动日志。
+/// 会阻塞较长时间，异步命令中通过 `spawn_launch` 调用。
+pub(crate) fn launch_path(
+    state: &AppState,
+    app: &AppHandle,
+    project_path: &str,
+    launcher_id: Option<String>,
+    target: &LaunchTarget,
+) -> Result<LaunchReport, String> {
+    let started = Instant::now();
+    let resolved = match resolve_launch(state, project_path, launcher_id.clone(), target) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            // 找不到启动器等情况也记入日志，启动器名称未知
+            state.launch_log.append(&LaunchLogEntry {
+                id: Uuid::new_v4().to_string(),
+                time: chrono::Utc::now().to_rfc3339(),
+                project_path: project_path.to_string(),
+                launcher_id: launcher_id.unwrap_or_default(),
+                launcher_name: String::new(),
+                settings: None,
+                success: false,
+                error: Some(e.clone()),
+                duration_ms: started.elapsed().as_millis() as u64,
+            });
+            return Err(e);
+        }
     };
+    let project_hooks = resolved.project.map(|p| p.hooks).unwrap_or_default();
 
-    LauncherService::launch(launcher, &project_path)
-        .map_err(|e| e.to_string())?;
+    let tool_env = state.env_resolver.resolve(project_path);
+    let plan = LauncherService::prepare(
+        &resolved.launcher,
+        project_path,
+        &resolved.vars,
+        resolved.terminal.as_deref(),
+        tool_env.as_ref(),
+    );
+    let mut entry = LaunchLogEntry {
+        id: Uuid::new_v4().to_string(),
+        time: chrono::Utc::now().to_rfc3339(),
+        project_path: project_path.to_string(),
+        launcher_id: resolved.launcher.id.clone(),
+        launcher_name: resolved.launcher.name.clone(),
+        settings: None,
+        success: true,
+        error: None,
+        duration_ms: 0,
+    };
+    let stderr_dir = app.path().app_data_dir().map_err(|e| e.to_string())?.join(LAUNCH_STDERR_DIR);
+    let app = app.clone();
+    let late_entry = entry.clone();
+    let result = plan.as_ref()
+        .map_err(|e| e.to_string())
+        .and_then(|plan| {
+            LauncherService::launch(plan, &project_hooks, resolved.grace, &stderr_dir, move |event| match event {
+                LaunchEvent::PostHooks(results) => {
+                    for result in results.into_iter().filter(|r| !r.success) {
+                        let _ = app.emit("launch-hook-failed", result);
+                    }
+                }
+                LaunchEvent::Failed { exit_code, stderr } => {
+                    let failure = LaunchFailure {
+                        time: chrono::Utc::now().to_rfc3339(),
+                        project_path: late_entry.project_path.clone(),
+                        launcher_id: late_entry.launcher_id.clone(),
+                        launcher_name: late_entry.launcher_name.clone(),
+                        exit_code,
+                        stderr,
+                    };
+                    // 以相同 id 追加失败记录，读取日志时合并到本次启动上
+                    app.state::<AppState>().launch_log.append(&LaunchLogEntry {
+                        success: false,
+                        error: Some(failure_summary(&failure)),
+                        ..late_entry.clone()
+                    });
+                    notify_launch_failure(&app, &failure);
+                    let _ = app.emit("launch-failed", failure);
+                }
+            })
+            .map_err(|e| e.to_string())
+        });
 
-    Ok(())
+    entry.settings = plan.ok().map(|plan| plan.settings);
+    entry.success = result.is_ok();
+    entry.error = result.as_ref().err().cloned();
+    entry.duration_ms = started.elapsed().as_millis() as u64;
+    state.launch_log.append(&entry);
+
+    result
+}
+
+/// 启动后失败的简要说明（写入启动日志）
+fn failure_summary(failure: &LaunchFailure) -> String {
+    let reason = match failure.exit_code {
+        Some(code) => format!("启动后退出，退出码 {}", code),
+        None => "启动后进程被终止".to_string(),
+    };
+    match failure.stderr.trim() {
+        "" => reason,
+        stderr => format!("{}:\n{}", reason, stderr),
+    }
+}
+
+/// 启动失败的系统通知，正文包含标准错误输出的最后一行
+fn notify_launch_failure(app: &AppHandle, failure: &LaunchFailure) {
+    eprintln!("[Launcher] {} 启动后退出 (code {:?}): {}", failure.launcher_name, failure.exit_code, failure.stderr);
+
+    let name = std::path::Path::new(&failure.project_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| failure.project_path.clone());
+    let mut body = match failure.exit_code {
+        Some(code) => format!("{} 打开 {} 失败（退出码 {}）", failure.launcher_name, name, code),
+        None => format!("{} 打开 {} 失败（进程被终止）", failure.launcher_name, name),
+    };
+    if let Some(line) = failure.stderr.lines().rev().map(str::trim).find(|l| !l.is_empty()) {
+        body.push('\n');
+        body.push_str(line);
+    }
+    let _ = app.notification()
+        .builder()
+        .title("Project Launcher")
+        .body(body)
+        .show();
+}
+
+/// 获取项目的工具链环境（direnv / nix / mise / asdf / nvm），`refresh` 时丢弃缓存重新计算
+#[tauri::command]
+pub async fn get_project_env(
+    app: AppHandle,
+    project_path: String,
+    refresh: Option<bool>,
+) -> Result<Option<ToolEnv>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let resolver = &app.state::<AppState>().env_resolver;
+        if refresh.unwrap_or(false) {
+            resolver.invalidate(&project_path);
+        }
+        resolver.resolve(&project_path)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// 一次启动所需的启动器、项目和占位符取值
+struct ResolvedLaunch {
+    launcher: Launcher,
+    project: Option<Project>,
+    vars: HashMap<String, String>,
+    /// 设置中配置的终端命令
+    terminal: Option<String>,
+    /// 启动后观察进程的时长
+    grace: Duration,
+}
+
+/// 查找启动器和缓存中的项目，计算占位符的值
+fn resolve_launch(
+    state: &AppState,
+    project_path: &str,
+    launcher_id: Option<String>,
+    target: &LaunchTarget,
+) -> Result<ResolvedLaunch, String> {
+    let (launcher, workspaces, terminal, grace) = {
+        let config = state.config.lock().unwrap();
+        let launcher = if let Some(id) = launcher_id {
+            config.launchers.iter()
+                .find(|l| l.id == id)
+                .ok_or("启动器不存在")?
+        } else {
+            config.launchers.first()
+                .ok_or("没有配置启动器")?
+        };
+        (
+            launcher.clone(),
+            config.workspaces.clone(),
+            config.terminal.clone(),
+            Duration::from_millis(config.launch_grace_ms),
+        )
+    };
+
+    let project = state.cache_manager.lock().unwrap()
+        .load_instant()
+        .ok()
+        .flatten()
+        .and_then(|cache| cache.projects.into_iter().find(|p| p.path == project_path));
+
+    let vars = CommandTemplate::variables(&TemplateContext {
+        project_path,
+        project: project.as_ref(),
+        workspaces: &workspaces,
+        file: target.file.as_deref(),
+        line: target.line,
+    });
+
+    Ok(ResolvedLaunch { launcher, project, vars, terminal, grace })
 }
 
 /// 获取所有启动器
@@ -56,6 +285,54 @@ pub async fn get_launchers(state: State<'_, AppState>) -> Result<Vec<Launcher>,
     Ok(config.launchers.clone())
 }
 
+/// 自动发现已安装的编辑器和终端（目前仅支持 Linux），跳过已配置的程序
+#[tauri::command]
+pub async fn discover_launchers(state: State<'_, AppState>) -> Result<Vec<LauncherSuggestion>, String> {
+    let launchers = state.config.lock().unwrap().launchers.clone();
+
+    #[cfg(target_os = "linux")]
+    {
+        use crate::services::launcher_discovery::{DiscoveryEnv, LauncherDiscovery};
+        Ok(LauncherDiscovery::discover(&DiscoveryEnv::from_system(), &launchers))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = launchers;
+        Ok(Vec::new())
+    }
+}
+
+/// 检查所有启动器能否启动，结果保存在状态中并通过 `launchers-health` 事件通知
+#[tauri::command]
+pub async fn check_launchers(state: State<'_, AppState>, app: AppHandle) -> Result<Vec<LauncherHealth>, String> {
+    Ok(refresh_launcher_health(&state, &app))
+}
+
+/// 获取最近一次的启动器健康检查结果
+#[tauri::command]
+pub async fn get_launcher_health(state: State<'_, AppState>) -> Result<Vec<LauncherHealth>, String> {
+    Ok(state.launcher_health.lock().unwrap().clone())
+}
+
+/// 重新检查所有启动器（启动时、导入配置和修改启动器后调用）
+pub(crate) fn refresh_launcher_health(state: &AppState, app: &AppHandle) -> Vec<LauncherHealth> {
+    let (launchers, terminal) = {
+        let config = state.config.lock().unwrap();
+        (config.launchers.clone(), config.terminal.clone())
+    };
+    let health = LauncherHealthCheck::check_all(&launchers, terminal.as_deref());
+    for (launcher, result) in launchers.iter().zip(&health) {
+        if result.status == HealthStatus::Broken {
+            eprintln!("[Launcher] 启动器 {} 无法使用: {}", launcher.name, result.reasons.join("; "));
+        }
+    }
+
+    *state.launcher_health.lock().unwrap() = health.clone();
+    let _ = app.emit("launchers-health", &health);
+    health
+}
+
 /// 添加启动器
 #[tauri::command]
 pub async fn add_launcher(
@@ -70,9 +347,11 @@ pub async fn add_launcher(
 
     // Save to disk
     save_config_to_disk(&app, &config)?;
+    drop(config);
 
     // 通知所有窗口启动器列表已更新
     let _ = app.emit("launchers-updated", ());
+    refresh_launcher_health(&state, &app);
 
     Ok(launcher)
 }
@@ -91,9 +370,11 @@ pub async fn update_launcher(
 
         // Save to disk
         save_config_to_disk(&app, &config)?;
+        drop(config);
 
         // 通知所有窗口启动器列表已更新
         let _ = app.emit("launchers-updated", ());
+        refresh_launcher_health(&state, &app);
 
         Ok(())
     } else {
@@ -130,9 +411,11 @@ pub async fn remove_launcher(
             let _ = cache_manager.save(projects);
         }
     }
+    drop(cache_manager);
 
     // 通知所有窗口启动器列表已更新
     let _ = app.emit("launchers-updated", ());
+    refresh_launcher_health(&state, &app);
 
     Ok(())
 }
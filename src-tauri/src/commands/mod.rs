@@ -1,6 +1,14 @@
 pub mod project;
 pub mod launcher;
+pub mod history;
 pub mod shortcut;
 pub mod window;
 pub mod config;
 pub mod export;
+pub mod job;
+pub mod git;
+pub mod template;
+pub mod disk;
+pub mod process;
+pub mod task;
+pub mod service;
@@ -0,0 +1,85 @@
+use tauri::{AppHandle, Emitter, Manager, State};
+use std::time::Duration;
+use crate::commands::project::AppState;
+use crate::models::process::RunningProject;
+use crate::services::process_scanner::ProcessScanner;
+
+/// 进程监视的轮询间隔
+const PROCESS_MONITOR_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 每隔多少次轮询重新读取项目列表
+const PROJECT_RELOAD_TICKS: u32 = 6;
+
+/// 获取有进程在运行的项目
+#[tauri::command]
+pub async fn get_running_projects(state: State<'_, AppState>) -> Result<Vec<RunningProject>, String> {
+    let projects = project_roots(&state);
+    tauri::async_runtime::spawn_blocking(move || ProcessScanner::scan(&projects))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 终止项目中的进程（只允许终止属于某个项目的进程）
+#[tauri::command]
+pub async fn terminate_project_process(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    pid: u32,
+    force: Option<bool>,
+) -> Result<(), String> {
+    let projects = project_roots(&state);
+
+    // 扫描进程和等待进程退出都会阻塞，放到阻塞线程中执行
+    tauri::async_runtime::spawn_blocking(move || {
+        let running = ProcessScanner::scan(&projects);
+        if !running.iter().any(|p| p.processes.iter().any(|proc| proc.pid == pid)) {
+            return Err("进程不存在或不属于任何项目".to_string());
+        }
+
+        ProcessScanner::terminate(pid, force.unwrap_or(false)).map_err(|e| e.to_string())?;
+
+        // 进程退出需要一点时间，稍后推送最新状态
+        tauri::async_runtime::spawn_blocking(move || {
+            std::thread::sleep(Duration::from_millis(500));
+            let _ = app.emit("running-projects-changed", ProcessScanner::scan(&projects));
+        });
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 启动后台进程监视，运行中的项目变化时发送 `running-projects-changed`
+pub fn start_process_monitor(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut projects = Vec::new();
+        let mut last: Vec<RunningProject> = Vec::new();
+        let mut tick = 0;
+
+        loop {
+            if tick % PROJECT_RELOAD_TICKS == 0 {
+                projects = project_roots(&app.state::<AppState>());
+            }
+            tick += 1;
+
+            let running = ProcessScanner::scan(&projects);
+            if running != last {
+                let _ = app.emit("running-projects-changed", &running);
+                last = running;
+            }
+
+            std::thread::sleep(PROCESS_MONITOR_INTERVAL);
+        }
+    });
+}
+
+/// 缓存中的 (项目路径, 项目名)
+fn project_roots(state: &AppState) -> Vec<(String, String)> {
+    let cache_manager = state.cache_manager.lock().unwrap();
+    cache_manager
+        .load_instant()
+        .ok()
+        .flatten()
+        .map(|c| c.projects.into_iter().map(|p| (p.path, p.name)).collect())
+        .unwrap_or_default()
+}
@@ -2,23 +2,60 @@ use tauri::{AppHandle, Emitter, Manager, State};
 use std::sync::Mutex;
 use std::collections::HashMap;
 use crate::services::{scanner::ProjectScanner, cache_manager::CacheManager, type_detector::TypeDetector};
+use crate::services::metadata_extractor::MetadataExtractor;
+use crate::services::job_manager::JobManager;
+use crate::services::task_runner::TaskRunner;
+use crate::services::supervisor::Supervisor;
+use crate::services::launch_log::LaunchLog;
+use crate::services::env_resolver::EnvResolver;
+use crate::commands::job::{spawn_project_job, JobEvents, ProjectJob};
 use crate::models::{project::{Project, VersionControl}, config::Config};
+use crate::models::launcher::{LaunchHooks, LauncherHealth};
+use crate::models::job::ProjectTypeUpdate;
+use crate::models::language::LanguageStatsUpdate;
+use crate::services::language_stats::LanguageStatsService;
+use crate::services::project_ranking::ProjectRanking;
 
 pub struct AppState {
     pub cache_manager: Mutex<CacheManager>,
     pub config: Mutex<Config>,
+    pub jobs: JobManager,
+    pub tasks: TaskRunner,
+    pub supervisor: Supervisor,
+    pub launch_log: LaunchLog,
+    pub env_resolver: EnvResolver,
+    /// 最近一次的启动器健康检查结果
+    pub launcher_health: Mutex<Vec<LauncherHealth>>,
 }
 
-/// 获取缓存的项目列表
+/// 获取缓存的项目列表（已按置顶和排序设置排好序）
 #[tauri::command]
 pub async fn get_cached_projects(state: State<'_, AppState>) -> Result<Vec<Project>, String> {
-    let cache_manager = state.cache_manager.lock().unwrap();
+    let projects = {
+        let cache_manager = state.cache_manager.lock().unwrap();
+        match cache_manager.load_instant() {
+            Ok(Some(cache)) => cache.projects,
+            Ok(None) => Vec::new(),
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+
+    Ok(sort_projects(&state, projects))
+}
 
-    match cache_manager.load_instant() {
-        Ok(Some(cache)) => Ok(cache.projects),
-        Ok(None) => Ok(Vec::new()),
-        Err(e) => Err(e.to_string()),
+/// 按配置的排序方式排序，置顶项目在前；frecency 模式按（内存中的）启动日志计分
+fn sort_projects(state: &AppState, mut projects: Vec<Project>) -> Vec<Project> {
+    let (sort_by, buckets) = {
+        let config = state.config.lock().unwrap();
+        (config.project_sort_by.clone(), config.frecency_buckets.clone())
+    };
+    let now = chrono::Utc::now();
+    if sort_by == "frecency" {
+        state.launch_log.with_entries(|history| ProjectRanking::sort(&mut projects, &sort_by, history, &buckets, now));
+    } else {
+        ProjectRanking::sort(&mut projects, &sort_by, &[], &buckets, now);
     }
+    projects
 }
 
 /// 强制重新扫描项目（保留用户数据和自定义项目）
@@ -57,7 +94,7 @@ pub async fn force_rescan(state: State<'_, AppState>) -> Result<Vec<Project>, St
         .map(|p| p.path.clone())
         .collect();
 
-    // 合并：保留用户数据（hits, launcher_id, top, project_type, last_opened, alias）
+    // 合并：保留用户数据（hits, launcher_id, top, project_type, last_opened, alias, hooks）及已采集的元数据
     let mut merged: Vec<Project> = new_projects
         .into_iter()
         .map(|mut new| {
@@ -68,6 +105,11 @@ pub async fn force_rescan(state: State<'_, AppState>) -> Result<Vec<Project>, St
                 new.project_type = old.project_type.clone();
                 new.last_opened = old.last_opened.clone();
                 new.alias = old.alias.clone();
+                new.metadata = old.metadata.clone();
+                new.git = old.git.clone();
+                new.disk_usage = old.disk_usage.clone();
+                new.language_stats = old.language_stats.clone();
+                new.hooks = old.hooks.clone();
             }
             new
         })
@@ -83,8 +125,9 @@ pub async fn force_rescan(state: State<'_, AppState>) -> Result<Vec<Project>, St
     // 保存到缓存
     cache_manager.save(merged.clone())
         .map_err(|e| e.to_string())?;
+    drop(cache_manager);
 
-    Ok(merged)
+    Ok(sort_projects(&state, merged))
 }
 
 /// 检测单个项目类型
@@ -94,38 +137,123 @@ pub async fn detect_project_type(project_path: String) -> Result<String, String>
         .ok_or_else(|| "无法检测项目类型".to_string())
 }
 
-/// 批量检测项目类型
+/// 批量检测项目类型和描述信息（后台任务，立即返回任务 ID）
+///
+/// 进度通过 `type-detection-progress` 事件节流发送，检测结果以
+/// `type-detection-results` 事件批量推送并增量写入缓存，
+/// 结束时发送 `type-detection-finished`。可通过 `cancel_job` 取消。
 #[tauri::command]
 pub async fn batch_detect_types(
     state: State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<(), String> {
-    use rayon::prelude::*;
-
-    let cache_manager = state.cache_manager.lock().unwrap();
-    let cache = cache_manager.load_instant()
-        .map_err(|e| e.to_string())?
-        .ok_or("缓存为空")?;
-
-    let total = cache.projects.len();
-    let mut updated_projects = cache.projects;
-
-    // 并行检测类型
-    updated_projects.par_iter_mut().enumerate().for_each(|(idx, project)| {
-        if let Some(project_type) = TypeDetector::detect(&project.path) {
-            project.project_type = Some(project_type);
-        }
+) -> Result<String, String> {
+    // 仅在读取快照时短暂持有锁
+    let paths: Vec<String> = {
+        let cache_manager = state.cache_manager.lock().unwrap();
+        cache_manager.load_instant()
+            .map_err(|e| e.to_string())?
+            .ok_or("缓存为空")?
+            .projects
+            .into_iter()
+            .map(|p| p.path)
+            .collect()
+    };
 
-        // 发送进度事件
-        let progress = ((idx + 1) as f32 / total as f32 * 100.0) as u32;
-        let _ = app.emit("type-detection-progress", progress);
-    });
+    let (job_id, token) = state.jobs.start("type-detection")?;
+
+    spawn_project_job(
+        app,
+        ProjectJob {
+            job_id: job_id.clone(),
+            token,
+            paths,
+            events: JobEvents {
+                progress: "type-detection-progress",
+                results: "type-detection-results",
+                finished: "type-detection-finished",
+            },
+            concurrency: None,
+        },
+        |path| {
+            Some(ProjectTypeUpdate {
+                path: path.to_string(),
+                project_type: TypeDetector::detect(path),
+                metadata: MetadataExtractor::extract(path),
+            })
+        },
+        |project, update| {
+            if let Some(project_type) = &update.project_type {
+                project.project_type = Some(project_type.clone());
+            }
+            project.metadata = update.metadata.clone();
+        },
+        |_| None,
+    );
 
-    // 保存更新后的缓存
-    cache_manager.save(updated_projects)
-        .map_err(|e| e.to_string())?;
+    Ok(job_id)
+}
 
-    Ok(())
+/// 后台统计项目语言构成（`project_paths` 为空时处理全部项目），立即返回任务 ID
+///
+/// 文件指纹与缓存一致的项目会被跳过（`force` 为 true 时全部重新统计）。
+/// 结果通过 `language-stats-results` 事件推送并写入缓存，结束时发送 `language-stats-finished`。
+#[tauri::command]
+pub async fn calculate_language_stats(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    project_paths: Option<Vec<String>>,
+    force: Option<bool>,
+) -> Result<String, String> {
+    let ignore_dirs = state.config.lock().unwrap().ignore_dirs.clone();
+    let force = force.unwrap_or(false);
+
+    // 路径 -> 已缓存的指纹
+    let fingerprints: HashMap<String, Option<String>> = {
+        let cache_manager = state.cache_manager.lock().unwrap();
+        cache_manager.load_instant()
+            .map_err(|e| e.to_string())?
+            .ok_or("缓存为空")?
+            .projects
+            .into_iter()
+            .filter(|p| project_paths.as_ref().is_none_or(|paths| paths.contains(&p.path)))
+            .map(|p| (p.path, p.language_stats.map(|s| s.fingerprint)))
+            .collect()
+    };
+    let paths: Vec<String> = fingerprints.keys().cloned().collect();
+
+    let (job_id, token) = state.jobs.start("language-stats")?;
+
+    spawn_project_job(
+        app,
+        ProjectJob {
+            job_id: job_id.clone(),
+            token,
+            paths,
+            events: JobEvents {
+                progress: "language-stats-progress",
+                results: "language-stats-results",
+                finished: "language-stats-finished",
+            },
+            concurrency: None,
+        },
+        move |path| {
+            let fingerprint = LanguageStatsService::fingerprint(path, &ignore_dirs);
+            let cached = fingerprints.get(path).cloned().flatten();
+            if !force && cached.as_deref() == Some(fingerprint.as_str()) {
+                return None;
+            }
+            Some(LanguageStatsUpdate {
+                path: path.to_string(),
+                stats: LanguageStatsService::compute(path, &ignore_dirs, fingerprint),
+            })
+        },
+        |project, update| {
+            project.language_stats = Some(update.stats.clone());
+        },
+        |_| None,
+    );
+
+    Ok(job_id)
 }
 
 /// 更新项目打开次数
@@ -239,6 +367,39 @@ pub async fn update_project_alias(
     Ok(())
 }
 
+/// 更新项目的启动钩子
+#[tauri::command]
+pub async fn update_project_hooks(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    project_path: String,
+    hooks: LaunchHooks,
+) -> Result<(), String> {
+    if hooks.pre_launch.iter().chain(&hooks.post_launch).any(|h| h.command.trim().is_empty()) {
+        return Err("钩子命令不能为空".to_string());
+    }
+
+    let cache_manager = state.cache_manager.lock().unwrap();
+    let cache = cache_manager.load_instant()
+        .map_err(|e| e.to_string())?
+        .ok_or("缓存为空")?;
+
+    let mut projects = cache.projects;
+    if let Some(project) = projects.iter_mut().find(|p| p.path == project_path) {
+        project.hooks = hooks;
+    } else {
+        return Err("项目不存在".to_string());
+    }
+
+    cache_manager.save(projects)
+        .map_err(|e| e.to_string())?;
+
+    // 通知所有窗口项目列表已更新
+    let _ = app.emit("projects-updated", ());
+
+    Ok(())
+}
+
 /// 手动添加自定义项目文件夹
 #[tauri::command]
 pub async fn add_custom_project(
@@ -290,13 +451,15 @@ pub async fn add_custom_project(
         VersionControl::None
     };
 
-    // 检测项目类型
+    // 检测项目类型和描述信息
     let project_type = TypeDetector::detect(&folder_path);
+    let metadata = MetadataExtractor::extract(&folder_path);
 
     // 创建自定义项目
     let mut project = Project::new(folder_path, name, vc);
     project.is_custom = true;
     project.project_type = project_type;
+    project.metadata = metadata;
 
     // 添加到缓存
     projects.push(project.clone());
@@ -308,6 +471,53 @@ pub async fn add_custom_project(
     Ok(project)
 }
 
+/// 将新创建（克隆或模板生成）的目录加入项目列表
+///
+/// 检测项目类型、描述和 Git 状态；不在已配置工作区扫描深度内的目录标记为自定义项目，
+/// 以免重新扫描时丢失
+pub(crate) fn register_project(
+    state: &AppState,
+    app: &AppHandle,
+    path: &std::path::Path,
+) -> Result<Project, String> {
+    use crate::services::git_service::GitService;
+
+    let path_str = path.to_string_lossy().into_owned();
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(|s| s.to_string())
+        .ok_or("无法获取文件夹名称")?;
+
+    let vc = if path.join(".git").exists() { VersionControl::Git } else { VersionControl::None };
+    let mut project = Project::new(path_str.clone(), name, vc);
+    project.project_type = TypeDetector::detect(&path_str);
+    project.metadata = MetadataExtractor::extract(&path_str);
+    project.git = GitService::read_info(&path_str).ok();
+
+    // 扫描器只遍历工作区内 4 层目录
+    let workspaces = state.config.lock().unwrap().workspaces.clone();
+    project.is_custom = !workspaces.iter().any(|ws| {
+        path.strip_prefix(ws)
+            .is_ok_and(|rel| rel.components().count() <= 4)
+    });
+
+    {
+        let cache_manager = state.cache_manager.lock().unwrap();
+        let mut projects = cache_manager
+            .load_instant()
+            .map_err(|e| e.to_string())?
+            .map(|c| c.projects)
+            .unwrap_or_default();
+        projects.retain(|p| p.path != path_str);
+        projects.push(project.clone());
+        cache_manager.save(projects).map_err(|e| e.to_string())?;
+    }
+
+    let _ = app.emit("projects-updated", ());
+    Ok(project)
+}
+
 /// 删除自定义项目
 #[tauri::command]
 pub async fn remove_custom_project(
@@ -360,6 +570,8 @@ pub async fn reset_project_hits(
     }
 
     cache_manager.save(projects).map_err(|e| e.to_string())?;
+    // 启动记录同时清除，避免按记录统计时又恢复
+    state.launch_log.remove_project(&project_path)?;
 
     // 通知所有窗口项目列表已更新
     let _ = app.emit("projects-updated", ());
@@ -386,6 +598,7 @@ pub async fn reset_all_project_hits(
     }
 
     cache_manager.save(projects).map_err(|e| e.to_string())?;
+    state.launch_log.clear()?;
 
     // 通知所有窗口项目列表已更新
     let _ = app.emit("projects-updated", ());
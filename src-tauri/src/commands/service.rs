@@ -0,0 +1,64 @@
+use tauri::{AppHandle, Manager, State};
+use crate::commands::project::AppState;
+use crate::models::service::{ServiceInfo, ServiceLogLine};
+use crate::models::task::TaskSource;
+use crate::services::task_discovery::TaskDiscovery;
+
+/// 以托管服务方式启动项目任务（开发服务器、watch 等）
+///
+/// 与 `run_project_task` 一样只接受从项目文件中发现的任务。
+/// 输出通过 `service-log` 事件推送，状态变化发送 `services-changed`。
+#[tauri::command]
+pub async fn start_service(
+    state: State<'_, AppState>,
+    project_path: String,
+    task_name: String,
+    source: TaskSource,
+    auto_restart: Option<bool>,
+) -> Result<ServiceInfo, String> {
+    let task = TaskDiscovery::discover(&project_path)
+        .into_iter()
+        .find(|t| t.name == task_name && t.source == source)
+        .ok_or("任务不存在")?;
+
+    state.supervisor
+        .start(&project_path, &task, auto_restart.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
+
+/// 停止服务
+#[tauri::command]
+pub async fn stop_service(state: State<'_, AppState>, service_id: String) -> Result<(), String> {
+    state.supervisor.stop(&service_id).map_err(|e| e.to_string())
+}
+
+/// 重启服务（等待旧进程退出后重新启动）
+#[tauri::command]
+pub async fn restart_service(app: AppHandle, service_id: String) -> Result<ServiceInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        app.state::<AppState>().supervisor.restart(&service_id).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 移除已停止的服务
+#[tauri::command]
+pub async fn remove_service(state: State<'_, AppState>, service_id: String) -> Result<(), String> {
+    state.supervisor.remove(&service_id).map_err(|e| e.to_string())
+}
+
+/// 获取所有托管服务
+#[tauri::command]
+pub async fn list_services(state: State<'_, AppState>) -> Result<Vec<ServiceInfo>, String> {
+    Ok(state.supervisor.list())
+}
+
+/// 获取服务最近的输出
+#[tauri::command]
+pub async fn get_service_logs(
+    state: State<'_, AppState>,
+    service_id: String,
+) -> Result<Vec<ServiceLogLine>, String> {
+    state.supervisor.logs(&service_id).map_err(|e| e.to_string())
+}
@@ -0,0 +1,66 @@
+use tauri::{AppHandle, Emitter, Manager, State};
+use crate::commands::project::AppState;
+use crate::models::task::{ProjectTask, TaskRun, TaskSource};
+use crate::services::task_discovery::TaskDiscovery;
+use crate::services::task_runner::TaskEvent;
+
+/// 发现项目中可运行的任务
+#[tauri::command]
+pub async fn discover_project_tasks(project_path: String) -> Result<Vec<ProjectTask>, String> {
+    tauri::async_runtime::spawn_blocking(move || TaskDiscovery::discover(&project_path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 运行项目任务
+///
+/// 只运行从项目文件中发现的任务（按名称和来源匹配），不接受任意命令。
+/// 输出逐行通过 `task-output` 事件推送，结束时发送 `task-finished`。
+#[tauri::command]
+pub async fn run_project_task(
+    app: AppHandle,
+    project_path: String,
+    task_name: String,
+    source: TaskSource,
+) -> Result<TaskRun, String> {
+    // 发现任务需要读取项目文件，启动进程也可能较慢，放到阻塞线程中执行
+    tauri::async_runtime::spawn_blocking(move || {
+        let task = TaskDiscovery::discover(&project_path)
+            .into_iter()
+            .find(|t| t.name == task_name && t.source == source)
+            .ok_or("任务不存在")?;
+
+        let events = app.clone();
+        app.state::<AppState>().tasks
+            .start(&project_path, &task, move |event| {
+                let _ = match event {
+                    TaskEvent::Output(output) => events.emit("task-output", output),
+                    TaskEvent::Finished(run) => events.emit("task-finished", run),
+                };
+            })
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 停止正在运行的任务
+#[tauri::command]
+pub async fn stop_task(state: State<'_, AppState>, run_id: String) -> Result<(), String> {
+    state.tasks.stop(&run_id).map_err(|e| e.to_string())
+}
+
+/// 获取正在运行的任务
+#[tauri::command]
+pub async fn get_running_tasks(state: State<'_, AppState>) -> Result<Vec<TaskRun>, String> {
+    Ok(state.tasks.running())
+}
+
+/// 获取项目最近的任务运行记录
+#[tauri::command]
+pub async fn get_task_history(
+    state: State<'_, AppState>,
+    project_path: String,
+) -> Result<Vec<TaskRun>, String> {
+    Ok(state.tasks.history(&project_path))
+}
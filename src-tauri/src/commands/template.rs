@@ -0,0 +1,119 @@
+use tauri::{AppHandle, Emitter, State};
+use std::path::Path;
+use uuid::Uuid;
+use crate::commands::project::{register_project, AppState};
+use crate::commands::launcher::{save_config_to_disk, spawn_launch};
+use crate::models::template::{ProjectTemplate, TemplateCreateRequest, TemplateCreateResult};
+use crate::models::launcher::LaunchTarget;
+use crate::services::template_service::TemplateService;
+
+/// 获取所有模板
+#[tauri::command]
+pub async fn get_templates(state: State<'_, AppState>) -> Result<Vec<ProjectTemplate>, String> {
+    let config = state.config.lock().unwrap();
+    Ok(config.templates.clone())
+}
+
+/// 添加模板
+#[tauri::command]
+pub async fn add_template(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    mut template: ProjectTemplate,
+) -> Result<ProjectTemplate, String> {
+    let mut config = state.config.lock().unwrap();
+
+    template.id = Uuid::new_v4().to_string();
+    config.templates.push(template.clone());
+    save_config_to_disk(&app, &config)?;
+
+    let _ = app.emit("templates-updated", ());
+    Ok(template)
+}
+
+/// 更新模板
+#[tauri::command]
+pub async fn update_template(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    template: ProjectTemplate,
+) -> Result<(), String> {
+    let mut config = state.config.lock().unwrap();
+
+    let idx = config.templates.iter()
+        .position(|t| t.id == template.id)
+        .ok_or("模板不存在")?;
+    config.templates[idx] = template;
+    save_config_to_disk(&app, &config)?;
+
+    let _ = app.emit("templates-updated", ());
+    Ok(())
+}
+
+/// 删除模板
+#[tauri::command]
+pub async fn remove_template(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    template_id: String,
+) -> Result<(), String> {
+    let mut config = state.config.lock().unwrap();
+    config.templates.retain(|t| t.id != template_id);
+    save_config_to_disk(&app, &config)?;
+
+    let _ = app.emit("templates-updated", ());
+    Ok(())
+}
+
+/// 从模板创建项目
+///
+/// 在工作区下生成 `<name>` 目录、替换模板变量、`git init`，再依次执行创建后命令。
+/// 创建后命令失败不会删除项目，结果中返回每条命令的输出。
+#[tauri::command]
+pub async fn create_project_from_template(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    request: TemplateCreateRequest,
+) -> Result<TemplateCreateResult, String> {
+    let name = request.name.trim().to_string();
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        return Err("项目名无效".to_string());
+    }
+    let workspace = Path::new(&request.workspace);
+    if !workspace.is_dir() {
+        return Err("工作区目录不存在".to_string());
+    }
+
+    let template = state.config.lock().unwrap()
+        .templates
+        .iter()
+        .find(|t| t.id == request.template_id)
+        .cloned()
+        .ok_or("模板不存在")?;
+
+    let target = workspace.join(&name);
+    if target.exists() {
+        return Err("目标目录已存在".to_string());
+    }
+
+    let dir = target.clone();
+    let author = request.author.clone();
+    let extra = request.variables.clone();
+    let post_create = tauri::async_runtime::spawn_blocking(move || -> anyhow::Result<_> {
+        let vars = TemplateService::variables(&name, author.as_deref(), &extra);
+        TemplateService::materialize(&template, &dir, &vars)?;
+        TemplateService::git_init(&dir)?;
+        Ok(TemplateService::run_post_create(&template.post_create, &dir, &vars))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    let project = register_project(&state, &app, &target)?;
+
+    if request.launch {
+        spawn_launch(app.clone(), project.path.clone(), request.launcher_id, LaunchTarget::default()).await?;
+    }
+
+    Ok(TemplateCreateResult { project, post_create })
+}
@@ -1,5 +1,6 @@
 use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
 
+use crate::commands::project::AppState;
 use crate::services::monitor_utils;
 
 #[cfg(target_os = "macos")]
@@ -129,6 +130,11 @@ pub async fn hide_settings_window(app: AppHandle) -> Result<(), String> {
 /// 退出应用
 #[tauri::command]
 pub async fn quit_app(app: AppHandle) -> Result<(), String> {
+    // 等待托管服务退出可能需要数秒，放到阻塞线程中执行
+    let handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || handle.state::<AppState>().supervisor.stop_all())
+        .await
+        .map_err(|e| e.to_string())?;
     app.exit(0);
     Ok(())
 }
@@ -2,12 +2,17 @@ mod commands;
 mod models;
 mod services;
 
-use tauri::{Emitter, Manager};
-use tauri::menu::{MenuBuilder, MenuItemBuilder};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri::menu::{Menu, MenuBuilder, MenuItemBuilder, SubmenuBuilder};
 use tauri::tray::{TrayIconBuilder, MouseButton, MouseButtonState, TrayIconEvent};
 use commands::project::AppState;
 use services::cache_manager::CacheManager;
 use services::shortcut_manager::ShortcutManager;
+use services::job_manager::JobManager;
+use services::task_runner::TaskRunner;
+use services::supervisor::{Supervisor, SupervisorEvent};
+use services::launch_log::LaunchLog;
+use services::env_resolver::EnvResolver;
 use models::config::Config;
 use std::sync::Mutex;
 use std::fs;
@@ -15,6 +20,58 @@ use std::fs;
 #[cfg(target_os = "macos")]
 use cocoa::appkit::{NSApp, NSApplication, NSApplicationActivationPolicy};
 
+/// 托盘图标 ID
+const TRAY_ID: &str = "main";
+
+/// 构建托盘菜单，运行中的托管服务放在子菜单中
+fn build_tray_menu<R: Runtime, M: Manager<R>>(manager: &M) -> tauri::Result<Menu<R>> {
+    let mut builder = MenuBuilder::new(manager)
+        .item(&MenuItemBuilder::with_id("show", "显示搜索框").build(manager)?)
+        .item(&MenuItemBuilder::with_id("settings", "打开设置").build(manager)?)
+        .item(&MenuItemBuilder::with_id("refresh", "刷新项目缓存").build(manager)?);
+
+    let services: Vec<_> = manager
+        .try_state::<AppState>()
+        .map(|state| state.supervisor.list())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|s| s.status.is_active())
+        .collect();
+
+    if !services.is_empty() {
+        let mut submenu = SubmenuBuilder::new(manager, format!("运行中的服务 ({})", services.len()));
+        for service in &services {
+            let project = std::path::Path::new(&service.project_path)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| service.project_path.clone());
+            let entry = SubmenuBuilder::new(manager, format!("{} · {}", project, service.name))
+                .item(&MenuItemBuilder::with_id(format!("service-restart:{}", service.id), "重启").build(manager)?)
+                .item(&MenuItemBuilder::with_id(format!("service-stop:{}", service.id), "停止").build(manager)?)
+                .build()?;
+            submenu = submenu.item(&entry);
+        }
+        builder = builder.separator().item(&submenu.build()?);
+    }
+
+    builder
+        .separator()
+        .item(&MenuItemBuilder::with_id("quit", "退出").build(manager)?)
+        .build()
+}
+
+/// 托管服务变化后刷新托盘菜单
+fn refresh_tray_menu(app: &AppHandle) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        match build_tray_menu(app) {
+            Ok(menu) => {
+                let _ = tray.set_menu(Some(menu));
+            }
+            Err(e) => eprintln!("刷新托盘菜单失败: {}", e),
+        }
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -42,6 +99,11 @@ pub fn run() {
             // 确保数据目录存在
             fs::create_dir_all(&app_data_dir)?;
 
+            // 旧版本把工具链环境（可能含密钥）明文缓存在这里，现在只保存在内存中
+            let _ = fs::remove_file(app_data_dir.join("env_cache.json"));
+            // 上次运行时未能删除的 stderr 临时文件（Windows 上进程仍在运行时无法删除）
+            let _ = fs::remove_dir_all(app_data_dir.join(commands::launcher::LAUNCH_STDERR_DIR));
+
             let cache_manager = CacheManager::new(app_data_dir.clone());
 
             // 加载配置
@@ -66,11 +128,34 @@ pub fn run() {
                 Config::default()
             };
 
+            // 托管服务的输出和状态变化推送到前端，并同步到托盘菜单
+            let handle = app.handle().clone();
             app.manage(AppState {
                 cache_manager: Mutex::new(cache_manager),
                 config: Mutex::new(config.clone()),
+                jobs: JobManager::new(),
+                tasks: TaskRunner::new(app_data_dir.join("task_history.json")),
+                launch_log: LaunchLog::new(app_data_dir.join("launch_log.jsonl")),
+                env_resolver: EnvResolver::new(),
+                launcher_health: Mutex::new(Vec::new()),
+                supervisor: Supervisor::new(move |event| match event {
+                    SupervisorEvent::Logs(lines) => {
+                        let _ = handle.emit("service-log", lines);
+                    }
+                    SupervisorEvent::Changed(_) | SupervisorEvent::Removed(_) => {
+                        let _ = handle.emit("services-changed", handle.state::<AppState>().supervisor.list());
+                        refresh_tray_menu(&handle);
+                    }
+                }),
             });
 
+            // 监视在项目目录中运行的进程（目前仅支持 Linux）
+            #[cfg(target_os = "linux")]
+            commands::process::start_process_monitor(app.handle().clone());
+
+            // 检查启动器指向的程序是否仍然存在
+            commands::launcher::refresh_launcher_health(&app.state::<AppState>(), app.handle());
+
             // 注册启动器快捷键（使用 tauri-plugin-global-shortcut）
             let shortcut_manager = ShortcutManager::new(app.handle().clone());
             for launcher in &config.launchers {
@@ -90,16 +175,10 @@ pub fn run() {
             // }
 
             // 创建托盘图标和菜单
-            let menu = MenuBuilder::new(app)
-                .item(&MenuItemBuilder::with_id("show", "显示搜索框").build(app)?)
-                .item(&MenuItemBuilder::with_id("settings", "打开设置").build(app)?)
-                .item(&MenuItemBuilder::with_id("refresh", "刷新项目缓存").build(app)?)
-                .separator()
-                .item(&MenuItemBuilder::with_id("quit", "退出").build(app)?)
-                .build()?;
+            let menu = build_tray_menu(app)?;
 
             // Build tray icon with platform-specific settings
-            let mut tray_builder = TrayIconBuilder::new();
+            let mut tray_builder = TrayIconBuilder::with_id(TRAY_ID);
 
             #[cfg(target_os = "macos")]
             {
@@ -136,9 +215,24 @@ pub fn run() {
                             let _ = app.emit("refresh-projects", ());
                         }
                         "quit" => {
-                            std::process::exit(0);
+                            // 结束托管服务的进程组，避免留下孤儿进程；最多等待数秒，不阻塞主线程
+                            let app = app.clone();
+                            tauri::async_runtime::spawn_blocking(move || {
+                                app.state::<AppState>().supervisor.stop_all();
+                                std::process::exit(0);
+                            });
+                        }
+                        id => {
+                            if let Some(service_id) = id.strip_prefix("service-stop:") {
+                                let _ = app.state::<AppState>().supervisor.stop(service_id);
+                            } else if let Some(service_id) = id.strip_prefix("service-restart:") {
+                                let app = app.clone();
+                                let service_id = service_id.to_string();
+                                tauri::async_runtime::spawn_blocking(move || {
+                                    let _ = app.state::<AppState>().supervisor.restart(&service_id);
+                                });
+                            }
                         }
-                        _ => {}
                     }
                 })
                 .on_tray_icon_event(|tray, event| {
@@ -161,10 +255,12 @@ pub fn run() {
             commands::project::force_rescan,
             commands::project::detect_project_type,
             commands::project::batch_detect_types,
+            commands::project::calculate_language_stats,
             commands::project::increment_project_hits,
             commands::project::update_project_launcher,
             commands::project::update_project_top,
             commands::project::update_project_alias,
+            commands::project::update_project_hooks,
             commands::project::add_custom_project,
             commands::project::remove_custom_project,
             commands::project::reset_project_hits,
@@ -173,12 +269,60 @@ pub fn run() {
             commands::project::exclude_project,
             commands::project::get_excluded_projects,
             commands::project::restore_excluded_project,
+            // 后台任务相关
+            commands::job::cancel_job,
+            // Git 相关
+            commands::git::get_git_info,
+            commands::git::refresh_git_info,
+            commands::git::get_repository_report,
+            commands::git::export_repository_report,
+            commands::git::sync_projects,
+            commands::git::list_project_branches,
+            commands::git::switch_project_branch,
+            commands::git::get_project_web_links,
+            commands::git::open_project_web_page,
+            commands::git::clone_project,
+            // 模板相关
+            commands::template::get_templates,
+            commands::template::add_template,
+            commands::template::update_template,
+            commands::template::remove_template,
+            commands::template::create_project_from_template,
+            // 磁盘占用相关
+            commands::disk::calculate_disk_usage,
+            commands::disk::cleanup_artifacts,
+            // 项目任务
+            commands::task::discover_project_tasks,
+            commands::task::run_project_task,
+            commands::task::stop_task,
+            commands::task::get_running_tasks,
+            commands::task::get_task_history,
+            // 运行中的进程
+            commands::process::get_running_projects,
+            commands::process::terminate_project_process,
+            // 托管服务
+            commands::service::start_service,
+            commands::service::stop_service,
+            commands::service::restart_service,
+            commands::service::remove_service,
+            commands::service::list_services,
+            commands::service::get_service_logs,
             // 启动器相关
             commands::launcher::launch_project,
+            commands::launcher::preview_launcher_command,
+            commands::launcher::get_project_env,
             commands::launcher::get_launchers,
+            commands::launcher::discover_launchers,
+            commands::launcher::check_launchers,
+            commands::launcher::get_launcher_health,
             commands::launcher::add_launcher,
             commands::launcher::update_launcher,
             commands::launcher::remove_launcher,
+            // 启动记录
+            commands::history::get_recent_launches,
+            commands::history::get_project_launch_history,
+            commands::history::get_launcher_launch_counts,
+            commands::history::get_suggestions,
             // 快捷键相关
             commands::shortcut::check_shortcut_conflict,
             commands::shortcut::register_launcher_shortcut,
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 use super::launcher::Launcher;
+use super::git::GitProvider;
+use super::template::ProjectTemplate;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -11,15 +13,62 @@ pub struct Config {
     pub launchers: Vec<Launcher>,
     pub autostart: bool,
     pub theme: String,
-    /// 项目列表排序方式: "hits" | "last_opened" | "name"
+    /// 项目列表排序方式: "hits" | "last_opened" | "name" | "frecency"
     #[serde(default = "default_project_sort_by")]
     pub project_sort_by: String,
+    /// frecency 排序的时间衰减分段：每次启动按距今时间落入的分段计分
+    #[serde(default = "default_frecency_buckets")]
+    pub frecency_buckets: Vec<FrecencyBucket>,
+    /// 自建代码托管主机映射（用于从远程地址推导网页地址）
+    #[serde(default)]
+    pub git_hosts: Vec<GitHostMapping>,
+    /// 项目模板
+    #[serde(default)]
+    pub templates: Vec<ProjectTemplate>,
+    /// 在终端中运行启动器时使用的终端命令，要运行的参数追加在末尾，
+    /// 如 `kitty --directory {cwd}`、`wezterm start --cwd {cwd} --`；未设置时使用平台默认终端
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal: Option<String>,
+    /// 启动后观察进程的时长（毫秒），期间进程以非零状态退出视为启动失败
+    #[serde(default = "default_launch_grace_ms")]
+    pub launch_grace_ms: u64,
+}
+
+/// frecency 时间分段：距今不超过 `days` 天的启动计 `weight` 分（取第一个满足的分段），
+/// 超过所有分段的启动不计分
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrecencyBucket {
+    pub days: u32,
+    pub weight: u32,
+}
+
+/// 自建托管主机映射
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHostMapping {
+    /// 远程地址中的主机名（SSH 主机可与网页主机不同）
+    pub host: String,
+    pub provider: GitProvider,
+    /// 网页根地址，默认 https://{host}
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web_url: Option<String>,
 }
 
 fn default_project_sort_by() -> String {
     "hits".to_string()
 }
 
+/// 默认分段：一周内的少量启动即可超过很久以前的大量启动
+fn default_frecency_buckets() -> Vec<FrecencyBucket> {
+    [(1, 100), (7, 70), (30, 30), (90, 10), (365, 1)]
+        .into_iter()
+        .map(|(days, weight)| FrecencyBucket { days, weight })
+        .collect()
+}
+
+fn default_launch_grace_ms() -> u64 {
+    3000
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -36,6 +85,11 @@ impl Default for Config {
             autostart: false,
             theme: "light".to_string(),
             project_sort_by: "hits".to_string(),
+            frecency_buckets: default_frecency_buckets(),
+            git_hosts: Vec::new(),
+            templates: Vec::new(),
+            terminal: None,
+            launch_grace_ms: default_launch_grace_ms(),
         }
     }
 }
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use super::job::ProjectUpdate;
+
+/// 项目磁盘占用
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiskUsage {
+    /// 项目目录总大小（字节，含构建产物）
+    pub total: u64,
+    /// 可清理的构建产物目录
+    #[serde(default)]
+    pub artifacts: Vec<ArtifactUsage>,
+    pub measured_at: String,
+}
+
+impl DiskUsage {
+    /// 可回收的字节数
+    pub fn reclaimable(&self) -> u64 {
+        self.artifacts.iter().map(|a| a.size).sum()
+    }
+}
+
+/// 单个构建产物目录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactUsage {
+    /// 相对项目根目录的路径，如 `node_modules`、`src-tauri/target`
+    pub path: String,
+    pub size: u64,
+}
+
+/// 单个项目的磁盘占用计算结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskUsageUpdate {
+    pub path: String,
+    pub disk_usage: DiskUsage,
+}
+
+impl ProjectUpdate for DiskUsageUpdate {
+    fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+/// 磁盘占用计算任务摘要
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskUsageSummary {
+    pub total: u64,
+    pub reclaimable: u64,
+}
+
+/// 清理选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CleanupOptions {
+    /// 要清理的项目（None 表示全部项目，只能用于 dry run）
+    pub project_paths: Option<Vec<String>>,
+    /// 只清理超过 N 天未打开的项目（从未打开过的也计入）
+    pub not_opened_days: Option<u32>,
+    /// 只报告可回收大小，不删除；未指定时为 true，删除需要显式传 false
+    pub dry_run: bool,
+}
+
+impl Default for CleanupOptions {
+    fn default() -> Self {
+        Self {
+            project_paths: None,
+            not_opened_days: None,
+            dry_run: true,
+        }
+    }
+}
+
+/// 单个项目的清理结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectCleanup {
+    pub path: String,
+    pub name: String,
+    pub artifacts: Vec<ArtifactUsage>,
+    /// 已回收（dry run 时为可回收）的字节数
+    pub reclaimed: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+    /// 因包含受版本控制的文件而未清理的目录及原因
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+}
+
+/// 清理报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupReport {
+    pub dry_run: bool,
+    pub total_bytes: u64,
+    pub projects: Vec<ProjectCleanup>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_defaults_to_dry_run() {
+        let options: CleanupOptions = serde_json::from_str(r#"{"project_paths":["/p/a"]}"#).unwrap();
+        assert!(options.dry_run);
+        let options: CleanupOptions = serde_json::from_str(r#"{"dry_run":false}"#).unwrap();
+        assert!(!options.dry_run);
+    }
+}
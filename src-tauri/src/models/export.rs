@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use super::cache::CacheData;
+use super::config::FrecencyBucket;
 use super::launcher::Launcher;
 
 /// 导出数据结构
@@ -23,6 +24,8 @@ pub struct GeneralSettings {
     pub autostart: bool,
     pub theme: String,
     pub project_sort_by: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frecency_buckets: Option<Vec<FrecencyBucket>>,
 }
 
 /// 工作区设置
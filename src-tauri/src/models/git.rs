@@ -0,0 +1,343 @@
+use serde::{Deserialize, Serialize};
+use super::job::ProjectUpdate;
+
+/// Git 仓库状态快照
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GitInfo {
+    /// 当前分支（detached HEAD 时为 None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub detached: bool,
+    /// HEAD 提交的短哈希
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+    /// 上游分支，如 origin/main
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<String>,
+    #[serde(default)]
+    pub ahead: u32,
+    #[serde(default)]
+    pub behind: u32,
+    #[serde(default)]
+    pub staged: u32,
+    #[serde(default)]
+    pub unstaged: u32,
+    #[serde(default)]
+    pub untracked: u32,
+    #[serde(default)]
+    pub conflicted: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commit: Option<GitCommit>,
+    #[serde(default)]
+    pub remotes: Vec<GitRemote>,
+    /// 读取时间（RFC3339），用于判断是否需要刷新
+    #[serde(default)]
+    pub refreshed_at: String,
+}
+
+impl GitInfo {
+    /// 是否有未提交的修改（含未跟踪文件）
+    pub fn is_dirty(&self) -> bool {
+        self.staged + self.unstaged + self.untracked + self.conflicted > 0
+    }
+
+    /// 距离上次读取是否仍在有效期内
+    pub fn is_fresh(&self, max_age_secs: u64) -> bool {
+        chrono::DateTime::parse_from_rfc3339(&self.refreshed_at)
+            .map(|t| {
+                let age = chrono::Utc::now().signed_duration_since(t);
+                age.num_seconds() >= 0 && age.num_seconds() < max_age_secs as i64
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// 最近一次提交
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitCommit {
+    pub hash: String,
+    /// 提交时间（RFC3339）
+    pub time: String,
+    pub author: String,
+    pub subject: String,
+}
+
+/// 远程仓库
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitRemote {
+    pub name: String,
+    pub url: String,
+}
+
+/// 单个项目的 Git 状态刷新结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitInfoUpdate {
+    pub path: String,
+    pub git: GitInfo,
+}
+
+impl ProjectUpdate for GitInfoUpdate {
+    fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+/// 仓库需要关注的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepositoryIssue {
+    /// 有已暂存或未暂存的修改
+    Dirty,
+    /// 有未跟踪文件
+    Untracked,
+    /// 有冲突文件
+    Conflicted,
+    /// 有 stash
+    Stashed,
+    /// 有未推送的提交
+    Ahead,
+    /// 落后于上游
+    Behind,
+    /// 当前分支没有上游
+    NoUpstream,
+    /// 处于 detached HEAD
+    Detached,
+    /// 读取失败
+    Error,
+}
+
+impl RepositoryIssue {
+    /// 是否可能导致本地工作丢失（落后上游不算）
+    pub fn needs_attention(self) -> bool {
+        !matches!(self, RepositoryIssue::Behind)
+    }
+}
+
+/// 单个仓库的状态报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryStatus {
+    pub name: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitInfo>,
+    pub stashes: u32,
+    pub issues: Vec<RepositoryIssue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RepositoryStatus {
+    pub fn needs_attention(&self) -> bool {
+        self.issues.iter().any(|i| i.needs_attention())
+    }
+}
+
+/// 所有 Git 项目的状态报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryReport {
+    pub generated_at: String,
+    /// 参与评估的仓库总数
+    pub total: usize,
+    /// 需要关注的仓库数
+    pub needs_attention: usize,
+    pub repositories: Vec<RepositoryStatus>,
+}
+
+/// 批量同步的单仓库结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitSyncOutcome {
+    /// 已是最新
+    UpToDate,
+    /// 已 fetch，落后于上游但未合并
+    Fetched,
+    /// 已快进合并
+    Pulled,
+    /// 因工作区有修改或没有上游而跳过
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitSyncResult {
+    pub path: String,
+    pub outcome: GitSyncOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// 同步后的仓库状态
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitInfo>,
+}
+
+impl GitSyncResult {
+    pub fn new(path: &str, outcome: GitSyncOutcome, message: Option<String>, git: Option<GitInfo>) -> Self {
+        Self {
+            path: path.to_string(),
+            outcome,
+            message,
+            git,
+        }
+    }
+}
+
+impl ProjectUpdate for GitSyncResult {
+    fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+/// 批量同步汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitSyncSummary {
+    pub total: usize,
+    pub up_to_date: usize,
+    pub fetched: usize,
+    pub pulled: usize,
+    pub skipped: Vec<GitSyncResult>,
+    pub failures: Vec<GitSyncResult>,
+}
+
+/// 本地分支
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitBranch {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<String>,
+    /// 分支最新提交时间（ISO 8601）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commit_time: Option<String>,
+    pub is_current: bool,
+}
+
+/// 工作树
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitWorktree {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub detached: bool,
+}
+
+/// 分支切换器数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchList {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current: Option<String>,
+    /// 按最新提交时间排序的本地分支
+    pub local: Vec<GitBranch>,
+    /// 最近切换过的分支（来自 reflog，不含当前分支）
+    pub recent: Vec<String>,
+    pub worktrees: Vec<GitWorktree>,
+}
+
+/// 工作区有修改时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DirtyStrategy {
+    /// 不切换，返回 dirty 状态由前端询问
+    #[default]
+    Refuse,
+    /// 先 stash 再切换
+    Stash,
+}
+
+/// 分支切换结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchSwitchResult {
+    /// 是否已切换（工作区有修改且拒绝时为 false）
+    pub switched: bool,
+    pub dirty: bool,
+    pub stashed: bool,
+    pub branch: String,
+    /// 应打开的目录（创建工作树时为工作树路径）
+    pub project_path: String,
+    pub worktree: bool,
+}
+
+/// 分支切换选项
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BranchSwitchOptions {
+    #[serde(default)]
+    pub dirty_strategy: DirtyStrategy,
+    /// 创建工作树而不是在原目录切换
+    #[serde(default)]
+    pub worktree: bool,
+    /// 切换后使用启动器打开
+    #[serde(default)]
+    pub launch: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launcher_id: Option<String>,
+}
+
+/// 代码托管平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitProvider {
+    Github,
+    Gitlab,
+    /// Gitea / Forgejo / Codeberg
+    Gitea,
+    Bitbucket,
+    Unknown,
+}
+
+/// 托管平台页面
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebPage {
+    Repo,
+    Branch,
+    PullRequests,
+    Issues,
+    Pipelines,
+}
+
+/// 单个远程对应的网页地址
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectWebLinks {
+    pub remote: String,
+    pub provider: GitProvider,
+    pub repo: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_requests: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issues: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipelines: Option<String>,
+}
+
+/// 克隆目录布局
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloneLayout {
+    /// `<workspace>/<repo>`
+    #[default]
+    Flat,
+    /// `<workspace>/<host>/<org>/<repo>`
+    Hosted,
+}
+
+/// 克隆选项
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CloneOptions {
+    pub layout: CloneLayout,
+    /// 覆盖推导出的目录名
+    pub folder_name: Option<String>,
+    /// 克隆完成后启动
+    pub launch: bool,
+    pub launcher_id: Option<String>,
+}
+
+/// 克隆进度事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloneProgress {
+    pub url: String,
+    pub path: String,
+    pub phase: String,
+    pub percent: Option<u32>,
+}
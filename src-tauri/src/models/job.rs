@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use super::project::ProjectMetadata;
+
+/// 后台任务进度事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobProgress {
+    pub job_id: String,
+    pub done: usize,
+    pub total: usize,
+    pub percent: u32,
+}
+
+impl JobProgress {
+    pub fn new(job_id: &str, done: usize, total: usize) -> Self {
+        let percent = if total == 0 {
+            100
+        } else {
+            (done as f32 / total as f32 * 100.0) as u32
+        };
+        Self {
+            job_id: job_id.to_string(),
+            done,
+            total,
+            percent,
+        }
+    }
+}
+
+/// 后台任务结束事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobFinished {
+    pub job_id: String,
+    pub cancelled: bool,
+    pub done: usize,
+    pub total: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 任务自定义的结果摘要
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<serde_json::Value>,
+}
+
+/// 单个项目的类型检测结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectTypeUpdate {
+    pub path: String,
+    pub project_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ProjectMetadata>,
+}
+
+/// 可按路径写回项目缓存的批处理结果
+pub trait ProjectUpdate {
+    fn path(&self) -> &str;
+}
+
+impl ProjectUpdate for ProjectTypeUpdate {
+    fn path(&self) -> &str {
+        &self.path
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use super::job::ProjectUpdate;
+
+/// 项目语言统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LanguageStats {
+    /// 按行数降序排列
+    pub languages: Vec<LanguageUsage>,
+    pub total_files: u32,
+    pub total_lines: u64,
+    /// 行数最多的语言
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary: Option<String>,
+    /// 计算时的文件指纹，未变化时跳过重新统计
+    pub fingerprint: String,
+    pub computed_at: String,
+}
+
+/// 单一语言的文件数和行数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageUsage {
+    pub name: String,
+    pub files: u32,
+    pub lines: u64,
+}
+
+/// 单个项目的语言统计结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageStatsUpdate {
+    pub path: String,
+    pub stats: LanguageStats,
+}
+
+impl ProjectUpdate for LanguageStatsUpdate {
+    fn path(&self) -> &str {
+        &self.path
+    }
+}
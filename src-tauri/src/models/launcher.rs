@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,10 +12,30 @@ pub struct Launcher {
     pub command: Option<String>,
     #[serde(default)]
     pub is_command: bool,
+    /// 命令模式下通过 shell（`sh -c` / `cmd /C`）执行，支持管道、重定向；
+    /// 关闭时拆分为参数直接执行，占位符的值不会被 shell 解释。
+    /// 旧版本配置中没有该字段的启动器一直通过 shell 执行，缺省为 true 以保持原有行为
+    #[serde(default = "default_true")]
+    pub run_in_shell: bool,
+    /// 额外的环境变量，值支持占位符
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// 工作目录，支持占位符，相对路径按项目目录解析；默认项目目录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    /// 加载项目目录下的 `.env`（启动器的 `env` 优先）
+    #[serde(default)]
+    pub load_dotenv: bool,
+    /// 在配置的终端中运行
+    #[serde(default)]
+    pub in_terminal: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub icon_path: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shortcut: Option<String>,
+    /// 启动前后执行的钩子
+    #[serde(default, skip_serializing_if = "LaunchHooks::is_empty")]
+    pub hooks: LaunchHooks,
 }
 
 impl Launcher {
@@ -25,8 +46,273 @@ impl Launcher {
             path,
             command: None,
             is_command: false,
+            run_in_shell: false,
+            env: BTreeMap::new(),
+            working_dir: None,
+            load_dotenv: false,
+            in_terminal: false,
             icon_path: None,
             shortcut: None,
+            hooks: LaunchHooks::default(),
+        }
+    }
+}
+
+/// 启动器健康状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Healthy,
+    /// 可以启动，但有问题（如图标缺失）
+    Warning,
+    /// 无法启动
+    Broken,
+}
+
+/// 启动器健康检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LauncherHealth {
+    pub launcher_id: String,
+    pub status: HealthStatus,
+    /// 问题说明，无法启动的原因在前
+    pub reasons: Vec<String>,
+    pub checked_at: String,
+}
+
+/// 自动发现的启动器类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LauncherKind {
+    Editor,
+    Terminal,
+}
+
+/// 自动发现的启动器建议（`launcher.id` 为空，添加时生成）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LauncherSuggestion {
+    pub launcher: Launcher,
+    pub kind: LauncherKind,
+    /// 来源：.desktop 文件或 PATH 中的可执行文件
+    pub source: String,
+}
+
+fn default_hook_timeout() -> u64 {
+    30
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 启动钩子：在项目目录中通过 shell 执行的命令
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LaunchHook {
+    pub command: String,
+    /// 超时（秒），超时后结束整个进程组并视为失败
+    #[serde(default = "default_hook_timeout")]
+    pub timeout_secs: u64,
+    /// 失败时中止（启动前钩子：不再打开项目；启动后钩子：跳过后续钩子）
+    #[serde(default = "default_true")]
+    pub abort_on_failure: bool,
+}
+
+/// 启动器/项目上配置的钩子
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LaunchHooks {
+    /// 打开项目之前执行，例如 `git fetch`、`docker compose up -d`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_launch: Vec<LaunchHook>,
+    /// 启动的进程退出后执行（部分编辑器的命令行工具会立即返回）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_launch: Vec<LaunchHook>,
+}
+
+impl LaunchHooks {
+    pub fn is_empty(&self) -> bool {
+        self.pre_launch.is_empty() && self.post_launch.is_empty()
+    }
+}
+
+/// 钩子阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookStage {
+    PreLaunch,
+    PostLaunch,
+}
+
+/// 一次钩子执行结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HookResult {
+    pub project_path: String,
+    pub command: String,
+    pub stage: HookStage,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    #[serde(default)]
+    pub timed_out: bool,
+    /// 合并的 stdout/stderr（只保留末尾部分）
+    pub output: String,
+    pub duration_ms: u64,
+}
+
+impl HookResult {
+    /// 用于错误提示的描述
+    pub fn failure_message(&self) -> String {
+        let reason = if self.timed_out {
+            "超时".to_string()
+        } else {
+            match self.exit_code {
+                Some(code) => format!("退出码 {}", code),
+                None => "执行失败".to_string(),
+            }
+        };
+        if self.output.is_empty() {
+            format!("钩子 `{}` {}", self.command, reason)
+        } else {
+            format!("钩子 `{}` {}:\n{}", self.command, reason, self.output)
         }
     }
 }
+
+/// 一次启动实际使用的设置（写入启动日志，便于排查失败）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LaunchSettings {
+    /// 展开后的参数列表（含终端、shell 包装）
+    pub argv: Vec<String>,
+    pub cwd: String,
+    /// 额外设置的环境变量名（值可能包含密钥，不记录）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<String>,
+    /// 加载的 .env 文件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dotenv: Option<String>,
+    /// 生效的工具链环境来源（direnv / nix / mise / asdf / nvm）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub toolchain: Vec<String>,
+    /// 计算失败的工具链环境（其余设置照常生效）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub toolchain_errors: Vec<String>,
+    #[serde(default)]
+    pub run_in_shell: bool,
+    #[serde(default)]
+    pub in_terminal: bool,
+}
+
+/// 项目工具链（direnv、nix、mise/asdf、nvm）导出的环境
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolEnv {
+    pub project_path: String,
+    /// 生效的来源，按应用顺序
+    pub sources: Vec<String>,
+    /// 变量值；None 表示需要从环境中移除
+    pub vars: BTreeMap<String, Option<String>>,
+    /// 计算失败的来源及原因
+    #[serde(default)]
+    pub errors: Vec<String>,
+    /// 配置文件及其修改时间（毫秒），变化时重新计算
+    pub fingerprint: Vec<(String, u64)>,
+    pub resolved_at: String,
+}
+
+/// 启动结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LaunchReport {
+    /// 启动前钩子的执行情况
+    pub hooks: Vec<HookResult>,
+    pub settings: LaunchSettings,
+}
+
+/// 启动日志中的一条记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchLogEntry {
+    /// 同一次启动在观察期内失败时，以相同 id 追加一条记录
+    #[serde(default)]
+    pub id: String,
+    pub time: String,
+    pub project_path: String,
+    /// 未能确定启动器（如启动器已删除）时 `launcher_name` 为空
+    pub launcher_id: String,
+    pub launcher_name: String,
+    /// 未能生成启动参数时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<LaunchSettings>,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// 执行启动前钩子和创建进程所用的时间
+    #[serde(default)]
+    pub duration_ms: u64,
+}
+
+/// 按启动器统计的启动次数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LauncherLaunchCount {
+    pub launcher_id: String,
+    pub launcher_name: String,
+    pub count: u32,
+    pub failures: u32,
+    /// 最近一次启动的时间
+    pub last_used: Option<String>,
+}
+
+/// 根据启动记录推荐的项目
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectSuggestion {
+    pub project_path: String,
+    pub score: f64,
+    pub reason: SuggestionReason,
+}
+
+/// 推荐理由
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SuggestionReason {
+    /// 常在每周的这一天、这个时段打开
+    TimeOfDay,
+    /// 常在打开 `after` 之后打开
+    CoLaunch { after: String },
+}
+
+/// 启动后在观察期内以非零状态退出的进程（`launch-failed` 事件）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchFailure {
+    pub time: String,
+    pub project_path: String,
+    pub launcher_id: String,
+    pub launcher_name: String,
+    /// 被信号终止时为空
+    pub exit_code: Option<i32>,
+    /// 标准错误输出的末尾部分
+    pub stderr: String,
+}
+
+/// 启动时定位到的文件（`{file}` / `{line}` 占位符）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LaunchTarget {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_old_command_launcher_keeps_shell() {
+        // 旧版本保存的命令启动器（没有 run_in_shell 字段）
+        let old: Launcher = serde_json::from_str(
+            r#"{"id":"l1","name":"Code","path":"","command":"cd {project} && code .","is_command":true}"#,
+        ).unwrap();
+        assert!(old.run_in_shell);
+
+        let new: Launcher = serde_json::from_str(
+            r#"{"id":"l2","name":"Code","command":"code {project}","is_command":true,"run_in_shell":false}"#,
+        ).unwrap();
+        assert!(!new.run_in_shell);
+        assert!(!Launcher::new("l3".to_string(), "Code".to_string(), String::new()).run_in_shell);
+    }
+}
@@ -3,3 +3,11 @@ pub mod launcher;
 pub mod config;
 pub mod cache;
 pub mod export;
+pub mod job;
+pub mod git;
+pub mod template;
+pub mod disk;
+pub mod language;
+pub mod process;
+pub mod task;
+pub mod service;
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// 在项目目录中运行的进程
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectProcess {
+    pub pid: u32,
+    /// 进程名（/proc/<pid>/comm）
+    pub name: String,
+    /// 完整命令行
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// 正在监听的 TCP 端口
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<u16>,
+}
+
+/// 有进程在运行的项目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunningProject {
+    pub path: String,
+    pub name: String,
+    pub processes: Vec<ProjectProcess>,
+}
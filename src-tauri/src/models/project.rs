@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use super::git::GitInfo;
+use super::disk::DiskUsage;
+use super::language::LanguageStats;
+use super::launcher::LaunchHooks;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
@@ -12,6 +16,59 @@ pub struct Project {
     pub is_custom: bool,
     pub last_opened: Option<String>,
     pub alias: Option<String>,
+    /// 从清单文件/README 提取的描述信息（类型检测时填充）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ProjectMetadata>,
+    /// Git 仓库状态（按需刷新，见 `refreshed_at`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitInfo>,
+    /// 磁盘占用（后台计算）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_usage: Option<DiskUsage>,
+    /// 各语言文件数/行数（后台按指纹增量计算）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language_stats: Option<LanguageStats>,
+    /// 打开该项目时额外执行的钩子（在启动器钩子之后执行启动前钩子）
+    #[serde(default, skip_serializing_if = "LaunchHooks::is_empty")]
+    pub hooks: LaunchHooks,
+}
+
+/// 项目描述信息
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+impl ProjectMetadata {
+    /// 用另一来源补全缺失字段（已有值优先）
+    pub fn merge(&mut self, other: ProjectMetadata) {
+        if self.description.is_none() {
+            self.description = other.description;
+        }
+        if self.homepage.is_none() {
+            self.homepage = other.homepage;
+        }
+        if self.license.is_none() {
+            self.license = other.license;
+        }
+        if self.keywords.is_empty() {
+            self.keywords = other.keywords;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none()
+            && self.homepage.is_none()
+            && self.license.is_none()
+            && self.keywords.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +92,11 @@ impl Project {
             is_custom: false,
             last_opened: None,
             alias: None,
+            metadata: None,
+            git: None,
+            disk_usage: None,
+            language_stats: None,
+            hooks: LaunchHooks::default(),
         }
     }
 }
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use super::task::{OutputStream, TaskSource};
+
+/// 托管服务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceStatus {
+    Running,
+    /// 崩溃后等待重启
+    Restarting,
+    Stopped,
+    /// 异常退出且不再重启
+    Crashed,
+}
+
+impl ServiceStatus {
+    /// 是否仍由监管线程管理（运行中或等待重启）
+    pub fn is_active(self) -> bool {
+        matches!(self, ServiceStatus::Running | ServiceStatus::Restarting)
+    }
+}
+
+/// 由 Supervisor 托管的长时间运行进程（开发服务器、watch 等）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceInfo {
+    pub id: String,
+    pub project_path: String,
+    pub name: String,
+    pub source: TaskSource,
+    /// 用于显示的命令行
+    pub command: String,
+    pub status: ServiceStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    /// 崩溃后自动重启
+    pub auto_restart: bool,
+    /// 连续重启次数（稳定运行一段时间后清零）
+    pub restarts: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 服务输出（逐行）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceLogLine {
+    pub service_id: String,
+    pub stream: OutputStream,
+    pub line: String,
+    pub time: String,
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+/// 任务来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSource {
+    /// package.json scripts
+    Npm,
+    Make,
+    Just,
+    /// Taskfile.yml
+    Taskfile,
+    /// Cargo bin / example
+    Cargo,
+    /// composer.json scripts
+    Composer,
+    /// pyproject.toml scripts
+    Pyproject,
+}
+
+/// 项目中可运行的任务
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectTask {
+    pub name: String,
+    pub source: TaskSource,
+    /// 实际执行的参数列表（不经过 shell）
+    pub command: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 任务运行状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+    Stopped,
+}
+
+/// 一次任务运行记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskRun {
+    pub run_id: String,
+    pub project_path: String,
+    pub task_name: String,
+    pub source: TaskSource,
+    /// 用于显示的命令行
+    pub command: String,
+    #[serde(default)]
+    pub pid: Option<u32>,
+    pub status: TaskRunStatus,
+    pub started_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 输出流
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// 任务输出事件（逐行）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskOutput {
+    pub run_id: String,
+    pub stream: OutputStream,
+    pub line: String,
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use super::project::Project;
+
+/// 项目模板
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectTemplate {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub source: TemplateSource,
+    /// 创建后在项目目录依次执行的命令（支持模板变量）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_create: Vec<String>,
+}
+
+/// 模板来源
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TemplateSource {
+    /// 本地目录
+    Local { path: String },
+    /// Git 仓库（浅克隆后复制，不保留模板的提交历史）
+    Git {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<String>,
+    },
+}
+
+/// 从模板创建项目的参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateCreateRequest {
+    pub template_id: String,
+    pub workspace: String,
+    /// 项目名（同时作为目录名）
+    pub name: String,
+    /// 作者，默认读取 git 全局 user.name
+    #[serde(default)]
+    pub author: Option<String>,
+    /// 额外的模板变量
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    #[serde(default)]
+    pub launch: bool,
+    #[serde(default)]
+    pub launcher_id: Option<String>,
+}
+
+/// 创建后命令的执行结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostCreateOutcome {
+    pub command: String,
+    pub success: bool,
+    pub output: String,
+}
+
+/// 从模板创建项目的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateCreateResult {
+    pub project: Project,
+    pub post_create: Vec<PostCreateOutcome>,
+}
@@ -43,6 +43,18 @@ impl CacheManager {
         Ok(())
     }
 
+    /// 读取-修改-写回项目列表（调用方需持有 cache_manager 锁，保证不会丢失并发修改）
+    pub fn update<R>(&self, f: impl FnOnce(&mut Vec<Project>) -> R) -> Result<R> {
+        let mut projects = self
+            .load_instant()?
+            .map(|c| c.projects)
+            .ok_or_else(|| anyhow::anyhow!("缓存为空"))?;
+
+        let result = f(&mut projects);
+        self.save(projects)?;
+        Ok(result)
+    }
+
     /// 清除缓存
     pub fn clear(&self) -> Result<()> {
         if self.cache_path.exists() {
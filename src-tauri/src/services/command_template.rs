@@ -0,0 +1,509 @@
+//! 启动命令模板
+//!
+//! 默认按参数列表执行：模板先拆分为参数，再在每个参数内替换 `{project}` 等占位符，
+//! 替换值无论包含空格、引号还是 `$()`、`;` 都只会成为同一个参数，不经过 shell。
+//! 需要管道、重定向时可显式选择通过 shell 执行，此时替换值按目标 shell 的规则加引号。
+//!
+//! 拆分规则：空白分隔参数；`'…'` 内原样保留；`"…"` 内支持 `\"` 和 `\\`；
+//! 引号外的反斜杠按字面处理（兼容 Windows 路径）。
+//!
+//! 占位符：`{project}` `{name}` `{alias}` `{type}` `{branch}` `{parent}` `{workspace}`
+//! `{remote_url}` `{file}` `{line}`，环境变量写作 `{env:HOME}`；
+//! `{branch:-main}` 在值为空时使用默认值。未知占位符原样保留。
+//! 终端命令中另有 `{cwd}`（启动时的工作目录）。
+
+use std::collections::HashMap;
+use std::path::Path;
+use anyhow::Result;
+use crate::models::project::Project;
+
+/// 占位符取值的来源
+pub struct TemplateContext<'a> {
+    pub project_path: &'a str,
+    /// 缓存中的项目（自定义路径可能没有）
+    pub project: Option<&'a Project>,
+    /// 配置的工作区，用于 `{workspace}`
+    pub workspaces: &'a [String],
+    pub file: Option<&'a str>,
+    pub line: Option<u32>,
+}
+
+/// 通过 shell 执行时使用的 shell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellKind {
+    /// `sh -c`
+    Posix,
+    /// `cmd /C`
+    Cmd,
+}
+
+impl ShellKind {
+    /// 当前平台的 shell
+    pub fn native() -> Self {
+        if cfg!(target_os = "windows") {
+            ShellKind::Cmd
+        } else {
+            ShellKind::Posix
+        }
+    }
+}
+
+pub struct CommandTemplate;
+
+impl CommandTemplate {
+    /// 计算所有占位符的值（没有值的为空字符串）；环境变量以 `env:NAME` 为键
+    pub fn variables(ctx: &TemplateContext) -> HashMap<String, String> {
+        let path = Path::new(ctx.project_path);
+        let project = ctx.project;
+        let git = project.and_then(|p| p.git.as_ref());
+        let parent = path.parent().map(|p| p.to_string_lossy().into_owned()).unwrap_or_default();
+
+        // 包含项目的最深的工作区，不在任何工作区中时取上级目录
+        let workspace = ctx.workspaces
+            .iter()
+            .filter(|w| path.starts_with(w.as_str()))
+            .max_by_key(|w| w.len())
+            .cloned()
+            .unwrap_or_else(|| parent.clone());
+
+        let remote_url = git
+            .and_then(|g| g.remotes.iter().find(|r| r.name == "origin").or(g.remotes.first()))
+            .map(|r| r.url.clone());
+
+        // 相对路径的文件按项目目录解析
+        let file = ctx.file.map(|f| {
+            if Path::new(f).is_absolute() { f.to_string() } else { path.join(f).to_string_lossy().into_owned() }
+        });
+
+        let name = project
+            .map(|p| p.name.clone())
+            .or_else(|| path.file_name().map(|n| n.to_string_lossy().into_owned()));
+
+        [
+            ("project", Some(ctx.project_path.to_string())),
+            ("name", name),
+            ("alias", project.and_then(|p| p.alias.clone())),
+            ("type", project.and_then(|p| p.project_type.clone())),
+            ("branch", git.and_then(|g| g.branch.clone())),
+            ("parent", Some(parent)),
+            ("workspace", Some(workspace)),
+            ("remote_url", remote_url),
+            ("file", file),
+            ("line", ctx.line.map(|l| l.to_string())),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.unwrap_or_default()))
+        .chain(std::env::vars().map(|(key, value)| (format!("env:{}", key), value)))
+        .collect()
+    }
+
+    /// 渲染为参数列表（不经过 shell）
+    pub fn render_argv(template: &str, vars: &HashMap<String, String>) -> Result<Vec<String>> {
+        let argv: Vec<String> = Self::tokenize(template)?
+            .iter()
+            .map(|token| Self::expand(token, vars))
+            .collect();
+        if argv.first().is_none_or(|program| program.is_empty()) {
+            anyhow::bail!("启动命令为空");
+        }
+        Ok(argv)
+    }
+
+    /// 渲染为交给 shell 执行的命令行，替换值按 `shell` 的规则加引号
+    ///
+    /// 模板中已用引号包住的占位符（`"{project}"`、`'{project}'`）会去掉原有引号，避免重复加引号。
+    /// 占位符位于更长的引号字符串中（`"{project}/x.code-workspace"`）时，先结束引号，
+    /// 写入加好引号的值，再重新开始引号，替换值不会被外层引号内的规则解释。
+    pub fn render_shell(template: &str, vars: &HashMap<String, String>, shell: ShellKind) -> Result<String> {
+        Self::render_shell_with(template, shell, |text| {
+            let p = Placeholder::parse(text)?;
+            p.resolve(vars).map(|value| (p.len, value))
+        })
+    }
+
+    /// 按 `shell` 的规则渲染，占位符由 `placeholder` 识别：
+    /// 返回 `text` 开头占位符的长度和值，不是占位符时返回 None（原样保留）
+    pub(crate) fn render_shell_with<F>(template: &str, shell: ShellKind, mut placeholder: F) -> Result<String>
+    where
+        F: FnMut(&str) -> Option<(usize, String)>,
+    {
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        // 当前所在的引号，None 表示在引号外
+        let mut quote: Option<char> = None;
+
+        while let Some(c) = rest.chars().next() {
+            if quote.is_none() && Self::is_quote(c, shell) {
+                if let Some((len, value)) = placeholder(&rest[1..]) {
+                    if rest[1 + len..].starts_with(c) {
+                        out.push_str(&Self::quote(&value, shell)?);
+                        rest = &rest[len + 2..];
+                        continue;
+                    }
+                }
+            }
+            if let Some((len, value)) = placeholder(rest) {
+                out.push_str(&Self::quote_within(&value, shell, quote)?);
+                rest = &rest[len..];
+                continue;
+            }
+
+            // 转义字符连同下一个字符原样输出，被转义的引号不改变引号状态
+            let escape = match (shell, quote) {
+                (ShellKind::Posix, None | Some('"')) => c == '\\',
+                (ShellKind::Cmd, None) => c == '^',
+                _ => false,
+            };
+            let len = if escape {
+                c.len_utf8() + rest[c.len_utf8()..].chars().next().map_or(0, char::len_utf8)
+            } else {
+                match quote {
+                    None if Self::is_quote(c, shell) => quote = Some(c),
+                    Some(q) if q == c => quote = None,
+                    _ => {}
+                }
+                c.len_utf8()
+            };
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+        }
+        Ok(out)
+    }
+
+    /// `c` 在 `shell` 中是否为引号（cmd 只有双引号）
+    fn is_quote(c: char, shell: ShellKind) -> bool {
+        c == '"' || (c == '\'' && shell == ShellKind::Posix)
+    }
+
+    /// 给位于 `quote` 引号内（None 为引号外）的值加引号
+    fn quote_within(value: &str, shell: ShellKind, quote: Option<char>) -> Result<String> {
+        match (quote, shell) {
+            (None, _) => Self::quote(value, shell),
+            // 结束当前引号，写入单独加引号的值，再重新开始引号
+            (Some(q), ShellKind::Posix) => Ok(format!("{q}{}{q}", Self::quote_posix(value))),
+            // cmd 的双引号内 `&|<>^` 已是普通字符；引号本身和 `%`、`!` 无法转义，直接拒绝
+            (Some(_), ShellKind::Cmd) => {
+                Self::check_cmd(value)?;
+                Ok(value.to_string())
+            }
+        }
+    }
+
+    /// 按 `shell` 的规则给一个值加引号
+    pub fn quote(value: &str, shell: ShellKind) -> Result<String> {
+        match shell {
+            ShellKind::Posix => Ok(Self::quote_posix(value)),
+            ShellKind::Cmd => Self::quote_cmd(value),
+        }
+    }
+
+    /// POSIX shell：安全字符原样输出，否则用单引号包住（`'` 写作 `'\''`）
+    pub fn quote_posix(value: &str) -> String {
+        let safe = !value.is_empty()
+            && value.chars().all(|c| c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c));
+        if safe {
+            value.to_string()
+        } else {
+            format!("'{}'", value.replace('\'', "'\\''"))
+        }
+    }
+
+    /// cmd.exe：用双引号包住（引号内 `&|<>^` 不再是特殊字符）
+    ///
+    /// cmd 在引号内仍会展开 `%VAR%` / `!VAR!`，且无法转义引号，含这些字符的值直接拒绝。
+    pub fn quote_cmd(value: &str) -> Result<String> {
+        Self::check_cmd(value)?;
+        Ok(format!("\"{}\"", value))
+    }
+
+    /// 检查值能否放在 cmd 的双引号内
+    fn check_cmd(value: &str) -> Result<()> {
+        if let Some(c) = value.chars().find(|c| matches!(c, '"' | '%' | '!' | '\r' | '\n')) {
+            anyhow::bail!("值 {:?} 包含 cmd 无法安全转义的字符 {:?}，请改用参数模式", value, c);
+        }
+        Ok(())
+    }
+
+    /// 直接作为 cmd.exe 参数时是否安全：cmd 不按常规规则解析参数，
+    /// 未加引号的 `&|<>^()` 等会被当作命令分隔/重定向
+    pub fn is_cmd_safe_arg(value: &str) -> bool {
+        !value.chars().any(|c| "\"%!&|<>^()\r\n".contains(c))
+    }
+
+    /// 拆分模板为参数（尚未替换占位符）
+    pub fn tokenize(template: &str) -> Result<Vec<String>> {
+        let mut tokens = Vec::new();
+        let mut current = String::new();
+        // 当前参数是否已开始（`""` 也算一个空参数）
+        let mut in_token = false;
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                c if c.is_whitespace() => {
+                    if in_token {
+                        tokens.push(std::mem::take(&mut current));
+                        in_token = false;
+                    }
+                }
+                '\'' => {
+                    in_token = true;
+                    loop {
+                        match chars.next() {
+                            Some('\'') => break,
+                            Some(c) => current.push(c),
+                            None => anyhow::bail!("命令模板中的单引号不匹配"),
+                        }
+                    }
+                }
+                '"' => {
+                    in_token = true;
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some('\\') if matches!(chars.peek(), Some('"') | Some('\\')) => {
+                                current.push(chars.next().unwrap());
+                            }
+                            Some(c) => current.push(c),
+                            None => anyhow::bail!("命令模板中的双引号不匹配"),
+                        }
+                    }
+                }
+                c => {
+                    in_token = true;
+                    current.push(c);
+                }
+            }
+        }
+        if in_token {
+            tokens.push(current);
+        }
+        Ok(tokens)
+    }
+
+    /// 替换文本中的已知占位符（不加引号），未知占位符原样保留
+    pub fn expand(text: &str, vars: &HashMap<String, String>) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+            match Placeholder::parse(rest).and_then(|p| p.resolve(vars).map(|v| (v, p.len))) {
+                Some((value, len)) => {
+                    out.push_str(&value);
+                    rest = &rest[len..];
+                }
+                None => {
+                    out.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// 模板中的一个占位符：`{name}`、`{name:-default}`、`{env:NAME}`
+struct Placeholder<'a> {
+    name: &'a str,
+    default: Option<&'a str>,
+    /// 占位符在模板中的长度（含花括号）
+    len: usize,
+}
+
+impl<'a> Placeholder<'a> {
+    /// 识别 `text` 开头的占位符
+    fn parse(text: &'a str) -> Option<Self> {
+        let inner = text.strip_prefix('{')?;
+        let end = inner.find('}')?;
+        let body = &inner[..end];
+        let (name, default) = match body.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (body, None),
+        };
+        let ident = name.strip_prefix("env:").unwrap_or(name);
+        let valid = !ident.is_empty() && ident.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        valid.then_some(Self { name, default, len: end + 2 })
+    }
+
+    /// 取值：值为空时使用默认值；未知占位符返回 None（原样保留），未设置的环境变量视为空
+    fn resolve(&self, vars: &HashMap<String, String>) -> Option<String> {
+        let value = match vars.get(self.name) {
+            None if self.name.starts_with("env:") => Some(String::new()),
+            value => value.cloned(),
+        };
+        match (value, self.default) {
+            (Some(v), _) if !v.is_empty() => Some(v),
+            (_, Some(default)) => Some(default.to_string()),
+            (value, None) => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 各种可能被 shell 解释的目录名
+    const HOSTILE: &[&str] = &[
+        "/work/a b",
+        "/work/x\"; touch pwned; echo \"",
+        "/work/$(touch pwned)",
+        "/work/`touch pwned`",
+        "/work/it's",
+        "/work/a;b&c|d>e",
+        "/work/-rf *",
+        "/work/${HOME}",
+        "/work/new\nline",
+        "/work/100% & 50%",
+    ];
+
+    fn vars(project: &str) -> HashMap<String, String> {
+        HashMap::from([("project".to_string(), project.to_string())])
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = CommandTemplate::tokenize(r#"code  --new-window "{project}" 'a b' C:\Tools\x "say \"hi\"" """#).unwrap();
+        assert_eq!(tokens, vec!["code", "--new-window", "{project}", "a b", r"C:\Tools\x", r#"say "hi""#, ""]);
+        assert!(CommandTemplate::tokenize("code \"{project}").is_err());
+        assert!(CommandTemplate::tokenize("code '{project}").is_err());
+    }
+
+    #[test]
+    fn test_render_argv_keeps_hostile_paths_in_one_argument() {
+        for path in HOSTILE {
+            let argv = CommandTemplate::render_argv(r#"editor --goto "{project}:1" {project} {unknown}"#, &vars(path)).unwrap();
+            assert_eq!(argv, vec!["editor".to_string(), "--goto".to_string(), format!("{}:1", path), path.to_string(), "{unknown}".to_string()]);
+        }
+        assert!(CommandTemplate::render_argv("  ", &vars("/p")).is_err());
+    }
+
+    #[test]
+    fn test_render_shell_quotes_values() {
+        let render = |t: &str, p: &str| CommandTemplate::render_shell(t, &vars(p), ShellKind::Posix).unwrap();
+        assert_eq!(render("code {project}", "/work/app"), "code /work/app");
+        assert_eq!(render("code \"{project}\" | cat", "/work/a b"), "code '/work/a b' | cat");
+        assert_eq!(render("code '{project}'", "/work/it's"), r"code '/work/it'\''s'");
+
+        let cmd = |t: &str, p: &str| CommandTemplate::render_shell(t, &vars(p), ShellKind::Cmd);
+        assert_eq!(cmd("code \"{project}\"", r"C:\a&b").unwrap(), r#"code "C:\a&b""#);
+        assert!(cmd("code {project}", r"C:\100%").is_err());
+        assert!(cmd("code {project}", "C:\\a\"b").is_err());
+
+        // 占位符在更长的引号字符串中：值被单独加引号，不受外层引号影响
+        assert_eq!(render("code \"{project}/x.code-workspace\"", "/work/$(cmd)"), r#"code ""'/work/$(cmd)'"/x.code-workspace""#);
+        assert_eq!(render("code '{project}/x'", "/work/it's"), r"code '''/work/it'\''s''/x'");
+        assert_eq!(render(r#"echo "a\"{project}\"b""#, "/w/`x`"), r#"echo "a\""'/w/`x`'"\"b""#);
+        assert_eq!(cmd("code \"{project}\\x.code-workspace\"", r"C:\a&b").unwrap(), r#"code "C:\a&b\x.code-workspace""#);
+        assert_eq!(cmd("code {project}\\x", r"C:\a&b").unwrap(), r#"code "C:\a&b"\x"#);
+        for hostile in [r"C:\100%PATH%", "C:\\a\"&calc&\"", r"C:\a!x!"] {
+            assert!(cmd("code \"{project}\\x.code-workspace\"", hostile).is_err());
+            assert!(cmd("code {project}\\x", hostile).is_err());
+        }
+        assert_eq!(cmd("echo ^\"{project}", "a&b").unwrap(), r#"echo ^""a&b""#);
+
+        assert!(CommandTemplate::is_cmd_safe_arg(r"C:\Users\me\My Project"));
+        assert!(!CommandTemplate::is_cmd_safe_arg(r"C:\a&calc"));
+        assert!(!CommandTemplate::is_cmd_safe_arg(r"C:\(x)"));
+    }
+
+    #[test]
+    fn test_placeholders_defaults_and_env() {
+        use crate::models::git::{GitInfo, GitRemote};
+        use crate::models::project::VersionControl;
+
+        let mut project = Project::new("/work/acme/app".to_string(), "app".to_string(), VersionControl::Git);
+        project.project_type = Some("node".to_string());
+        project.git = Some(GitInfo {
+            branch: Some("feature/x".to_string()),
+            remotes: vec![
+                GitRemote { name: "upstream".to_string(), url: "git@example.com:up/app.git".to_string() },
+                GitRemote { name: "origin".to_string(), url: "git@example.com:me/app.git".to_string() },
+            ],
+            ..Default::default()
+        });
+        let workspaces = vec!["/work".to_string(), "/work/acme".to_string()];
+        let ctx = TemplateContext {
+            project_path: "/work/acme/app",
+            project: Some(&project),
+            workspaces: &workspaces,
+            file: Some("src/main.ts"),
+            line: Some(42),
+        };
+        let vars = CommandTemplate::variables(&ctx);
+
+        let argv = CommandTemplate::render_argv(
+            "tmux new -s {name} -c {workspace} {parent} {type} {branch} {remote_url} {file}:{line} {alias} {alias:-none}",
+            &vars,
+        ).unwrap();
+        assert_eq!(argv[3..], [
+            "app", "-c", "/work/acme", "/work/acme", "node", "feature/x", "git@example.com:me/app.git",
+            "/work/acme/app/src/main.ts:42", "", "none",
+        ]);
+
+        // 环境变量来自 variables 收集的进程环境
+        let mut vars = vars;
+        vars.insert("env:LAUNCHER_TEMPLATE_TEST".to_string(), "/opt/x y".to_string());
+        let argv = CommandTemplate::render_argv(
+            "{env:LAUNCHER_TEMPLATE_TEST}/bin {env:LAUNCHER_TEMPLATE_UNSET} {env:LAUNCHER_TEMPLATE_UNSET:-fallback} {project}/{name}.code-workspace",
+            &vars,
+        ).unwrap();
+        assert_eq!(argv, ["/opt/x y/bin", "", "fallback", "/work/acme/app/app.code-workspace"]);
+
+        // 没有缓存项目时从路径推断
+        let bare = CommandTemplate::variables(&TemplateContext {
+            project_path: "/elsewhere/tool",
+            project: None,
+            workspaces: &workspaces,
+            file: None,
+            line: None,
+        });
+        assert_eq!(bare["name"], "tool");
+        if let Some((key, value)) = std::env::vars().next() {
+            assert_eq!(bare[&format!("env:{}", key)], value);
+        }
+        assert_eq!(bare["workspace"], "/elsewhere");
+        assert_eq!(CommandTemplate::render_argv("x {branch:-main}", &bare).unwrap()[1], "main");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hostile_paths_are_not_interpreted_by_shell() {
+        use crate::services::test_utils::TempDir;
+        use std::process::Command;
+
+        let tmp = TempDir::new();
+        for name in HOSTILE {
+            let path = tmp.0.join(name.trim_start_matches("/work/")).to_string_lossy().into_owned();
+            std::fs::create_dir_all(&path).unwrap();
+
+            let script = CommandTemplate::render_shell("printf '%s' \"{project}\"", &vars(&path), ShellKind::Posix).unwrap();
+            let output = Command::new("sh").args(["-c", &script]).current_dir(&tmp.0).output().unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout), path);
+
+            // 占位符嵌在更长的引号字符串中
+            for template in [
+                "printf '%s' \"{project}/x.code-workspace\"",
+                "printf '%s' '{project}/x.code-workspace'",
+                "printf '%s' {project}/x.code-workspace",
+                "printf '%s' \"[\\\"{project}\\\"]\"",
+            ] {
+                let script = CommandTemplate::render_shell(template, &vars(&path), ShellKind::Posix).unwrap();
+                let output = Command::new("sh").args(["-c", &script]).current_dir(&tmp.0).output().unwrap();
+                let expected = if template.contains('[') {
+                    format!("[\"{}\"]", path)
+                } else {
+                    format!("{}/x.code-workspace", path)
+                };
+                assert_eq!(String::from_utf8_lossy(&output.stdout), expected, "{}", template);
+            }
+
+            let argv = CommandTemplate::render_argv("printf %s {project}", &vars(&path)).unwrap();
+            let output = Command::new(&argv[0]).args(&argv[1..]).current_dir(&tmp.0).output().unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout), path);
+        }
+        assert!(!tmp.join("pwned").exists());
+    }
+}
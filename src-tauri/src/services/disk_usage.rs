@@ -0,0 +1,261 @@
+//! 项目磁盘占用统计和构建产物清理
+
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+use super::git_service::{GitService, GIT_TIMEOUT};
+use crate::models::disk::{ArtifactUsage, DiskUsage, ProjectCleanup};
+use crate::models::project::Project;
+
+const NODE_ARTIFACTS: &[&str] = &["node_modules", "dist", "build", ".next", ".nuxt", ".svelte-kit", ".angular", ".output"];
+const PYTHON_ARTIFACTS: &[&str] = &[".venv", "venv", "build", "dist", ".pytest_cache", ".mypy_cache", ".ruff_cache"];
+const JVM_ARTIFACTS: &[&str] = &["build", "target", ".gradle"];
+
+pub struct DiskUsageService;
+
+impl DiskUsageService {
+    /// 按项目类型返回可清理的构建产物目录
+    ///
+    /// 类型未知时只清理不会与源码混淆的依赖目录
+    pub fn artifact_dirs(project_type: Option<&str>) -> &'static [&'static str] {
+        match project_type.unwrap_or("unknown") {
+            "rust" => &["target"],
+            "tauri" => &["node_modules", "dist", "target", "src-tauri/target"],
+            "javascript" | "typescript" | "react" | "react_ts" | "vue" | "vite" | "nextjs" | "nuxt"
+            | "angular" | "svelte" | "solidjs" | "remix" | "astro" | "qwik" | "nest" | "electron"
+            | "hexo" | "bun" => NODE_ARTIFACTS,
+            "deno" => &["node_modules"],
+            "python" | "django" | "flask" | "fastapi" | "jupyter" => PYTHON_ARTIFACTS,
+            "maven" | "scala" => &["target"],
+            "gradle" | "kotlin" | "spring" | "android" => JVM_ARTIFACTS,
+            "flutter" | "dart" => &["build", ".dart_tool"],
+            "dotnet" => &["bin", "obj"],
+            "php" | "laravel" => &["vendor", "node_modules"],
+            "ruby" | "rails" => &["vendor/bundle", "node_modules", "tmp/cache"],
+            "elixir" => &["_build", "deps"],
+            "haskell" => &["dist-newstyle", ".stack-work"],
+            "zig" => &["zig-out", ".zig-cache", "zig-cache"],
+            "c" | "cpp" => &["build", "cmake-build-debug", "cmake-build-release"],
+            "unity" => &["Library", "Temp", "Obj"],
+            "unreal" => &["Binaries", "Intermediate", "DerivedDataCache"],
+            _ => &["node_modules", ".venv"],
+        }
+    }
+
+    /// 统计项目总大小和各构建产物目录大小（单次遍历，不跟随符号链接）
+    pub fn measure(project_path: &str, project_type: Option<&str>) -> DiskUsage {
+        let root = Path::new(project_path);
+        let mut artifacts: Vec<ArtifactUsage> = Self::existing_artifacts(root, project_type).0
+            .into_iter()
+            .map(|path| ArtifactUsage { path: path.to_string(), size: 0 })
+            .collect();
+        let mut total = 0;
+
+        for entry in WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            total += size;
+
+            if let Ok(relative) = entry.path().strip_prefix(root) {
+                if let Some(artifact) = artifacts.iter_mut().find(|a| relative.starts_with(&a.path)) {
+                    artifact.size += size;
+                }
+            }
+        }
+
+        DiskUsage {
+            total,
+            artifacts,
+            measured_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// 清理项目的构建产物目录；`dry_run` 时只统计大小
+    pub fn cleanup(project_path: &str, name: &str, project_type: Option<&str>, dry_run: bool) -> ProjectCleanup {
+        let root = Path::new(project_path);
+        let mut artifacts = Vec::new();
+        let mut errors = Vec::new();
+        let (candidates, skipped) = Self::existing_artifacts(root, project_type);
+
+        for relative in candidates {
+            let dir = root.join(relative);
+            let size = Self::dir_size(&dir);
+            if !dry_run {
+                if let Err(e) = fs::remove_dir_all(&dir) {
+                    errors.push(format!("{}: {}", relative, e));
+                    continue;
+                }
+            }
+            artifacts.push(ArtifactUsage { path: relative.to_string(), size });
+        }
+
+        ProjectCleanup {
+            path: project_path.to_string(),
+            name: name.to_string(),
+            reclaimed: artifacts.iter().map(|a| a.size).sum(),
+            artifacts,
+            errors,
+            skipped,
+        }
+    }
+
+    /// 项目是否超过 `days` 天未打开（从未打开过视为满足）
+    pub fn not_opened_within(project: &Project, days: u32, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let Some(last_opened) = project.last_opened.as_deref() else { return true };
+        match chrono::DateTime::parse_from_rfc3339(last_opened) {
+            Ok(time) => now.signed_duration_since(time) > chrono::Duration::days(days as i64),
+            Err(_) => true,
+        }
+    }
+
+    /// 实际存在的构建产物目录（跳过符号链接，避免删除链接目标），以及因包含
+    /// 受版本控制的文件而跳过的目录及原因（如 Vue CLI 2 的 `build/`、提交了的 `vendor/`）
+    fn existing_artifacts(root: &Path, project_type: Option<&str>) -> (Vec<&'static str>, Vec<String>) {
+        let existing: Vec<&'static str> = Self::artifact_dirs(project_type)
+            .iter()
+            .copied()
+            .filter(|relative| {
+                fs::symlink_metadata(root.join(relative))
+                    .map(|m| m.is_dir())
+                    .unwrap_or(false)
+            })
+            .collect();
+        if existing.is_empty() {
+            return (existing, Vec::new());
+        }
+
+        let root_path = root.to_string_lossy();
+        let in_repo = GitService::run(&root_path, &["rev-parse", "--is-inside-work-tree"], GIT_TIMEOUT)
+            .is_ok_and(|out| out.trim() == "true");
+        if !in_repo {
+            return (existing, Vec::new());
+        }
+        let mut candidates = Vec::new();
+        let mut skipped = Vec::new();
+        for relative in existing {
+            match GitService::run(&root_path, &["ls-files", "--", relative], GIT_TIMEOUT) {
+                Ok(files) if files.trim().is_empty() => candidates.push(relative),
+                Ok(_) => skipped.push(format!("{}: 包含受版本控制的文件，已跳过", relative)),
+                Err(e) => skipped.push(format!("{}: 无法确认是否受版本控制（{}），已跳过", relative, e)),
+            }
+        }
+        (candidates, skipped)
+    }
+
+    fn dir_size(dir: &Path) -> u64 {
+        WalkDir::new(dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| e.metadata().ok())
+            .map(|m| m.len())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::project::VersionControl;
+    use crate::services::test_utils::{git, TempDir};
+
+    fn write(path: &Path, size: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![b'x'; size]).unwrap();
+    }
+
+    #[test]
+    fn test_measure_attributes_artifact_sizes() {
+        let tmp = TempDir::new();
+        write(&tmp.join("src/main.rs"), 100);
+        write(&tmp.join("target/debug/app"), 1000);
+        write(&tmp.join("src-tauri/target/release/app"), 500);
+        write(&tmp.join("node_modules/a/index.js"), 200);
+
+        let rust = DiskUsageService::measure(tmp.0.to_str().unwrap(), Some("rust"));
+        assert_eq!(rust.total, 1800);
+        assert_eq!(rust.artifacts, vec![ArtifactUsage { path: "target".to_string(), size: 1000 }]);
+
+        let tauri = DiskUsageService::measure(tmp.0.to_str().unwrap(), Some("tauri"));
+        assert_eq!(tauri.reclaimable(), 1700);
+        assert_eq!(tauri.artifacts.len(), 3);
+    }
+
+    #[test]
+    fn test_unknown_type_keeps_build_dirs() {
+        let tmp = TempDir::new();
+        write(&tmp.join("build/script.sh"), 10);
+        write(&tmp.join("node_modules/x.js"), 20);
+
+        let report = DiskUsageService::cleanup(tmp.0.to_str().unwrap(), "p", None, false);
+        assert_eq!(report.reclaimed, 20);
+        assert!(tmp.join("build/script.sh").exists());
+        assert!(!tmp.join("node_modules").exists());
+    }
+
+    #[test]
+    fn test_dry_run_does_not_delete() {
+        let tmp = TempDir::new();
+        write(&tmp.join("src/index.ts"), 10);
+        write(&tmp.join("node_modules/a.js"), 300);
+        write(&tmp.join("dist/bundle.js"), 50);
+
+        let dry = DiskUsageService::cleanup(tmp.0.to_str().unwrap(), "p", Some("vite"), true);
+        assert_eq!(dry.reclaimed, 350);
+        assert!(tmp.join("node_modules/a.js").exists());
+
+        let real = DiskUsageService::cleanup(tmp.0.to_str().unwrap(), "p", Some("vite"), false);
+        assert_eq!(real.reclaimed, 350);
+        assert!(real.errors.is_empty());
+        assert!(!tmp.join("node_modules").exists() && !tmp.join("dist").exists());
+        assert!(tmp.join("src/index.ts").exists());
+    }
+
+    #[test]
+    fn test_tracked_artifact_dir_is_kept() {
+        let tmp = TempDir::new();
+        write(&tmp.join("build/webpack.config.js"), 10);
+        write(&tmp.join("src/main.js"), 10);
+        write(&tmp.join("dist/app.js"), 30);
+        fs::write(tmp.join(".gitignore"), "dist/\n").unwrap();
+        git(&tmp.0, &["init"]);
+        git(&tmp.0, &["add", "."]);
+        git(&tmp.0, &["commit", "-m", "init"]);
+
+        let usage = DiskUsageService::measure(tmp.0.to_str().unwrap(), Some("vue"));
+        assert_eq!(usage.artifacts, vec![ArtifactUsage { path: "dist".to_string(), size: 30 }]);
+
+        let report = DiskUsageService::cleanup(tmp.0.to_str().unwrap(), "p", Some("vue"), false);
+        assert_eq!(report.reclaimed, 30);
+        assert_eq!(report.skipped.len(), 1);
+        assert!(report.skipped[0].starts_with("build:"));
+        assert!(tmp.join("build/webpack.config.js").exists());
+        assert!(!tmp.join("dist").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_artifact_is_skipped() {
+        let tmp = TempDir::new();
+        let shared = TempDir::new();
+        write(&shared.join("lib.js"), 10);
+        std::os::unix::fs::symlink(&shared.0, tmp.join("node_modules")).unwrap();
+
+        let report = DiskUsageService::cleanup(tmp.0.to_str().unwrap(), "p", Some("react"), false);
+        assert!(report.artifacts.is_empty());
+        assert!(shared.join("lib.js").exists());
+    }
+
+    #[test]
+    fn test_not_opened_within() {
+        let now = chrono::Utc::now();
+        let mut project = Project::new("/p".to_string(), "p".to_string(), VersionControl::Git);
+        assert!(DiskUsageService::not_opened_within(&project, 30, now));
+
+        project.last_opened = Some((now - chrono::Duration::days(10)).to_rfc3339());
+        assert!(!DiskUsageService::not_opened_within(&project, 30, now));
+        assert!(DiskUsageService::not_opened_within(&project, 7, now));
+    }
+}
@@ -0,0 +1,447 @@
+//! 工具链环境：按项目的 direnv / nix / mise(asdf) / nvm 配置计算启动时应有的环境变量
+//!
+//! 托盘应用启动的编辑器不经过 shell，继承不到这些工具设置的环境。这里在项目目录运行各工具的
+//! 导出命令得到环境变量，按项目缓存，配置文件的修改时间变化时重新计算。
+//! 导出的变量中可能包含密钥（如 `.envrc` 加载的 token），缓存只保存在内存中，不写入磁盘。
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use crate::models::launcher::ToolEnv;
+use super::process_scanner::ProcessScanner;
+
+/// 参与缓存判断的配置文件（`.env` 可能被 `.envrc` 的 `dotenv` 加载）
+const CONFIG_FILES: &[&str] = &[
+    ".envrc", ".env", "flake.nix", "flake.lock", ".tool-versions", ".mise.toml", "mise.toml", ".nvmrc",
+];
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(30);
+/// 首次求值 flake 可能需要下载依赖
+const NIX_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// `nix develop` 不会带入 shell 的变量
+const NIX_IGNORED_VARS: &[&str] = &[
+    "BASHOPTS", "HOME", "NIX_BUILD_TOP", "NIX_ENFORCE_PURITY", "NIX_LOG_FD", "NIX_REMOTE", "PPID",
+    "SHELL", "SHELLOPTS", "SSL_CERT_FILE", "TEMP", "TEMPDIR", "TERM", "TMP", "TMPDIR", "TZ", "UID",
+];
+
+/// 对比 nvm 前后环境时忽略的变量
+#[cfg(unix)]
+const SHELL_VARS: &[&str] = &["PWD", "OLDPWD", "SHLVL", "_"];
+
+/// 支持的工具
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tool {
+    Direnv,
+    Nix,
+    Mise,
+    #[cfg(unix)]
+    Nvm,
+}
+
+impl Tool {
+    fn name(self) -> &'static str {
+        match self {
+            Tool::Direnv => "direnv",
+            Tool::Nix => "nix",
+            Tool::Mise => "mise",
+            #[cfg(unix)]
+            Tool::Nvm => "nvm",
+        }
+    }
+}
+
+/// 一个工具导出的变量
+type Exported = (&'static str, BTreeMap<String, Option<String>>);
+
+pub struct EnvResolver {
+    cache: Mutex<HashMap<String, ToolEnv>>,
+    /// 计算时的基础环境（应用自身的环境）
+    base_env: HashMap<String, String>,
+}
+
+impl EnvResolver {
+    pub fn new() -> Self {
+        Self { cache: Mutex::new(HashMap::new()), base_env: std::env::vars().collect() }
+    }
+
+    /// 项目的工具链环境，项目没有相关配置时返回 None
+    ///
+    /// 配置文件未变化时直接使用缓存；计算可能需要较长时间（如首次求值 flake），应在阻塞线程中调用。
+    pub fn resolve(&self, project_path: &str) -> Option<ToolEnv> {
+        let dir = Path::new(project_path);
+        let tools = Self::detect(dir);
+        if tools.is_empty() {
+            return None;
+        }
+
+        let fingerprint = Self::fingerprint(dir);
+        if let Some(cached) = self.cache.lock().unwrap().get(project_path) {
+            if cached.fingerprint == fingerprint {
+                return Some(cached.clone());
+            }
+        }
+
+        // 计算期间不持有锁
+        let env = Self::compute(project_path, &tools, &self.base_env, fingerprint);
+        self.cache.lock().unwrap().insert(project_path.to_string(), env.clone());
+        Some(env)
+    }
+
+    /// 丢弃项目的缓存，下次启动时重新计算
+    pub fn invalidate(&self, project_path: &str) {
+        self.cache.lock().unwrap().remove(project_path);
+    }
+
+    /// 检测项目使用的工具
+    ///
+    /// 有 `.envrc` 时只使用 direnv（它通常已经通过 `use flake`、`use asdf` 等加载其他工具）。
+    fn detect(dir: &Path) -> Vec<Tool> {
+        if dir.join(".envrc").is_file() {
+            return vec![Tool::Direnv];
+        }
+        let mut tools = Vec::new();
+        if dir.join("flake.nix").is_file() {
+            tools.push(Tool::Nix);
+        }
+        if [".tool-versions", ".mise.toml", "mise.toml"].iter().any(|f| dir.join(f).is_file()) {
+            tools.push(Tool::Mise);
+        }
+        #[cfg(unix)]
+        if dir.join(".nvmrc").is_file() {
+            tools.push(Tool::Nvm);
+        }
+        tools
+    }
+
+    /// 配置文件及其修改时间
+    fn fingerprint(dir: &Path) -> Vec<(String, u64)> {
+        CONFIG_FILES
+            .iter()
+            .filter_map(|name| {
+                let modified = fs::metadata(dir.join(name)).ok()?.modified().ok()?;
+                let millis = modified.duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+                Some((name.to_string(), millis))
+            })
+            .collect()
+    }
+
+    /// 依次运行各工具的导出命令，后面的工具在前面工具的环境中运行
+    fn compute(
+        project_path: &str,
+        tools: &[Tool],
+        base_env: &HashMap<String, String>,
+        fingerprint: Vec<(String, u64)>,
+    ) -> ToolEnv {
+        let dir = Path::new(project_path);
+        let mut env = ToolEnv {
+            project_path: project_path.to_string(),
+            fingerprint,
+            resolved_at: chrono::Utc::now().to_rfc3339(),
+            ..Default::default()
+        };
+
+        for &tool in tools {
+            let current = Self::apply(base_env, &env.vars);
+            match Self::export(tool, dir, &current) {
+                Ok((source, vars)) => {
+                    env.sources.push(source.to_string());
+                    env.vars.extend(vars);
+                }
+                Err(e) => {
+                    eprintln!("[EnvResolver] {} 环境计算失败 ({}): {}", tool.name(), project_path, e);
+                    env.errors.push(format!("{}: {}", tool.name(), e));
+                }
+            }
+        }
+        env
+    }
+
+    /// 在基础环境上应用变量（None 为移除）
+    fn apply(base: &HashMap<String, String>, vars: &BTreeMap<String, Option<String>>) -> HashMap<String, String> {
+        let mut env = base.clone();
+        for (key, value) in vars {
+            match value {
+                Some(value) => env.insert(key.clone(), value.clone()),
+                None => env.remove(key),
+            };
+        }
+        env
+    }
+
+    fn export(tool: Tool, dir: &Path, env: &HashMap<String, String>) -> Result<Exported, String> {
+        match tool {
+            Tool::Direnv => {
+                let output = Self::run("direnv", &["export", "json"], dir, env, EXPORT_TIMEOUT)?;
+                if output.trim().is_empty() {
+                    return Ok(("direnv", BTreeMap::new()));
+                }
+                let vars = serde_json::from_str(&output).map_err(|e| format!("无法解析输出: {}", e))?;
+                Ok(("direnv", vars))
+            }
+            Tool::Nix => {
+                let args = ["--extra-experimental-features", "nix-command flakes", "print-dev-env", "--json"];
+                let output = Self::run("nix", &args, dir, env, NIX_TIMEOUT)?;
+                Self::parse_nix_env(&output, env.get("PATH").map(String::as_str))
+            }
+            Tool::Mise => {
+                if Self::find_program("mise", env).is_none() && dir.join(".tool-versions").is_file() {
+                    return Self::asdf_env(env);
+                }
+                let output = Self::run("mise", &["env", "--json"], dir, env, EXPORT_TIMEOUT)?;
+                let vars: BTreeMap<String, String> =
+                    serde_json::from_str(&output).map_err(|e| format!("无法解析输出: {}", e))?;
+                Ok(("mise", vars.into_iter().map(|(k, v)| (k, Some(v))).collect()))
+            }
+            #[cfg(unix)]
+            Tool::Nvm => Self::nvm_env(dir, env),
+        }
+    }
+
+    /// 解析 `nix print-dev-env --json`，只取导出的变量；PATH 追加在当前 PATH 之前（与 `nix develop` 一致）
+    fn parse_nix_env(output: &str, current_path: Option<&str>) -> Result<Exported, String> {
+        let json: serde_json::Value = serde_json::from_str(output).map_err(|e| format!("无法解析输出: {}", e))?;
+        let variables = json.get("variables").and_then(|v| v.as_object()).ok_or("输出中没有 variables")?;
+
+        let mut vars = BTreeMap::new();
+        for (name, var) in variables {
+            if var.get("type").and_then(|t| t.as_str()) != Some("exported") || NIX_IGNORED_VARS.contains(&name.as_str()) {
+                continue;
+            }
+            let Some(value) = var.get("value").and_then(|v| v.as_str()) else { continue };
+            let value = match (name.as_str(), current_path) {
+                ("PATH", Some(current)) => Self::prepend_path(&[PathBuf::from(value)], current),
+                _ => value.to_string(),
+            };
+            vars.insert(name.clone(), Some(value));
+        }
+        Ok(("nix", vars))
+    }
+
+    /// 没有安装 mise 时使用 asdf 的 shims（shims 按工作目录的 `.tool-versions` 选择版本）
+    fn asdf_env(env: &HashMap<String, String>) -> Result<Exported, String> {
+        let data_dir = env.get("ASDF_DATA_DIR")
+            .map(PathBuf::from)
+            .or_else(|| env.get("HOME").map(|home| Path::new(home).join(".asdf")))
+            .ok_or("未安装 mise 或 asdf")?;
+        let shims = data_dir.join("shims");
+        if !shims.is_dir() {
+            return Err("未安装 mise 或 asdf".to_string());
+        }
+        let path = Self::prepend_path(&[shims], env.get("PATH").map(String::as_str).unwrap_or(""));
+        Ok(("asdf", BTreeMap::from([("PATH".to_string(), Some(path))])))
+    }
+
+    /// nvm 是 shell 函数：在 bash 中加载 nvm 并 `nvm use`，取前后变化的变量
+    #[cfg(unix)]
+    fn nvm_env(dir: &Path, env: &HashMap<String, String>) -> Result<Exported, String> {
+        let nvm_dir = env.get("NVM_DIR")
+            .map(PathBuf::from)
+            .or_else(|| env.get("HOME").map(|home| Path::new(home).join(".nvm")))
+            .filter(|dir| dir.join("nvm.sh").is_file())
+            .ok_or("未安装 nvm")?;
+
+        let mut env = env.clone();
+        env.insert("NVM_DIR".to_string(), nvm_dir.to_string_lossy().into_owned());
+        let script = r#". "$NVM_DIR/nvm.sh" --no-use && nvm use >&2 && env -0"#;
+        let output = Self::run("bash", &["-c", script], dir, &env, EXPORT_TIMEOUT)?;
+
+        let vars = output
+            .split('\0')
+            .filter_map(|entry| entry.split_once('='))
+            .filter(|(key, value)| !SHELL_VARS.contains(key) && env.get(*key).map(String::as_str) != Some(*value))
+            .map(|(key, value)| (key.to_string(), Some(value.to_string())))
+            .collect();
+        Ok(("nvm", vars))
+    }
+
+    fn prepend_path(dirs: &[PathBuf], current: &str) -> String {
+        let paths: Vec<PathBuf> = dirs.iter()
+            .flat_map(std::env::split_paths)
+            .chain(std::env::split_paths(current))
+            .collect();
+        std::env::join_paths(paths)
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| current.to_string())
+    }
+
+    /// 在环境的 PATH 中查找程序
+    fn find_program(name: &str, env: &HashMap<String, String>) -> Option<PathBuf> {
+        let names: Vec<String> = if cfg!(windows) {
+            vec![format!("{}.exe", name), format!("{}.cmd", name)]
+        } else {
+            vec![name.to_string()]
+        };
+        std::env::split_paths(env.get("PATH")?)
+            .flat_map(|dir| names.iter().map(move |n| dir.join(n)))
+            .find(|path| path.is_file())
+    }
+
+    /// 在项目目录运行导出命令，返回标准输出；失败时返回标准错误的末尾几行
+    fn run(
+        program: &str,
+        args: &[&str],
+        dir: &Path,
+        env: &HashMap<String, String>,
+        timeout: Duration,
+    ) -> Result<String, String> {
+        let path = Self::find_program(program, env).ok_or_else(|| format!("未安装 {}", program))?;
+        let mut cmd = Command::new(path);
+        cmd.args(args)
+            .current_dir(dir)
+            .env_clear()
+            .envs(env)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        // 独立进程组，超时时连同子进程一起结束
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.process_group(0);
+        }
+
+        let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+        let readers = [
+            child.stdout.take().map(|s| Box::new(s) as Box<dyn Read + Send>),
+            child.stderr.take().map(|s| Box::new(s) as Box<dyn Read + Send>),
+        ]
+        .map(|pipe| {
+            std::thread::spawn(move || {
+                let mut buf = Vec::new();
+                if let Some(mut pipe) = pipe {
+                    let _ = pipe.read_to_end(&mut buf);
+                }
+                String::from_utf8_lossy(&buf).into_owned()
+            })
+        });
+
+        let started = Instant::now();
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if started.elapsed() >= timeout => {
+                    let _ = ProcessScanner::terminate_group(child.id(), true);
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(format!("超时（{} 秒）", timeout.as_secs()));
+                }
+                Ok(None) => std::thread::sleep(POLL_INTERVAL),
+                Err(e) => return Err(e.to_string()),
+            }
+        };
+
+        let [stdout, stderr] = readers.map(|reader| reader.join().unwrap_or_default());
+        if status.success() {
+            return Ok(stdout);
+        }
+        let lines: Vec<&str> = stderr.trim().lines().collect();
+        let message = lines[lines.len().saturating_sub(5)..].join("\n");
+        Err(if message.is_empty() {
+            format!("退出码 {}", status.code().unwrap_or(-1))
+        } else {
+            message
+        })
+    }
+}
+
+impl Default for EnvResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::SystemTime;
+    use crate::services::test_utils::{executable_script, TempDir};
+
+    fn setup() -> (TempDir, PathBuf, HashMap<String, String>) {
+        let tmp = TempDir::new();
+        let bin = tmp.join("bin");
+        let project = tmp.join("project");
+        fs::create_dir_all(&bin).unwrap();
+        fs::create_dir_all(&project).unwrap();
+        let base = HashMap::from([
+            ("PATH".to_string(), format!("{}:/usr/bin:/bin", bin.display())),
+            ("HOME".to_string(), tmp.join("home").to_string_lossy().into_owned()),
+            ("KEEP".to_string(), "1".to_string()),
+        ]);
+        (tmp, project, base)
+    }
+
+    #[test]
+    fn test_direnv_takes_precedence_and_unsets() {
+        let (tmp, project, base) = setup();
+        let bin = tmp.join("bin");
+        executable_script(&bin.join("direnv"), r#"echo '{"GOPATH":"/go","KEEP":null}'"#);
+        executable_script(&bin.join("nix"), "touch ../nix-called; exit 1");
+        fs::write(project.join(".envrc"), "use flake\n").unwrap();
+        fs::write(project.join("flake.nix"), "{}\n").unwrap();
+
+        let tools = EnvResolver::detect(&project);
+        assert_eq!(tools, vec![Tool::Direnv]);
+        let env = EnvResolver::compute(project.to_str().unwrap(), &tools, &base, Vec::new());
+        assert_eq!(env.sources, ["direnv"]);
+        assert_eq!(env.vars.get("GOPATH"), Some(&Some("/go".to_string())));
+        assert_eq!(env.vars.get("KEEP"), Some(&None));
+        assert!(!tmp.join("nix-called").exists());
+    }
+
+    #[test]
+    fn test_tools_are_layered_and_errors_recorded() {
+        let (tmp, project, base) = setup();
+        let bin = tmp.join("bin");
+        executable_script(&bin.join("nix"), r#"echo '{"variables":{"PATH":{"type":"exported","value":"/nix/bin"},"CC":{"type":"exported","value":"gcc"},"TMPDIR":{"type":"exported","value":"/build"},"out":{"type":"var","value":"/nix/out"}}}'"#);
+        // mise 在 nix 的环境中运行
+        executable_script(&bin.join("mise"), r#"printf '{"PATH":"/mise/bin:%s"}' "$PATH""#);
+        fs::write(project.join("flake.nix"), "{}\n").unwrap();
+        fs::write(project.join(".tool-versions"), "nodejs 20.0.0\n").unwrap();
+        fs::write(project.join(".nvmrc"), "20\n").unwrap();
+
+        let tools = EnvResolver::detect(&project);
+        let env = EnvResolver::compute(project.to_str().unwrap(), &tools, &base, Vec::new());
+        assert_eq!(env.sources, ["nix", "mise"]);
+        assert_eq!(env.vars.get("CC"), Some(&Some("gcc".to_string())));
+        assert!(!env.vars.contains_key("TMPDIR") && !env.vars.contains_key("out"));
+        let expected_path = format!("/mise/bin:/nix/bin:{}", base["PATH"]);
+        assert_eq!(env.vars.get("PATH"), Some(&Some(expected_path)));
+        assert_eq!(env.errors, ["nvm: 未安装 nvm"]);
+    }
+
+    #[test]
+    fn test_cache_invalidated_when_config_changes() {
+        let (tmp, project, base) = setup();
+        let bin = tmp.join("bin");
+        let output = tmp.join("mise-output");
+        fs::write(&output, r#"{"NODE_VERSION":"18"}"#).unwrap();
+        executable_script(&bin.join("mise"), &format!("cat {}", output.display()));
+        let config = project.join(".mise.toml");
+        fs::write(&config, "[tools]\nnode = \"18\"\n").unwrap();
+        let project_path = project.to_str().unwrap();
+
+        let mut resolver = EnvResolver::new();
+        resolver.base_env = base;
+        let first = resolver.resolve(project_path).unwrap();
+        assert_eq!(first.vars.get("NODE_VERSION"), Some(&Some("18".to_string())));
+
+        // 配置未变化时使用缓存
+        fs::write(&output, r#"{"NODE_VERSION":"20"}"#).unwrap();
+        assert_eq!(resolver.resolve(project_path).unwrap(), first);
+
+        File::options().write(true).open(&config).unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10)).unwrap();
+        let second = resolver.resolve(project_path).unwrap();
+        assert_eq!(second.vars.get("NODE_VERSION"), Some(&Some("20".to_string())));
+
+        assert!(resolver.resolve(tmp.0.to_str().unwrap()).is_none());
+    }
+}
@@ -0,0 +1,333 @@
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use crate::models::git::{BranchList, BranchSwitchResult, DirtyStrategy, GitBranch, GitWorktree};
+use super::git_service::{GitService, GIT_TIMEOUT};
+
+/// 最近分支列表的最大长度
+const MAX_RECENT_BRANCHES: usize = 10;
+
+/// 扫描 reflog 的最大条数
+const REFLOG_SCAN_LIMIT: &str = "500";
+
+pub struct GitBranchService;
+
+impl GitBranchService {
+    /// 列出本地分支、最近切换过的分支和工作树
+    pub fn list(repo_path: &str) -> Result<BranchList> {
+        let refs = GitService::run(
+            repo_path,
+            &[
+                "for-each-ref",
+                "--sort=-committerdate",
+                "--format=%(HEAD)%00%(refname:short)%00%(upstream:short)%00%(committerdate:iso-strict)",
+                "refs/heads",
+            ],
+            GIT_TIMEOUT,
+        )?;
+        let local = Self::parse_branches(&refs);
+        let current = local.iter().find(|b| b.is_current).map(|b| b.name.clone());
+
+        let reflog = GitService::run(
+            repo_path,
+            &["reflog", "show", "--format=%gs", "-n", REFLOG_SCAN_LIMIT, "HEAD"],
+            GIT_TIMEOUT,
+        )
+        .unwrap_or_default();
+        let recent = Self::parse_recent(&reflog, &local, current.as_deref());
+
+        let worktrees = GitService::run(repo_path, &["worktree", "list", "--porcelain"], GIT_TIMEOUT)
+            .map(|out| Self::parse_worktrees(&out))
+            .unwrap_or_default();
+
+        Ok(BranchList { current, local, recent, worktrees })
+    }
+
+    /// 切换分支，或为分支创建工作树
+    ///
+    /// 工作区有修改时按 `strategy` 拒绝或先 stash；创建工作树不受工作区状态影响。
+    /// stash 后切换失败时恢复 stash，恢复也失败时在错误中给出 stash 的提交，便于手动恢复
+    pub fn switch(
+        repo_path: &str,
+        branch: &str,
+        strategy: DirtyStrategy,
+        worktree: bool,
+    ) -> Result<BranchSwitchResult> {
+        if branch.trim().is_empty() || branch.starts_with('-') {
+            anyhow::bail!("分支名无效: {}", branch);
+        }
+
+        if worktree {
+            let path = Self::add_worktree(repo_path, branch)?;
+            return Ok(BranchSwitchResult {
+                switched: true,
+                dirty: false,
+                stashed: false,
+                branch: branch.to_string(),
+                project_path: path.to_string_lossy().into_owned(),
+                worktree: true,
+            });
+        }
+
+        let info = GitService::read_info(repo_path)?;
+        let dirty = info.staged + info.unstaged + info.conflicted > 0;
+        // 自动保存的 stash 提交
+        let mut stash = None;
+
+        if dirty {
+            match strategy {
+                DirtyStrategy::Refuse => {
+                    return Ok(BranchSwitchResult {
+                        switched: false,
+                        dirty: true,
+                        stashed: false,
+                        branch: branch.to_string(),
+                        project_path: repo_path.to_string(),
+                        worktree: false,
+                    });
+                }
+                DirtyStrategy::Stash => {
+                    let message = format!("project-launcher: 切换到 {} 前自动保存", branch);
+                    GitService::run(repo_path, &["stash", "push", "-m", &message], GIT_TIMEOUT)?;
+                    let commit = GitService::run(repo_path, &["rev-parse", "--verify", "refs/stash"], GIT_TIMEOUT)?;
+                    stash = Some(commit.trim().to_string());
+                }
+            }
+        }
+
+        // git switch 会自动从唯一匹配的远程分支创建跟踪分支
+        if let Err(e) = GitService::run(repo_path, &["switch", branch], GIT_TIMEOUT) {
+            if let Some(commit) = &stash {
+                if let Err(pop) = GitService::run(repo_path, &["stash", "pop", "--index"], GIT_TIMEOUT) {
+                    anyhow::bail!(
+                        "切换到 {} 失败: {}\n自动保存的修改未能恢复（{}），仍在 stash@{{0}}（{}），可用 `git stash pop` 手动恢复",
+                        branch, e, pop, commit,
+                    );
+                }
+            }
+            return Err(e);
+        }
+
+        Ok(BranchSwitchResult {
+            switched: true,
+            dirty,
+            stashed: stash.is_some(),
+            branch: branch.to_string(),
+            project_path: repo_path.to_string(),
+            worktree: false,
+        })
+    }
+
+    /// 为分支创建工作树（已存在时直接复用），返回工作树路径
+    fn add_worktree(repo_path: &str, branch: &str) -> Result<PathBuf> {
+        let existing = GitService::run(repo_path, &["worktree", "list", "--porcelain"], GIT_TIMEOUT)
+            .map(|out| Self::parse_worktrees(&out))
+            .unwrap_or_default();
+        if let Some(wt) = existing.iter().find(|w| w.branch.as_deref() == Some(branch)) {
+            return Ok(PathBuf::from(&wt.path));
+        }
+
+        let target = Self::worktree_path(Path::new(repo_path), branch);
+        if target.exists() {
+            anyhow::bail!("目标目录已存在: {}", target.display());
+        }
+        let target_str = target.to_string_lossy().into_owned();
+
+        let has_local = GitService::run(
+            repo_path,
+            &["rev-parse", "--verify", "--quiet", &format!("refs/heads/{}", branch)],
+            GIT_TIMEOUT,
+        )
+        .is_ok();
+
+        if has_local {
+            GitService::run(repo_path, &["worktree", "add", &target_str, branch], GIT_TIMEOUT)?;
+        } else {
+            // 远程分支会自动创建跟踪分支；不存在时基于当前 HEAD 新建
+            let dwim = GitService::run(repo_path, &["worktree", "add", &target_str, branch], GIT_TIMEOUT);
+            if dwim.is_err() {
+                GitService::run(repo_path, &["worktree", "add", "-b", branch, &target_str], GIT_TIMEOUT)?;
+            }
+        }
+
+        Ok(target)
+    }
+
+    /// 工作树默认放在仓库同级目录：`<repo>@<branch>`
+    fn worktree_path(repo: &Path, branch: &str) -> PathBuf {
+        let name = repo.file_name().and_then(|n| n.to_str()).unwrap_or("worktree");
+        let suffix: String = branch
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '-' })
+            .collect();
+        let dir = format!("{}@{}", name, suffix);
+        repo.parent().map(|p| p.join(&dir)).unwrap_or_else(|| PathBuf::from(dir))
+    }
+
+    /// 解析 for-each-ref 输出
+    fn parse_branches(output: &str) -> Vec<GitBranch> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split('\0');
+                let head = parts.next()?;
+                let name = parts.next()?.to_string();
+                let upstream = parts.next().filter(|s| !s.is_empty()).map(|s| s.to_string());
+                let last_commit_time = parts.next().filter(|s| !s.is_empty()).map(|s| s.to_string());
+                Some(GitBranch {
+                    name,
+                    upstream,
+                    last_commit_time,
+                    is_current: head == "*",
+                })
+            })
+            .collect()
+    }
+
+    /// 从 reflog 的 "checkout: moving from A to B" 记录中提取最近使用的分支
+    fn parse_recent(reflog: &str, local: &[GitBranch], current: Option<&str>) -> Vec<String> {
+        let mut recent: Vec<String> = Vec::new();
+
+        for line in reflog.lines() {
+            let Some(rest) = line.strip_prefix("checkout: moving from ") else { continue };
+            let Some((from, to)) = rest.rsplit_once(" to ") else { continue };
+
+            for name in [to, from] {
+                let is_local = local.iter().any(|b| b.name == name);
+                if is_local && Some(name) != current && !recent.iter().any(|r| r == name) {
+                    recent.push(name.to_string());
+                }
+            }
+            if recent.len() >= MAX_RECENT_BRANCHES {
+                break;
+            }
+        }
+
+        recent.truncate(MAX_RECENT_BRANCHES);
+        recent
+    }
+
+    /// 解析 `git worktree list --porcelain`
+    fn parse_worktrees(output: &str) -> Vec<GitWorktree> {
+        let mut worktrees = Vec::new();
+        let mut current: Option<GitWorktree> = None;
+
+        for line in output.lines() {
+            if let Some(path) = line.strip_prefix("worktree ") {
+                worktrees.extend(current.take());
+                current = Some(GitWorktree {
+                    path: path.to_string(),
+                    branch: None,
+                    detached: false,
+                });
+            } else if let Some(wt) = current.as_mut() {
+                if let Some(branch) = line.strip_prefix("branch ") {
+                    wt.branch = Some(branch.trim_start_matches("refs/heads/").to_string());
+                } else if line == "detached" {
+                    wt.detached = true;
+                }
+            }
+        }
+        worktrees.extend(current);
+        worktrees
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use crate::services::test_utils::{git, repo_with_remote, TempDir};
+
+    #[test]
+    fn test_parse_recent_from_reflog() {
+        let reflog = "\
+checkout: moving from feature to main
+commit: wip
+checkout: moving from main to feature
+checkout: moving from hotfix to main
+checkout: moving from main to gone
+";
+        let local: Vec<GitBranch> = ["main", "feature", "hotfix"]
+            .iter()
+            .map(|n| GitBranch { name: n.to_string(), upstream: None, last_commit_time: None, is_current: *n == "main" })
+            .collect();
+        let recent = GitBranchService::parse_recent(reflog, &local, Some("main"));
+        assert_eq!(recent, vec!["feature", "hotfix"]);
+    }
+
+    #[test]
+    fn test_list_and_switch() {
+        let tmp = TempDir::new();
+        let (_, work) = repo_with_remote(&tmp);
+        let repo = work.to_str().unwrap();
+
+        git(&work, &["branch", "feature"]);
+        git(&work, &["switch", "feature"]);
+        git(&work, &["switch", "main"]);
+
+        let list = GitBranchService::list(repo).unwrap();
+        assert_eq!(list.current.as_deref(), Some("main"));
+        assert_eq!(list.local.len(), 2);
+        assert_eq!(list.recent, vec!["feature"]);
+        assert_eq!(list.worktrees.len(), 1);
+
+        let result = GitBranchService::switch(repo, "feature", DirtyStrategy::Refuse, false).unwrap();
+        assert!(result.switched);
+        assert_eq!(GitService::read_info(repo).unwrap().branch.as_deref(), Some("feature"));
+    }
+
+    #[test]
+    fn test_dirty_tree_refuses_or_stashes() {
+        let tmp = TempDir::new();
+        let (_, work) = repo_with_remote(&tmp);
+        let repo = work.to_str().unwrap();
+        git(&work, &["branch", "feature"]);
+        fs::write(work.join("README.md"), "dirty\n").unwrap();
+
+        let refused = GitBranchService::switch(repo, "feature", DirtyStrategy::Refuse, false).unwrap();
+        assert!(!refused.switched);
+        assert!(refused.dirty);
+        assert_eq!(GitService::read_info(repo).unwrap().branch.as_deref(), Some("main"));
+
+        let stashed = GitBranchService::switch(repo, "feature", DirtyStrategy::Stash, false).unwrap();
+        assert!(stashed.switched && stashed.stashed);
+        let info = GitService::read_info(repo).unwrap();
+        assert_eq!(info.branch.as_deref(), Some("feature"));
+        assert!(!info.is_dirty());
+    }
+
+    #[test]
+    fn test_failed_switch_restores_stash() {
+        let tmp = TempDir::new();
+        let (_, work) = repo_with_remote(&tmp);
+        let repo = work.to_str().unwrap();
+        fs::write(work.join("README.md"), "dirty\n").unwrap();
+
+        assert!(GitBranchService::switch(repo, "missing", DirtyStrategy::Stash, false).is_err());
+        let info = GitService::read_info(repo).unwrap();
+        assert_eq!(info.branch.as_deref(), Some("main"));
+        assert!(info.is_dirty());
+        assert_eq!(fs::read_to_string(work.join("README.md")).unwrap(), "dirty\n");
+        assert!(GitService::run(repo, &["stash", "list"], GIT_TIMEOUT).unwrap().trim().is_empty());
+    }
+
+    #[test]
+    fn test_worktree_for_new_and_existing_branch() {
+        let tmp = TempDir::new();
+        let (_, work) = repo_with_remote(&tmp);
+        let repo = work.to_str().unwrap();
+        fs::write(work.join("README.md"), "dirty is fine for worktrees\n").unwrap();
+
+        let result = GitBranchService::switch(repo, "feat/new", DirtyStrategy::Refuse, true).unwrap();
+        assert!(result.worktree);
+        assert_eq!(result.project_path, tmp.join("work@feat-new").to_string_lossy());
+        let info = GitService::read_info(&result.project_path).unwrap();
+        assert_eq!(info.branch.as_deref(), Some("feat/new"));
+
+        // 再次请求同一分支时复用已有工作树
+        let again = GitBranchService::switch(repo, "feat/new", DirtyStrategy::Refuse, true).unwrap();
+        assert_eq!(again.project_path, result.project_path);
+
+        assert!(GitBranchService::switch(repo, "--force", DirtyStrategy::Refuse, false).is_err());
+    }
+}
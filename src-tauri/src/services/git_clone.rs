@@ -0,0 +1,203 @@
+//! 克隆远程仓库到工作区
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use anyhow::Result;
+use crate::models::git::CloneLayout;
+use super::git_hosting::GitHosting;
+use super::git_service::GitService;
+
+/// 失败时保留的 stderr 末尾行数
+const ERROR_TAIL_LINES: usize = 5;
+
+/// 一行 `git clone --progress` 输出
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloneProgressLine {
+    pub phase: String,
+    pub percent: Option<u32>,
+}
+
+pub struct GitClone;
+
+impl GitClone {
+    /// 根据地址和布局计算克隆目标目录
+    ///
+    /// `Flat` 为 `<workspace>/<repo>`，`Hosted` 为 `<workspace>/<host>/<org>/<repo>`；
+    /// 本地路径没有主机信息，总是使用 `Flat`。`folder_name` 可覆盖最后一级目录名。
+    pub fn target_dir(
+        workspace: &Path,
+        url: &str,
+        layout: CloneLayout,
+        folder_name: Option<&str>,
+    ) -> Result<PathBuf> {
+        let mut segments: Vec<String> = match GitHosting::parse_remote(url) {
+            Some(location) => {
+                let mut segments = vec![location.host];
+                segments.extend(location.path.split('/').map(|s| s.to_string()));
+                segments
+            }
+            None => {
+                let local = url.trim().trim_start_matches("file://").trim_end_matches(['/', '\\']);
+                let name = local.rsplit(['/', '\\']).next().unwrap_or(local);
+                vec![name.trim_end_matches(".git").to_string()]
+            }
+        };
+
+        if let Some(name) = folder_name.map(str::trim).filter(|n| !n.is_empty()) {
+            if let Some(last) = segments.last_mut() {
+                *last = name.to_string();
+            }
+        }
+
+        if layout == CloneLayout::Flat || segments.len() == 1 {
+            segments = segments.split_off(segments.len() - 1);
+        }
+
+        let mut target = workspace.to_path_buf();
+        for segment in &segments {
+            if segment.is_empty() || segment == "." || segment == ".." || segment.contains(['/', '\\']) {
+                anyhow::bail!("无法从地址推导目录名: {}", url);
+            }
+            target.push(segment);
+        }
+        Ok(target)
+    }
+
+    /// 克隆仓库到 `target`，每行进度回调一次
+    ///
+    /// 目标目录已存在时报错；克隆失败会清理已创建的目录
+    pub fn clone(url: &str, target: &Path, mut on_progress: impl FnMut(CloneProgressLine)) -> Result<()> {
+        if url.trim().is_empty() || url.starts_with('-') {
+            anyhow::bail!("仓库地址无效: {}", url);
+        }
+        if target.exists() {
+            anyhow::bail!("目标目录已存在: {}", target.display());
+        }
+        let parent = target.parent().ok_or_else(|| anyhow::anyhow!("目标目录无效"))?;
+        std::fs::create_dir_all(parent)?;
+
+        let mut child = GitService::command(&parent.to_string_lossy())
+            .args(["clone", "--progress", "--", url])
+            .arg(target)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // git 用 \r 刷新同一行进度，逐段读取
+        let mut stderr = child.stderr.take().ok_or_else(|| anyhow::anyhow!("无法读取 git 输出"))?;
+        let mut tail: Vec<String> = Vec::new();
+        let mut line = Vec::new();
+        let mut buf = [0u8; 1024];
+        loop {
+            let n = stderr.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            for &byte in &buf[..n] {
+                if byte != b'\r' && byte != b'\n' {
+                    line.push(byte);
+                    continue;
+                }
+                let text = String::from_utf8_lossy(&line).trim().to_string();
+                line.clear();
+                if text.is_empty() {
+                    continue;
+                }
+                if let Some(progress) = Self::parse_progress(&text) {
+                    on_progress(progress);
+                }
+                tail.push(text);
+                if tail.len() > ERROR_TAIL_LINES {
+                    tail.remove(0);
+                }
+            }
+        }
+
+        let status = child.wait()?;
+        if !status.success() {
+            let _ = std::fs::remove_dir_all(target);
+            anyhow::bail!("{}", tail.join("\n"));
+        }
+        Ok(())
+    }
+
+    /// 解析进度行，如 `Receiving objects:  45% (45/100), 1.20 MiB | 2.00 MiB/s`
+    pub fn parse_progress(line: &str) -> Option<CloneProgressLine> {
+        let line = line.strip_prefix("remote: ").unwrap_or(line);
+
+        if line.starts_with("Cloning into") {
+            return Some(CloneProgressLine { phase: "Cloning".to_string(), percent: None });
+        }
+
+        let (phase, rest) = line.split_once(':')?;
+        let percent = rest.trim_start().split_once('%')?.0.trim().parse().ok()?;
+        Some(CloneProgressLine { phase: phase.trim().to_string(), percent: Some(percent) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_utils::{file_url, repo_with_remote, TempDir};
+
+    #[test]
+    fn test_target_dir_layouts() {
+        let ws = Path::new("/ws");
+        let flat = GitClone::target_dir(ws, "git@github.com:org/repo.git", CloneLayout::Flat, None).unwrap();
+        assert_eq!(flat, Path::new("/ws/repo"));
+
+        let hosted = GitClone::target_dir(ws, "https://gitlab.com/group/sub/repo.git", CloneLayout::Hosted, None).unwrap();
+        assert_eq!(hosted, Path::new("/ws/gitlab.com/group/sub/repo"));
+
+        let renamed = GitClone::target_dir(ws, "git@github.com:org/repo.git", CloneLayout::Hosted, Some("api")).unwrap();
+        assert_eq!(renamed, Path::new("/ws/github.com/org/api"));
+
+        let local = GitClone::target_dir(ws, "file:///srv/git/tool.git", CloneLayout::Hosted, None).unwrap();
+        assert_eq!(local, Path::new("/ws/tool"));
+
+        assert!(GitClone::target_dir(ws, "git@github.com:org/repo.git", CloneLayout::Flat, Some("..")).is_err());
+    }
+
+    #[test]
+    fn test_parse_progress() {
+        assert_eq!(
+            GitClone::parse_progress("Receiving objects:  45% (45/100), 1.20 MiB | 2.00 MiB/s"),
+            Some(CloneProgressLine { phase: "Receiving objects".to_string(), percent: Some(45) })
+        );
+        assert_eq!(
+            GitClone::parse_progress("remote: Counting objects: 100% (3/3), done."),
+            Some(CloneProgressLine { phase: "Counting objects".to_string(), percent: Some(100) })
+        );
+        assert_eq!(GitClone::parse_progress("warning: You appear to have cloned an empty repository."), None);
+    }
+
+    #[test]
+    fn test_clone_local_bare_repo() {
+        let tmp = TempDir::new();
+        let (remote, _) = repo_with_remote(&tmp);
+        let url = file_url(&remote);
+
+        let target = GitClone::target_dir(&tmp.join("ws"), &url, CloneLayout::Flat, None).unwrap();
+        assert_eq!(target, tmp.join("ws").join("remote"));
+
+        let mut phases = Vec::new();
+        GitClone::clone(&url, &target, |p| phases.push(p.phase)).unwrap();
+        assert!(target.join("README.md").exists());
+        assert!(phases.iter().any(|p| p == "Cloning"), "{:?}", phases);
+
+        // 目标已存在
+        assert!(GitClone::clone(&url, &target, |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_failed_clone_cleans_up() {
+        let tmp = TempDir::new();
+        let target = tmp.join("ws").join("missing");
+        let err = GitClone::clone("file:///nonexistent/project-launcher.git", &target, |_| {}).unwrap_err();
+        assert!(!err.to_string().is_empty());
+        assert!(!target.exists());
+        assert!(GitClone::clone("--upload-pack=touch", &tmp.join("x"), |_| {}).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+//! Git 仓库状态读取
+//! 通过有超时限制的 `git` 子进程读取分支、工作区状态、提交和远程信息
+
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use anyhow::Result;
+use crate::models::git::{GitCommit, GitInfo, GitRemote};
+
+/// 单次 git 调用的默认超时
+pub const GIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct GitService;
+
+impl GitService {
+    /// 读取仓库当前状态
+    pub fn read_info(repo_path: &str) -> Result<GitInfo> {
+        let status = Self::run(repo_path, &["status", "--porcelain=v2", "--branch"], GIT_TIMEOUT)?;
+        let mut info = Self::parse_status(&status);
+
+        // 空仓库没有提交，git log 会失败，此时保留 None
+        info.last_commit = Self::run(
+            repo_path,
+            &["log", "-1", "--format=%H%x00%ct%x00%an%x00%s"],
+            GIT_TIMEOUT,
+        )
+        .ok()
+        .and_then(|out| Self::parse_last_commit(&out));
+
+        info.remotes = Self::run(repo_path, &["remote", "-v"], GIT_TIMEOUT)
+            .map(|out| Self::parse_remotes(&out))
+            .unwrap_or_default();
+
+        info.refreshed_at = chrono::Utc::now().to_rfc3339();
+        Ok(info)
+    }
+
+    /// 在仓库目录执行 git 命令，超时后终止子进程
+    pub fn run(repo_path: &str, args: &[&str], timeout: Duration) -> Result<String> {
+        let mut child = Self::command(repo_path)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // 在独立线程读取输出，避免管道写满导致子进程阻塞
+        let mut stdout = child.stdout.take();
+        let mut stderr = child.stderr.take();
+        let stdout_reader = std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(out) = stdout.as_mut() {
+                let _ = out.read_to_end(&mut buf);
+            }
+            buf
+        });
+        let stderr_reader = std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(err) = stderr.as_mut() {
+                let _ = err.read_to_end(&mut buf);
+            }
+            buf
+        });
+
+        let deadline = Instant::now() + timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                anyhow::bail!("git {} 超时", args.join(" "));
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+
+        let stdout = stdout_reader.join().unwrap_or_default();
+        let stderr = stderr_reader.join().unwrap_or_default();
+
+        if !status.success() {
+            let msg = String::from_utf8_lossy(&stderr).trim().to_string();
+            anyhow::bail!("{}", if msg.is_empty() {
+                format!("git {} 失败，退出码: {}", args.join(" "), status.code().unwrap_or(-1))
+            } else {
+                msg
+            });
+        }
+
+        Ok(String::from_utf8_lossy(&stdout).into_owned())
+    }
+
+    /// 构造在仓库目录执行、不会弹出交互提示的 git 命令
+    pub fn command(repo_path: &str) -> Command {
+        let mut cmd = Command::new("git");
+        cmd.arg("-C")
+            .arg(Path::new(repo_path))
+            .arg("--no-optional-locks")
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .env("LC_ALL", "C");
+        cmd
+    }
+
+    /// 解析 `git status --porcelain=v2 --branch` 输出
+    pub fn parse_status(output: &str) -> GitInfo {
+        let mut info = GitInfo::default();
+
+        for line in output.lines() {
+            if let Some(header) = line.strip_prefix("# ") {
+                let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+                match key {
+                    "branch.oid" if value != "(initial)" => {
+                        info.head = Some(value.chars().take(12).collect());
+                    }
+                    "branch.head" => {
+                        if value == "(detached)" {
+                            info.detached = true;
+                        } else {
+                            info.branch = Some(value.to_string());
+                        }
+                    }
+                    "branch.upstream" => info.upstream = Some(value.to_string()),
+                    "branch.ab" => {
+                        for part in value.split_whitespace() {
+                            if let Some(n) = part.strip_prefix('+') {
+                                info.ahead = n.parse().unwrap_or(0);
+                            } else if let Some(n) = part.strip_prefix('-') {
+                                info.behind = n.parse().unwrap_or(0);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            let mut parts = line.splitn(3, ' ');
+            match (parts.next(), parts.next()) {
+                (Some("1") | Some("2"), Some(xy)) => {
+                    let mut flags = xy.chars();
+                    if flags.next().is_some_and(|c| c != '.') {
+                        info.staged += 1;
+                    }
+                    if flags.next().is_some_and(|c| c != '.') {
+                        info.unstaged += 1;
+                    }
+                }
+                (Some("u"), _) => info.conflicted += 1,
+                (Some("?"), _) => info.untracked += 1,
+                _ => {}
+            }
+        }
+
+        info
+    }
+
+    /// 解析 `git log -1 --format=%H%x00%ct%x00%an%x00%s` 输出
+    pub fn parse_last_commit(output: &str) -> Option<GitCommit> {
+        let line = output.trim_end_matches('\n');
+        let mut parts = line.splitn(4, '\0');
+        let hash = parts.next()?.to_string();
+        let timestamp: i64 = parts.next()?.parse().ok()?;
+        let author = parts.next()?.to_string();
+        let subject = parts.next().unwrap_or("").to_string();
+
+        let time = chrono::DateTime::from_timestamp(timestamp, 0)?.to_rfc3339();
+        Some(GitCommit { hash, time, author, subject })
+    }
+
+    /// 解析 `git remote -v` 输出（只取 fetch 地址）
+    pub fn parse_remotes(output: &str) -> Vec<GitRemote> {
+        output
+            .lines()
+            .filter(|l| l.ends_with("(fetch)"))
+            .filter_map(|l| {
+                let mut parts = l.split_whitespace();
+                Some(GitRemote {
+                    name: parts.next()?.to_string(),
+                    url: parts.next()?.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_branch_and_counts() {
+        let output = "\
+# branch.oid 1234567890abcdef1234567890abcdef12345678
+# branch.head main
+# branch.upstream origin/main
+# branch.ab +2 -3
+1 .M N... 100644 100644 100644 aaa bbb src/lib.rs
+1 M. N... 100644 100644 100644 aaa bbb README.md
+1 MM N... 100644 100644 100644 aaa bbb Cargo.toml
+2 R. N... 100644 100644 100644 aaa bbb R100 new.rs\told.rs
+u UU N... 100644 100644 100644 100644 aaa bbb ccc conflict.rs
+? notes.txt
+? tmp/
+";
+        let info = GitService::parse_status(output);
+        assert_eq!(info.branch.as_deref(), Some("main"));
+        assert_eq!(info.head.as_deref(), Some("1234567890ab"));
+        assert!(!info.detached);
+        assert_eq!(info.upstream.as_deref(), Some("origin/main"));
+        assert_eq!((info.ahead, info.behind), (2, 3));
+        assert_eq!(info.staged, 3);
+        assert_eq!(info.unstaged, 2);
+        assert_eq!(info.conflicted, 1);
+        assert_eq!(info.untracked, 2);
+        assert!(info.is_dirty());
+    }
+
+    #[test]
+    fn test_parse_status_detached_and_initial() {
+        let detached = GitService::parse_status("# branch.oid abcdef\n# branch.head (detached)\n");
+        assert!(detached.detached);
+        assert_eq!(detached.branch, None);
+        assert!(!detached.is_dirty());
+
+        let initial = GitService::parse_status("# branch.oid (initial)\n# branch.head main\n");
+        assert_eq!(initial.head, None);
+        assert_eq!(initial.branch.as_deref(), Some("main"));
+        assert_eq!(initial.upstream, None);
+    }
+
+    #[test]
+    fn test_parse_last_commit() {
+        let commit = GitService::parse_last_commit("abc123\u{0}1700000000\u{0}Jane Doe\u{0}Fix: a bug\n").unwrap();
+        assert_eq!(commit.hash, "abc123");
+        assert_eq!(commit.author, "Jane Doe");
+        assert_eq!(commit.subject, "Fix: a bug");
+        assert!(commit.time.starts_with("2023-11-14"));
+        assert!(GitService::parse_last_commit("").is_none());
+    }
+
+    #[test]
+    fn test_parse_remotes() {
+        let output = "\
+origin\tgit@github.com:org/repo.git (fetch)
+origin\tgit@github.com:org/repo.git (push)
+upstream\thttps://github.com/up/repo.git (fetch)
+upstream\thttps://github.com/up/repo.git (push)
+";
+        let remotes = GitService::parse_remotes(output);
+        assert_eq!(remotes.len(), 2);
+        assert_eq!(remotes[0].name, "origin");
+        assert_eq!(remotes[0].url, "git@github.com:org/repo.git");
+        assert_eq!(remotes[1].name, "upstream");
+    }
+}
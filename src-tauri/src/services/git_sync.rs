@@ -0,0 +1,149 @@
+use std::time::Duration;
+use crate::models::git::{GitSyncOutcome, GitSyncResult, GitSyncSummary};
+use super::git_service::GitService;
+
+/// fetch / merge 的超时（网络操作需要更长时间）
+const GIT_SYNC_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub struct GitSync;
+
+impl GitSync {
+    /// 同步单个仓库：fetch，`pull` 为 true 时再执行 fast-forward 合并
+    ///
+    /// 有未提交修改或没有上游的仓库直接跳过
+    pub fn sync_one(path: &str, pull: bool) -> GitSyncResult {
+        let before = match GitService::read_info(path) {
+            Ok(info) => info,
+            Err(e) => return GitSyncResult::new(path, GitSyncOutcome::Failed, Some(e.to_string()), None),
+        };
+
+        if before.upstream.is_none() {
+            let reason = if before.detached { "detached HEAD" } else { "没有上游分支" };
+            return GitSyncResult::new(path, GitSyncOutcome::Skipped, Some(reason.to_string()), Some(before));
+        }
+        if before.is_dirty() {
+            return GitSyncResult::new(path, GitSyncOutcome::Skipped, Some("有未提交的修改".to_string()), Some(before));
+        }
+
+        if let Err(e) = GitService::run(path, &["fetch", "--prune"], GIT_SYNC_TIMEOUT) {
+            return GitSyncResult::new(path, GitSyncOutcome::Failed, Some(e.to_string()), Some(before));
+        }
+
+        let fetched = GitService::read_info(path).unwrap_or(before);
+        if !pull || fetched.behind == 0 {
+            let outcome = if fetched.behind == 0 { GitSyncOutcome::UpToDate } else { GitSyncOutcome::Fetched };
+            return GitSyncResult::new(path, outcome, None, Some(fetched));
+        }
+
+        let behind = fetched.behind;
+        match GitService::run(path, &["merge", "--ff-only", "@{upstream}"], GIT_SYNC_TIMEOUT) {
+            Ok(_) => {
+                let pulled = GitService::read_info(path).unwrap_or(fetched);
+                let message = format!("快进 {} 个提交", behind);
+                GitSyncResult::new(path, GitSyncOutcome::Pulled, Some(message), Some(pulled))
+            }
+            // 分叉等无法快进的情况
+            Err(e) => GitSyncResult::new(path, GitSyncOutcome::Failed, Some(e.to_string()), Some(fetched)),
+        }
+    }
+
+    /// 汇总同步结果
+    pub fn summarize(results: &[GitSyncResult]) -> GitSyncSummary {
+        let count = |outcome: GitSyncOutcome| results.iter().filter(|r| r.outcome == outcome).count();
+
+        GitSyncSummary {
+            total: results.len(),
+            up_to_date: count(GitSyncOutcome::UpToDate),
+            fetched: count(GitSyncOutcome::Fetched),
+            pulled: count(GitSyncOutcome::Pulled),
+            skipped: results.iter().filter(|r| r.outcome == GitSyncOutcome::Skipped).cloned().collect(),
+            failures: results.iter().filter(|r| r.outcome == GitSyncOutcome::Failed).cloned().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use crate::services::test_utils::{file_url, git, repo_with_remote, TempDir};
+
+    /// 通过另一个克隆向远程推送一个提交
+    fn push_remote_commit(tmp: &TempDir, remote: &Path, file: &str) {
+        let other = tmp.join(&format!("other-{}", file));
+        git(&tmp.0, &["clone", &file_url(remote), other.to_str().unwrap()]);
+        fs::write(other.join(file), "remote\n").unwrap();
+        git(&other, &["add", "."]);
+        git(&other, &["commit", "-m", "remote change"]);
+        git(&other, &["push"]);
+    }
+
+    #[test]
+    fn test_fetch_only_reports_behind() {
+        let tmp = TempDir::new();
+        let (remote, work) = repo_with_remote(&tmp);
+        push_remote_commit(&tmp, &remote, "a.txt");
+
+        let result = GitSync::sync_one(work.to_str().unwrap(), false);
+        assert_eq!(result.outcome, GitSyncOutcome::Fetched, "{:?}", result);
+        assert_eq!(result.git.unwrap().behind, 1);
+        assert!(!work.join("a.txt").exists());
+    }
+
+    #[test]
+    fn test_pull_fast_forwards_clean_repo() {
+        let tmp = TempDir::new();
+        let (remote, work) = repo_with_remote(&tmp);
+        push_remote_commit(&tmp, &remote, "a.txt");
+
+        let result = GitSync::sync_one(work.to_str().unwrap(), true);
+        assert_eq!(result.outcome, GitSyncOutcome::Pulled, "{:?}", result);
+        assert_eq!(result.git.unwrap().behind, 0);
+        assert!(work.join("a.txt").exists());
+
+        let again = GitSync::sync_one(work.to_str().unwrap(), true);
+        assert_eq!(again.outcome, GitSyncOutcome::UpToDate);
+    }
+
+    #[test]
+    fn test_skips_dirty_and_no_upstream() {
+        let tmp = TempDir::new();
+        let (remote, work) = repo_with_remote(&tmp);
+        push_remote_commit(&tmp, &remote, "a.txt");
+
+        fs::write(work.join("README.md"), "dirty\n").unwrap();
+        let dirty = GitSync::sync_one(work.to_str().unwrap(), true);
+        assert_eq!(dirty.outcome, GitSyncOutcome::Skipped);
+        assert!(!work.join("a.txt").exists());
+
+        let local = tmp.join("local");
+        git(&tmp.0, &["init", "local"]);
+        let no_upstream = GitSync::sync_one(local.to_str().unwrap(), false);
+        assert_eq!(no_upstream.outcome, GitSyncOutcome::Skipped);
+    }
+
+    #[test]
+    fn test_diverged_pull_fails_and_summary_lists_failures() {
+        let tmp = TempDir::new();
+        let (remote, work) = repo_with_remote(&tmp);
+        push_remote_commit(&tmp, &remote, "a.txt");
+
+        fs::write(work.join("b.txt"), "local\n").unwrap();
+        git(&work, &["add", "."]);
+        git(&work, &["commit", "-m", "local change"]);
+
+        let diverged = GitSync::sync_one(work.to_str().unwrap(), true);
+        assert_eq!(diverged.outcome, GitSyncOutcome::Failed, "{:?}", diverged);
+
+        // 远程不可达
+        git(&work, &["remote", "set-url", "origin", "file:///nonexistent/project-launcher.git"]);
+        let unreachable = GitSync::sync_one(work.to_str().unwrap(), false);
+        assert_eq!(unreachable.outcome, GitSyncOutcome::Failed);
+
+        let summary = GitSync::summarize(&[diverged, unreachable]);
+        assert_eq!(summary.total, 2);
+        assert_eq!(summary.failures.len(), 2);
+        assert!(summary.failures.iter().all(|f| f.message.is_some()));
+    }
+}
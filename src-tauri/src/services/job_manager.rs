@@ -0,0 +1,150 @@
+//! 后台任务管理
+//! 负责登记长时间运行的任务、提供取消标记以及进度事件节流
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// 取消标记（在任务线程间共享）
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+struct JobEntry {
+    kind: String,
+    token: CancelToken,
+}
+
+/// 后台任务登记表
+pub struct JobManager {
+    jobs: Mutex<HashMap<String, JobEntry>>,
+}
+
+impl JobManager {
+    pub fn new() -> Self {
+        Self {
+            jobs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 登记新任务，同一类型的任务同时只允许运行一个
+    pub fn start(&self, kind: &str) -> Result<(String, CancelToken), String> {
+        let mut jobs = self.jobs.lock().unwrap();
+        if jobs.values().any(|j| j.kind == kind) {
+            return Err("已有相同任务正在运行".to_string());
+        }
+
+        let job_id = Uuid::new_v4().to_string();
+        let token = CancelToken::new();
+        jobs.insert(job_id.clone(), JobEntry {
+            kind: kind.to_string(),
+            token: token.clone(),
+        });
+        Ok((job_id, token))
+    }
+
+    /// 请求取消任务（任务自行在检查点退出）
+    pub fn cancel(&self, job_id: &str) -> bool {
+        let jobs = self.jobs.lock().unwrap();
+        match jobs.get(job_id) {
+            Some(job) => {
+                job.token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 任务结束后移除登记
+    pub fn finish(&self, job_id: &str) {
+        self.jobs.lock().unwrap().remove(job_id);
+    }
+
+    /// 指定类型的任务是否正在运行
+    pub fn is_running(&self, kind: &str) -> bool {
+        self.jobs.lock().unwrap().values().any(|j| j.kind == kind)
+    }
+}
+
+impl Default for JobManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 事件节流器：两次发送之间至少间隔 `interval`
+pub struct Throttle {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, last: None }
+    }
+
+    /// 距离上次发送已超过间隔时返回 true 并记录本次时间
+    pub fn ready(&mut self) -> bool {
+        let now = Instant::now();
+        match self.last {
+            Some(last) if now.duration_since(last) < self.interval => false,
+            _ => {
+                self.last = Some(now);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_job_per_kind() {
+        let manager = JobManager::new();
+        let (job_id, _) = manager.start("type-detection").unwrap();
+        assert!(manager.start("type-detection").is_err());
+        assert!(manager.start("other").is_ok());
+
+        manager.finish(&job_id);
+        assert!(!manager.is_running("type-detection"));
+        assert!(manager.start("type-detection").is_ok());
+    }
+
+    #[test]
+    fn test_cancel_sets_token() {
+        let manager = JobManager::new();
+        let (job_id, token) = manager.start("type-detection").unwrap();
+        assert!(!token.is_cancelled());
+        assert!(manager.cancel(&job_id));
+        assert!(token.is_cancelled());
+        assert!(!manager.cancel("missing"));
+    }
+
+    #[test]
+    fn test_throttle() {
+        let mut throttle = Throttle::new(Duration::from_secs(60));
+        assert!(throttle.ready());
+        assert!(!throttle.ready());
+
+        let mut zero = Throttle::new(Duration::ZERO);
+        assert!(zero.ready());
+        assert!(zero.ready());
+    }
+}
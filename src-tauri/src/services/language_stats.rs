@@ -0,0 +1,321 @@
+//! 按语言统计项目的文件数和代码行数
+//! Git 仓库通过 `git ls-files` 获取文件列表（遵循 .gitignore），其他目录直接遍历
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+use crate::models::language::{LanguageStats, LanguageUsage};
+use super::git_service::{GitService, GIT_TIMEOUT};
+
+/// 超过该大小的文件视为生成文件，不计入统计
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// 单个项目最多统计的文件数
+const MAX_FILES: usize = 50_000;
+
+/// 判断二进制文件时读取的字节数
+const BINARY_SNIFF_LEN: usize = 8000;
+
+pub struct LanguageStatsService;
+
+impl LanguageStatsService {
+    /// 计算项目的文件指纹：Git 仓库为 HEAD + 工作区状态，其他目录为文件列表 + 修改时间
+    ///
+    /// 指纹会持久化到缓存中，使用固定的 FNV-1a 算法，保证不同版本、不同次运行的结果一致。
+    pub fn fingerprint(project_path: &str, ignore_dirs: &[String]) -> String {
+        let mut hasher = Fnv1a::new();
+
+        let head = GitService::run(project_path, &["rev-parse", "HEAD"], GIT_TIMEOUT);
+        let status = GitService::run(project_path, &["status", "--porcelain", "-uall"], GIT_TIMEOUT);
+        match (head, status) {
+            (Ok(head), Ok(status)) => {
+                hasher.write_str("git");
+                hasher.write_str(&head);
+                hasher.write_str(&status);
+                // 已修改的文件再次修改时状态不变，需要加入修改时间
+                let root = Path::new(project_path);
+                for line in status.lines() {
+                    if let Some(file) = line.get(3..) {
+                        Self::hash_file_meta(&root.join(file), &mut hasher);
+                    }
+                }
+            }
+            _ => {
+                for path in Self::walk_files(Path::new(project_path), ignore_dirs) {
+                    Self::hash_file_meta(&path, &mut hasher);
+                }
+            }
+        }
+
+        format!("{:016x}", hasher.finish())
+    }
+
+    fn hash_file_meta(path: &Path, hasher: &mut Fnv1a) {
+        hasher.write_str(&path.to_string_lossy());
+        if let Ok(meta) = fs::metadata(path) {
+            hasher.write(&meta.len().to_le_bytes());
+            let modified = meta.modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_nanos());
+            hasher.write(&modified.to_le_bytes());
+        }
+    }
+
+    /// 统计项目中各语言的文件数和行数
+    pub fn compute(project_path: &str, ignore_dirs: &[String], fingerprint: String) -> LanguageStats {
+        let root = Path::new(project_path);
+        let mut by_language: HashMap<&'static str, LanguageUsage> = HashMap::new();
+
+        for path in Self::list_files(root, ignore_dirs) {
+            let Some(language) = Self::language_of(&path) else { continue };
+            let Some(lines) = Self::count_lines(&path) else { continue };
+
+            let usage = by_language.entry(language).or_insert_with(|| LanguageUsage {
+                name: language.to_string(),
+                files: 0,
+                lines: 0,
+            });
+            usage.files += 1;
+            usage.lines += lines;
+        }
+
+        let mut languages: Vec<LanguageUsage> = by_language.into_values().collect();
+        languages.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.name.cmp(&b.name)));
+
+        LanguageStats {
+            total_files: languages.iter().map(|l| l.files).sum(),
+            total_lines: languages.iter().map(|l| l.lines).sum(),
+            primary: languages.iter().find(|l| !Self::is_markup(&l.name)).map(|l| l.name.clone()),
+            languages,
+            fingerprint,
+            computed_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// 根据扩展名或文件名识别语言
+    pub fn language_of(path: &Path) -> Option<&'static str> {
+        let file_name = path.file_name()?.to_str()?;
+        match file_name {
+            "Dockerfile" => return Some("Dockerfile"),
+            "Makefile" | "GNUmakefile" => return Some("Makefile"),
+            "CMakeLists.txt" => return Some("CMake"),
+            _ => {}
+        }
+
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        let language = match ext.as_str() {
+            "rs" => "Rust",
+            "go" => "Go",
+            "ts" | "mts" | "cts" => "TypeScript",
+            "tsx" => "TSX",
+            "js" | "mjs" | "cjs" => "JavaScript",
+            "jsx" => "JSX",
+            "vue" => "Vue",
+            "svelte" => "Svelte",
+            "astro" => "Astro",
+            "py" | "pyi" => "Python",
+            "ipynb" => "Jupyter Notebook",
+            "java" => "Java",
+            "kt" | "kts" => "Kotlin",
+            "scala" | "sc" => "Scala",
+            "groovy" | "gradle" => "Groovy",
+            "c" | "h" => "C",
+            "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => "C++",
+            "m" | "mm" => "Objective-C",
+            "swift" => "Swift",
+            "cs" => "C#",
+            "fs" | "fsx" => "F#",
+            "php" => "PHP",
+            "rb" | "rake" => "Ruby",
+            "dart" => "Dart",
+            "ex" | "exs" => "Elixir",
+            "erl" | "hrl" => "Erlang",
+            "hs" => "Haskell",
+            "lua" => "Lua",
+            "zig" => "Zig",
+            "nim" => "Nim",
+            "r" => "R",
+            "jl" => "Julia",
+            "sh" | "bash" | "zsh" | "fish" => "Shell",
+            "ps1" | "psm1" => "PowerShell",
+            "sql" => "SQL",
+            "gd" => "GDScript",
+            "html" | "htm" => "HTML",
+            "css" => "CSS",
+            "scss" | "sass" => "SCSS",
+            "less" => "Less",
+            "md" | "markdown" => "Markdown",
+            "json" => "JSON",
+            "yaml" | "yml" => "YAML",
+            "toml" => "TOML",
+            "xml" => "XML",
+            _ => return None,
+        };
+        Some(language)
+    }
+
+    /// 标记/配置语言不作为主要语言
+    fn is_markup(language: &str) -> bool {
+        matches!(language, "Markdown" | "JSON" | "YAML" | "TOML" | "XML" | "HTML" | "CSS" | "SCSS" | "Less")
+    }
+
+    /// 待统计的文件列表（Git 仓库遵循 .gitignore，均排除 ignore_dirs）
+    fn list_files(root: &Path, ignore_dirs: &[String]) -> Vec<PathBuf> {
+        let git_files = GitService::run(
+            &root.to_string_lossy(),
+            &["ls-files", "-z", "--cached", "--others", "--exclude-standard"],
+            GIT_TIMEOUT,
+        );
+
+        match git_files {
+            Ok(output) => output
+                .split('\0')
+                .filter(|f| !f.is_empty())
+                .filter(|f| !f.split('/').any(|c| ignore_dirs.iter().any(|d| d == c)))
+                .take(MAX_FILES)
+                .map(|f| root.join(f))
+                .collect(),
+            Err(_) => Self::walk_files(root, ignore_dirs),
+        }
+    }
+
+    /// 非 Git 目录：跳过 ignore_dirs 和隐藏目录
+    fn walk_files(root: &Path, ignore_dirs: &[String]) -> Vec<PathBuf> {
+        WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|e| {
+                if e.depth() == 0 || !e.file_type().is_dir() {
+                    return true;
+                }
+                let name = e.file_name().to_string_lossy();
+                !name.starts_with('.') && !ignore_dirs.iter().any(|d| *d == name)
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .take(MAX_FILES)
+            .map(|e| e.into_path())
+            .collect()
+    }
+
+    /// 统计行数，跳过过大或二进制文件
+    fn count_lines(path: &Path) -> Option<u64> {
+        let meta = fs::metadata(path).ok()?;
+        if !meta.is_file() || meta.len() > MAX_FILE_SIZE {
+            return None;
+        }
+
+        let mut content = Vec::with_capacity(meta.len() as usize);
+        fs::File::open(path).ok()?.read_to_end(&mut content).ok()?;
+        if content[..content.len().min(BINARY_SNIFF_LEN)].contains(&0) {
+            return None;
+        }
+
+        let newlines = content.iter().filter(|&&b| b == b'\n').count() as u64;
+        let trailing = !content.is_empty() && !content.ends_with(b"\n");
+        Some(newlines + trailing as u64)
+    }
+}
+
+/// 64 位 FNV-1a 哈希（结果不随 Rust 版本或进程变化）
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// 写入字符串及分隔符，避免相邻字段拼接后产生相同输入
+    fn write_str(&mut self, s: &str) {
+        self.write(s.as_bytes());
+        self.write(&[0xff]);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_utils::{git, TempDir};
+
+    fn write(root: &Path, name: &str, content: &str) {
+        let path = root.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_git_repo_respects_gitignore_and_ignore_dirs() {
+        let tmp = TempDir::new();
+        git(&tmp.0, &["init"]);
+        write(&tmp.0, ".gitignore", "generated/\n");
+        write(&tmp.0, "main.go", "package main\n\nfunc main() {}\n");
+        write(&tmp.0, "util/util.go", "package util\nfunc A() {}");
+        write(&tmp.0, "web/app.ts", "export const a = 1\n");
+        write(&tmp.0, "generated/huge.go", "package gen\n\n\n\n\n\n\n\n\n\n");
+        write(&tmp.0, "vendor/lib.go", "package lib\n\n\n\n\n\n\n\n\n\n");
+        write(&tmp.0, "README.md", "# a\n\nb\n\nc\n\nd\n");
+
+        let stats = LanguageStatsService::compute(tmp.0.to_str().unwrap(), &["vendor".to_string()], String::new());
+        let go = stats.languages.iter().find(|l| l.name == "Go").unwrap();
+        assert_eq!(go.files, 2);
+        assert_eq!(go.lines, 5);
+        // Markdown 行数更多，但不作为主要语言
+        assert_eq!(stats.languages[0].name, "Markdown");
+        assert_eq!(stats.primary.as_deref(), Some("Go"));
+        assert_eq!(stats.total_files, 4);
+    }
+
+    #[test]
+    fn test_plain_directory_skips_hidden_and_binary() {
+        let tmp = TempDir::new();
+        write(&tmp.0, "app.py", "print(1)\nprint(2)\n");
+        write(&tmp.0, ".cache/x.py", "print(1)\n");
+        write(&tmp.0, "node_modules/x.js", "a\n");
+        fs::write(tmp.join("blob.js"), [b'a', 0, b'\n']).unwrap();
+
+        let stats = LanguageStatsService::compute(tmp.0.to_str().unwrap(), &["node_modules".to_string()], String::new());
+        assert_eq!(stats.languages, vec![LanguageUsage { name: "Python".to_string(), files: 1, lines: 2 }]);
+        assert_eq!(stats.primary.as_deref(), Some("Python"));
+    }
+
+    #[test]
+    fn test_fingerprint_changes_with_content() {
+        let tmp = TempDir::new();
+        write(&tmp.0, "a.rs", "fn main() {}\n");
+        let plain = LanguageStatsService::fingerprint(tmp.0.to_str().unwrap(), &[]);
+        assert_eq!(plain, LanguageStatsService::fingerprint(tmp.0.to_str().unwrap(), &[]));
+        write(&tmp.0, "b.rs", "fn b() {}\n");
+        assert_ne!(plain, LanguageStatsService::fingerprint(tmp.0.to_str().unwrap(), &[]));
+
+        git(&tmp.0, &["init"]);
+        git(&tmp.0, &["add", "."]);
+        git(&tmp.0, &["commit", "-m", "init"]);
+        let committed = LanguageStatsService::fingerprint(tmp.0.to_str().unwrap(), &[]);
+        write(&tmp.0, "a.rs", "fn main() { changed() }\n");
+        assert_ne!(committed, LanguageStatsService::fingerprint(tmp.0.to_str().unwrap(), &[]));
+    }
+
+    #[test]
+    fn test_fnv1a_is_stable() {
+        let mut hasher = Fnv1a::new();
+        assert_eq!(hasher.finish(), 0xcbf2_9ce4_8422_2325);
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+    }
+}
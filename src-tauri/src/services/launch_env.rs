@@ -0,0 +1,95 @@
+//! 启动时附加的环境变量：项目 `.env` 文件
+
+use std::fs;
+use std::path::Path;
+
+pub struct LaunchEnv;
+
+impl LaunchEnv {
+    /// 读取目录下的 `.env`，不存在时返回 None
+    pub fn load_dotenv(dir: &Path) -> Option<(String, Vec<(String, String)>)> {
+        let path = dir.join(".env");
+        let content = fs::read_to_string(&path).ok()?;
+        Some((path.to_string_lossy().into_owned(), Self::parse_dotenv(&content)))
+    }
+
+    /// 解析 `.env` 内容
+    ///
+    /// 支持 `export` 前缀、`#` 注释、单引号（原样）和双引号（`\n` `\"` `\\` 转义）；不做变量展开。
+    pub fn parse_dotenv(content: &str) -> Vec<(String, String)> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let line = line.strip_prefix("export ").map(str::trim_start).unwrap_or(line);
+                let (key, value) = line.split_once('=')?;
+                let key = key.trim();
+                let valid = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                    && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                valid.then(|| (key.to_string(), Self::parse_value(value.trim())))
+            })
+            .collect()
+    }
+
+    fn parse_value(value: &str) -> String {
+        if let Some(rest) = value.strip_prefix('\'') {
+            return rest.split_once('\'').map(|(v, _)| v).unwrap_or(rest).to_string();
+        }
+
+        if let Some(rest) = value.strip_prefix('"') {
+            let mut out = String::new();
+            let mut chars = rest.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => match chars.next() {
+                        Some('n') => out.push('\n'),
+                        Some('t') => out.push('\t'),
+                        Some(c) => out.push(c),
+                        None => out.push('\\'),
+                    },
+                    c => out.push(c),
+                }
+            }
+            return out;
+        }
+
+        // 未加引号时 ` #` 之后为注释
+        match value.find(" #") {
+            Some(index) => value[..index].trim_end().to_string(),
+            None => value.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dotenv() {
+        let content = r#"
+# comment
+PORT=3000
+export NODE_ENV = production
+EMPTY=
+URL=http://localhost:3000/#hash # trailing comment
+SINGLE='literal $HOME \n'
+DOUBLE="line1\nsay \"hi\"" # comment
+1INVALID=x
+not a pair
+"#;
+        let vars = LaunchEnv::parse_dotenv(content);
+        assert_eq!(vars, vec![
+            ("PORT".to_string(), "3000".to_string()),
+            ("NODE_ENV".to_string(), "production".to_string()),
+            ("EMPTY".to_string(), String::new()),
+            ("URL".to_string(), "http://localhost:3000/#hash".to_string()),
+            ("SINGLE".to_string(), r"literal $HOME \n".to_string()),
+            ("DOUBLE".to_string(), "line1\nsay \"hi\"".to_string()),
+        ]);
+    }
+}
@@ -0,0 +1,229 @@
+//! 执行启动前/启动后钩子：在项目目录通过 shell 运行，超时结束整个进程组，捕获输出
+
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::models::launcher::{HookResult, HookStage, LaunchHook};
+use super::process_scanner::ProcessScanner;
+
+/// 保留的输出长度（字节，取末尾）
+const OUTPUT_LIMIT: usize = 4000;
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// 进程退出后等待输出读取完成的时间
+const READER_GRACE: Duration = Duration::from_millis(500);
+
+pub struct HookRunner;
+
+impl HookRunner {
+    /// 依次执行钩子，遇到 `abort_on_failure` 的失败钩子时停止；返回已执行的结果
+    pub fn run_all<'a>(
+        hooks: impl IntoIterator<Item = &'a LaunchHook>,
+        stage: HookStage,
+        project_path: &str,
+    ) -> Vec<HookResult> {
+        let mut results = Vec::new();
+        for hook in hooks {
+            let result = Self::run(hook, stage, project_path);
+            let abort = !result.success && hook.abort_on_failure;
+            results.push(result);
+            if abort {
+                break;
+            }
+        }
+        results
+    }
+
+    /// 执行单个钩子
+    pub fn run(hook: &LaunchHook, stage: HookStage, project_path: &str) -> HookResult {
+        let started = Instant::now();
+        let run = Self::run_shell(&hook.command, project_path, Duration::from_secs(hook.timeout_secs.max(1)));
+        HookResult {
+            project_path: project_path.to_string(),
+            command: hook.command.clone(),
+            stage,
+            success: run.success,
+            exit_code: run.exit_code,
+            timed_out: run.timed_out,
+            output: run.output,
+            duration_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+
+    /// 在 `dir` 中通过 shell 执行命令，超过 `timeout` 时结束整个进程组
+    pub(crate) fn run_shell(command: &str, dir: &str, timeout: Duration) -> ShellRun {
+        let started = Instant::now();
+        let mut result = ShellRun::default();
+
+        #[cfg(target_os = "windows")]
+        let mut cmd = {
+            let mut cmd = Command::new("cmd");
+            cmd.args(["/C", command]);
+            cmd
+        };
+        #[cfg(not(target_os = "windows"))]
+        let mut cmd = {
+            let mut cmd = Command::new("sh");
+            cmd.args(["-c", command]);
+            cmd
+        };
+
+        cmd.current_dir(dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        // 独立进程组，超时时连同子进程一起结束
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.process_group(0);
+        }
+
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                result.output = e.to_string();
+                return result;
+            }
+        };
+
+        // stdout/stderr 按到达顺序合并
+        let lines = Arc::new(Mutex::new(Vec::<String>::new()));
+        let readers: Vec<_> = [
+            child.stdout.take().map(|s| Box::new(s) as Box<dyn Read + Send>),
+            child.stderr.take().map(|s| Box::new(s) as Box<dyn Read + Send>),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|pipe| {
+            let lines = lines.clone();
+            std::thread::spawn(move || {
+                let mut reader = BufReader::new(pipe);
+                let mut buf = Vec::new();
+                while reader.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+                    let line = String::from_utf8_lossy(&buf).trim_end_matches(['\r', '\n']).to_string();
+                    buf.clear();
+                    lines.lock().unwrap().push(line);
+                }
+            })
+        })
+        .collect();
+
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    result.exit_code = status.code();
+                    result.success = status.success();
+                    break;
+                }
+                Ok(None) if started.elapsed() >= timeout => {
+                    result.timed_out = true;
+                    let _ = ProcessScanner::terminate_group(child.id(), true);
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    break;
+                }
+                Ok(None) => std::thread::sleep(POLL_INTERVAL),
+                Err(e) => {
+                    // 进程尚未回收，进程组仍然有效
+                    lines.lock().unwrap().push(e.to_string());
+                    let _ = ProcessScanner::terminate_group(child.id(), true);
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    break;
+                }
+            }
+        }
+
+        // 进程已回收，不再向其进程组发送信号（进程号可能已被复用）；
+        // 钩子启动的后台进程（如守护进程）可能还持有管道，只等待片刻
+        let deadline = Instant::now() + READER_GRACE;
+        for reader in readers {
+            while !reader.is_finished() && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            if reader.is_finished() {
+                let _ = reader.join();
+            }
+        }
+
+        let output = lines.lock().unwrap().join("\n");
+        result.output = tail(output.trim(), OUTPUT_LIMIT);
+        result
+    }
+}
+
+/// 一次 shell 命令的执行结果
+#[derive(Debug, Default)]
+pub(crate) struct ShellRun {
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    /// 合并的 stdout/stderr（只保留末尾部分）
+    pub output: String,
+}
+
+/// 截取末尾最多 `limit` 字节（按字符边界）
+pub(crate) fn tail(text: &str, limit: usize) -> String {
+    if text.len() <= limit {
+        return text.to_string();
+    }
+    let mut start = text.len() - limit;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("…{}", &text[start..])
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::services::test_utils::TempDir;
+
+    fn hook(command: &str, timeout_secs: u64, abort_on_failure: bool) -> LaunchHook {
+        LaunchHook { command: command.to_string(), timeout_secs, abort_on_failure }
+    }
+
+    #[test]
+    fn test_runs_in_project_dir_and_captures_output() {
+        let tmp = TempDir::new();
+        let dir = tmp.0.to_str().unwrap();
+
+        let ok = HookRunner::run(&hook("pwd", 5, true), HookStage::PreLaunch, dir);
+        assert!(ok.success);
+        assert_eq!(ok.output, dir);
+
+        let failed = HookRunner::run(&hook("echo out; echo err >&2; exit 4", 5, true), HookStage::PreLaunch, dir);
+        assert!(!failed.success);
+        assert_eq!(failed.exit_code, Some(4));
+        assert!(failed.output.contains("out") && failed.output.contains("err"));
+        assert!(failed.failure_message().contains("退出码 4"));
+    }
+
+    #[test]
+    fn test_timeout_kills_process_group() {
+        let tmp = TempDir::new();
+        let marker = tmp.join("survived");
+        let command = format!("echo started; (sleep 2; touch {}) & sleep 30", marker.display());
+
+        let result = HookRunner::run(&hook(&command, 1, true), HookStage::PreLaunch, tmp.0.to_str().unwrap());
+        assert!(result.timed_out);
+        assert!(!result.success);
+        assert!(result.duration_ms < 5000);
+        assert_eq!(result.output, "started");
+        std::thread::sleep(Duration::from_millis(2200));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn test_run_all_stops_on_aborting_failure() {
+        let tmp = TempDir::new();
+        let dir = tmp.0.to_str().unwrap();
+        let hooks = [hook("exit 1", 5, false), hook("true", 5, true), hook("false", 5, true), hook("true", 5, true)];
+
+        let results = HookRunner::run_all(&hooks, HookStage::PostLaunch, dir);
+        assert_eq!(results.iter().map(|r| r.success).collect::<Vec<_>>(), vec![false, true, false]);
+        assert_eq!(tail("abcdef", 3), "…def");
+    }
+}
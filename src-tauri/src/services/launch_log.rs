@@ -0,0 +1,331 @@
+//! 启动日志：每次启动追加一行 JSON，记录实际使用的参数、工作目录、环境设置和结果
+//!
+//! 启动后在观察期内失败的进程会以相同 `id` 再追加一条记录，读取时合并到原记录上。
+//! 首次访问时把文件读入内存，之后的查询不再读文件。超过保留期和超出数量上限的记录
+//! 在首次访问时、此后每天一次以及行数超出上限较多时压缩掉。
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::models::launcher::{LaunchLogEntry, LauncherLaunchCount};
+
+/// 最多保留的记录数
+const MAX_ENTRIES: usize = 5000;
+/// 记录保留天数
+const MAX_AGE_DAYS: i64 = 365;
+/// 超出上限多少行后才压缩，避免每次追加都重写文件
+const COMPACT_SLACK: usize = 500;
+/// 应用长时间运行时按保留期压缩的间隔
+const COMPACT_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub struct LaunchLog {
+    path: PathBuf,
+    max_entries: usize,
+    max_age: chrono::Duration,
+    /// 串行化读写，同时缓存已合并的记录（首次访问时读取文件）
+    loaded: Mutex<Option<Loaded>>,
+}
+
+/// 内存中的日志
+struct Loaded {
+    /// 已合并的记录，按写入先后
+    entries: Vec<LaunchLogEntry>,
+    /// 记录 id -> `entries` 中的位置
+    ids: HashMap<String, usize>,
+    /// 文件中的行数（合并前）
+    lines: usize,
+    compacted_at: Instant,
+}
+
+impl Loaded {
+    fn new(entries: Vec<LaunchLogEntry>, lines: usize) -> Self {
+        let mut loaded = Self { entries: Vec::new(), ids: HashMap::new(), lines, compacted_at: Instant::now() };
+        for entry in entries {
+            loaded.merge(entry);
+        }
+        loaded
+    }
+
+    /// 加入一条记录，同一次启动后追加的失败记录合并到原记录上
+    fn merge(&mut self, entry: LaunchLogEntry) {
+        // 旧版本的记录没有 id，不参与合并
+        if !entry.id.is_empty() {
+            // 失败记录可能先于原记录写入，合并结果与顺序无关
+            if let Some(&i) = self.ids.get(&entry.id) {
+                let merged = &mut self.entries[i];
+                merged.success &= entry.success;
+                merged.error = merged.error.take().or(entry.error);
+                merged.settings = merged.settings.take().or(entry.settings);
+                merged.duration_ms = merged.duration_ms.max(entry.duration_ms);
+                return;
+            }
+            self.ids.insert(entry.id.clone(), self.entries.len());
+        }
+        self.entries.push(entry);
+    }
+}
+
+impl LaunchLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            max_entries: MAX_ENTRIES,
+            max_age: chrono::Duration::days(MAX_AGE_DAYS),
+            loaded: Mutex::new(None),
+        }
+    }
+
+    /// 追加一条记录（失败只打印，不影响启动）
+    pub fn append(&self, entry: &LaunchLogEntry) {
+        self.with_loaded(|loaded| {
+            let result = serde_json::to_string(entry)
+                .map_err(|e| e.to_string())
+                .and_then(|line| {
+                    if let Some(parent) = self.path.parent() {
+                        let _ = fs::create_dir_all(parent);
+                    }
+                    let mut file = OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(&self.path)
+                        .map_err(|e| e.to_string())?;
+                    writeln!(file, "{}", line).map_err(|e| e.to_string())
+                });
+            if let Err(e) = result {
+                eprintln!("写入启动日志失败: {}", e);
+                return;
+            }
+
+            loaded.lines += 1;
+            loaded.merge(entry.clone());
+            if loaded.lines > self.max_entries + COMPACT_SLACK || loaded.compacted_at.elapsed() >= COMPACT_INTERVAL {
+                self.compact(loaded);
+            }
+        })
+    }
+
+    /// 所有记录（按时间先后，已合并观察期内的失败）
+    pub fn entries(&self) -> Vec<LaunchLogEntry> {
+        self.with_entries(|entries| entries.to_vec())
+    }
+
+    /// 以所有记录（按时间先后，已合并）调用 `f`，避免复制
+    pub fn with_entries<R>(&self, f: impl FnOnce(&[LaunchLogEntry]) -> R) -> R {
+        self.with_loaded(|loaded| f(&loaded.entries))
+    }
+
+    /// 最近的启动记录（新的在前）
+    pub fn recent(&self, limit: usize) -> Vec<LaunchLogEntry> {
+        self.with_entries(|entries| entries.iter().rev().take(limit).cloned().collect())
+    }
+
+    /// 某个项目的启动记录（新的在前）
+    pub fn project_history(&self, project_path: &str, limit: usize) -> Vec<LaunchLogEntry> {
+        self.with_entries(|entries| {
+            entries.iter()
+                .rev()
+                .filter(|e| e.project_path == project_path)
+                .take(limit)
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// 按启动器统计启动次数，次数多的在前（未能确定启动器的失败启动不计入）
+    pub fn launcher_counts(&self) -> Vec<LauncherLaunchCount> {
+        let mut counts: Vec<LauncherLaunchCount> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        self.with_entries(|entries| {
+            for entry in entries.iter().filter(|e| !e.launcher_id.is_empty()) {
+                let i = *index.entry(entry.launcher_id.clone()).or_insert_with(|| {
+                    counts.push(LauncherLaunchCount {
+                        launcher_id: entry.launcher_id.clone(),
+                        launcher_name: entry.launcher_name.clone(),
+                        count: 0,
+                        failures: 0,
+                        last_used: None,
+                    });
+                    counts.len() - 1
+                });
+                let count = &mut counts[i];
+                // 启动器可能改过名，以最近一次为准
+                if !entry.launcher_name.is_empty() {
+                    count.launcher_name = entry.launcher_name.clone();
+                }
+                count.count += 1;
+                if !entry.success {
+                    count.failures += 1;
+                }
+                count.last_used = Some(entry.time.clone());
+            }
+        });
+        counts.sort_by_key(|c| std::cmp::Reverse(c.count));
+        counts
+    }
+
+    /// 删除某个项目的记录（重置项目打开次数时调用）
+    pub fn remove_project(&self, project_path: &str) -> Result<(), String> {
+        self.with_loaded(|loaded| {
+            let kept = loaded.entries.iter()
+                .filter(|e| e.project_path != project_path)
+                .cloned()
+                .collect();
+            self.replace(loaded, kept)
+        })
+    }
+
+    /// 清空所有记录
+    pub fn clear(&self) -> Result<(), String> {
+        self.with_loaded(|loaded| self.replace(loaded, Vec::new()))
+    }
+
+    /// 加锁后调用 `f`，首次访问时读取文件并压缩
+    fn with_loaded<R>(&self, f: impl FnOnce(&mut Loaded) -> R) -> R {
+        let mut guard = self.loaded.lock().unwrap();
+        let loaded = match guard.as_mut() {
+            Some(loaded) => loaded,
+            None => {
+                let entries = self.read_lines();
+                let lines = entries.len();
+                let mut loaded = Loaded::new(entries, lines);
+                self.compact(&mut loaded);
+                guard.insert(loaded)
+            }
+        };
+        f(loaded)
+    }
+
+    /// 去掉超过保留期和超出数量上限的记录；有记录被去掉或存在待合并的行时重写文件
+    fn compact(&self, loaded: &mut Loaded) {
+        loaded.compacted_at = Instant::now();
+        let cutoff = chrono::Utc::now() - self.max_age;
+        let mut kept: Vec<LaunchLogEntry> = loaded.entries.iter()
+            .filter(|entry| {
+                chrono::DateTime::parse_from_rfc3339(&entry.time).is_ok_and(|t| t >= cutoff)
+            })
+            .cloned()
+            .collect();
+        let excess = kept.len().saturating_sub(self.max_entries);
+        kept.drain(..excess);
+        if kept.len() == loaded.entries.len() && loaded.lines <= self.max_entries + COMPACT_SLACK {
+            return;
+        }
+        if let Err(e) = self.replace(loaded, kept) {
+            eprintln!("压缩启动日志失败: {}", e);
+        }
+    }
+
+    /// 用 `entries` 重写文件并替换内存中的记录；先写临时文件再替换，中途失败不会丢失原日志
+    fn replace(&self, loaded: &mut Loaded, entries: Vec<LaunchLogEntry>) -> Result<(), String> {
+        if self.path.exists() {
+            let mut content = String::new();
+            for entry in &entries {
+                content.push_str(&serde_json::to_string(entry).map_err(|e| e.to_string())?);
+                content.push('\n');
+            }
+            let tmp = self.path.with_extension("jsonl.tmp");
+            fs::write(&tmp, content).map_err(|e| e.to_string())?;
+            fs::rename(&tmp, &self.path).map_err(|e| e.to_string())?;
+        }
+        let lines = entries.len();
+        *loaded = Loaded { compacted_at: loaded.compacted_at, ..Loaded::new(entries, lines) };
+        Ok(())
+    }
+
+    /// 读取所有能解析的记录
+    fn read_lines(&self) -> Vec<LaunchLogEntry> {
+        let Ok(content) = fs::read_to_string(&self.path) else {
+            return Vec::new();
+        };
+        content.lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::services::test_utils::{launch_entry, TempDir};
+
+    #[test]
+    fn test_query_merge_and_reset() {
+        let tmp = TempDir::new();
+        let log = LaunchLog::new(tmp.join("launch_log.jsonl"));
+        let now = chrono::Utc::now().to_rfc3339();
+
+        log.append(&launch_entry("1", "/p/a", "code", &now));
+        log.append(&launch_entry("2", "/p/b", "code", &now));
+        log.append(&launch_entry("3", "/p/a", "vim", &now));
+        // 启动后失败的记录合并到 id 为 2 的记录上
+        let mut failed = launch_entry("2", "/p/b", "code", &now);
+        failed.success = false;
+        failed.error = Some("exit 1".to_string());
+        log.append(&failed);
+
+        let recent = log.recent(10);
+        assert_eq!(recent.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(), ["3", "2", "1"]);
+        assert!(!recent[1].success);
+        assert_eq!(recent[1].error.as_deref(), Some("exit 1"));
+        assert_eq!(log.recent(1).len(), 1);
+
+        let history = log.project_history("/p/a", 10);
+        assert_eq!(history.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(), ["3", "1"]);
+
+        let counts = log.launcher_counts();
+        assert_eq!(counts[0].launcher_id, "code");
+        assert_eq!((counts[0].count, counts[0].failures), (2, 1));
+        assert_eq!((counts[1].launcher_name.as_str(), counts[1].count), ("VIM", 1));
+
+        log.remove_project("/p/a").unwrap();
+        assert_eq!(log.recent(10).iter().map(|e| e.id.as_str()).collect::<Vec<_>>(), ["2"]);
+        log.clear().unwrap();
+        assert!(log.recent(10).is_empty());
+    }
+
+    #[test]
+    fn test_retention() {
+        let tmp = TempDir::new();
+        let mut log = LaunchLog::new(tmp.join("launch_log.jsonl"));
+        log.max_entries = 3;
+        let now = chrono::Utc::now().to_rfc3339();
+        let old = (chrono::Utc::now() - chrono::Duration::days(MAX_AGE_DAYS + 1)).to_rfc3339();
+
+        log.append(&launch_entry("old", "/p/a", "code", &old));
+        for i in 0..COMPACT_SLACK + 3 {
+            log.append(&launch_entry(&i.to_string(), "/p/a", "code", &now));
+        }
+        // 超出上限时压缩：丢掉过期记录，只保留最新的 3 条
+        let ids: Vec<_> = log.entries().into_iter().map(|e| e.id).collect();
+        let last = COMPACT_SLACK + 2;
+        assert_eq!(ids, [last - 2, last - 1, last].map(|i| i.to_string()));
+    }
+
+    #[test]
+    fn test_age_compaction_on_load_and_daily() {
+        let tmp = TempDir::new();
+        let path = tmp.join("launch_log.jsonl");
+        let now = chrono::Utc::now().to_rfc3339();
+        let old = (chrono::Utc::now() - chrono::Duration::days(MAX_AGE_DAYS + 1)).to_rfc3339();
+
+        let log = LaunchLog::new(path.clone());
+        log.append(&launch_entry("old", "/p/a", "code", &old));
+        log.append(&launch_entry("new", "/p/a", "code", &now));
+        assert_eq!(log.entries().len(), 2);
+
+        // 重新打开时压缩过期记录
+        let log = LaunchLog::new(path.clone());
+        assert_eq!(log.entries().into_iter().map(|e| e.id).collect::<Vec<_>>(), ["new"]);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+
+        // 距上次压缩超过一天时，追加记录后压缩
+        log.append(&launch_entry("old2", "/p/a", "code", &old));
+        log.with_loaded(|loaded| loaded.compacted_at = Instant::now() - COMPACT_INTERVAL);
+        log.append(&launch_entry("new2", "/p/a", "code", &now));
+        assert_eq!(log.entries().into_iter().map(|e| e.id).collect::<Vec<_>>(), ["new", "new2"]);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+    }
+}
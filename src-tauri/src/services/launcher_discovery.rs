@@ -0,0 +1,424 @@
+//! 自动发现已安装的编辑器和终端（Linux）
+//!
+//! 解析 XDG 数据目录（含 Flatpak、Snap 导出目录）中的 `.desktop` 文件，并在 PATH 和
+//! JetBrains Toolbox 脚本目录中查找已知程序，生成带正确参数的启动器建议。
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use crate::models::launcher::{Launcher, LauncherKind, LauncherSuggestion};
+use super::command_template::CommandTemplate;
+use super::launcher_health::LauncherHealthCheck;
+
+/// 已知的编辑器和终端
+struct KnownApp {
+    name: &'static str,
+    kind: LauncherKind,
+    /// 程序名（不含 `.sh`）或 Flatpak 应用 ID
+    programs: &'static [&'static str],
+    /// 追加在程序后的参数模板
+    args: &'static str,
+    /// 终端界面的程序（如 Neovim），需要在终端中运行
+    in_terminal: bool,
+    /// 从 PATH 发现时使用的图标名
+    icon: &'static str,
+}
+
+const fn editor(name: &'static str, programs: &'static [&'static str], icon: &'static str) -> KnownApp {
+    KnownApp { name, kind: LauncherKind::Editor, programs, args: "{project}", in_terminal: false, icon }
+}
+
+const fn terminal(name: &'static str, programs: &'static [&'static str], args: &'static str, icon: &'static str) -> KnownApp {
+    KnownApp { name, kind: LauncherKind::Terminal, programs, args, in_terminal: false, icon }
+}
+
+const KNOWN_APPS: &[KnownApp] = &[
+    editor("VS Code", &["code", "com.visualstudio.code"], "vscode"),
+    editor("VSCodium", &["codium", "com.vscodium.codium"], "vscodium"),
+    editor("Cursor", &["cursor"], "cursor"),
+    editor("Zed", &["zed", "zeditor", "zedit", "dev.zed.zed"], "zed"),
+    editor("Sublime Text", &["subl", "sublime_text", "com.sublimetext.three"], "sublime-text"),
+    editor("IntelliJ IDEA", &["idea", "intellij-idea-ultimate", "intellij-idea-community",
+        "com.jetbrains.intellij-idea-ultimate", "com.jetbrains.intellij-idea-community"], "intellij-idea"),
+    editor("PyCharm", &["pycharm", "pycharm-professional", "pycharm-community",
+        "com.jetbrains.pycharm-professional", "com.jetbrains.pycharm-community"], "pycharm"),
+    editor("WebStorm", &["webstorm", "com.jetbrains.webstorm"], "webstorm"),
+    editor("GoLand", &["goland", "com.jetbrains.goland"], "goland"),
+    editor("CLion", &["clion", "com.jetbrains.clion"], "clion"),
+    editor("Rider", &["rider", "com.jetbrains.rider"], "rider"),
+    editor("RustRover", &["rustrover", "com.jetbrains.rustrover"], "rustrover"),
+    editor("PhpStorm", &["phpstorm", "com.jetbrains.phpstorm"], "phpstorm"),
+    KnownApp {
+        name: "Neovim",
+        kind: LauncherKind::Editor,
+        programs: &["nvim", "io.neovim.nvim"],
+        args: "{project}",
+        in_terminal: true,
+        icon: "nvim",
+    },
+    terminal("Kitty", &["kitty"], "--directory {project}", "kitty"),
+    terminal("Alacritty", &["alacritty", "org.alacritty.alacritty"], "--working-directory {project}", "Alacritty"),
+    terminal("WezTerm", &["wezterm", "wezterm-gui", "org.wezfurlong.wezterm"], "start --cwd {project}", "org.wezfurlong.wezterm"),
+    terminal("GNOME Terminal", &["gnome-terminal"], "--working-directory={project}", "org.gnome.Terminal"),
+    terminal("Konsole", &["konsole", "org.kde.konsole"], "--workdir {project}", "utilities-terminal"),
+];
+
+/// 图标尺寸，按优先级
+const ICON_SIZES: &[&str] = &["scalable", "512x512", "256x256", "128x128", "64x64", "48x48"];
+
+/// 查找位置
+pub struct DiscoveryEnv {
+    /// XDG 数据目录（按优先级，其下的 `applications`、`icons`）
+    pub data_dirs: Vec<PathBuf>,
+    /// 查找可执行文件的目录
+    pub path_dirs: Vec<PathBuf>,
+}
+
+impl DiscoveryEnv {
+    /// 当前系统的查找位置：XDG_DATA_HOME、Flatpak 用户导出目录、XDG_DATA_DIRS、Flatpak 系统导出目录、
+    /// Snap 导出目录；PATH 之后追加 JetBrains Toolbox 的脚本目录
+    pub fn from_system() -> Self {
+        let home = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(".local/share"));
+        let system_dirs = std::env::var_os("XDG_DATA_DIRS")
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+
+        let mut data_dirs = vec![data_home.clone(), data_home.join("flatpak/exports/share")];
+        data_dirs.extend(std::env::split_paths(&system_dirs));
+        data_dirs.push(PathBuf::from("/var/lib/flatpak/exports/share"));
+        data_dirs.push(PathBuf::from("/var/lib/snapd/desktop"));
+        let mut seen = HashSet::new();
+        data_dirs.retain(|dir| seen.insert(dir.clone()));
+
+        let mut path_dirs: Vec<PathBuf> = std::env::var_os("PATH")
+            .map(|path| std::env::split_paths(&path).collect())
+            .unwrap_or_default();
+        path_dirs.push(data_home.join("JetBrains/Toolbox/scripts"));
+
+        Self { data_dirs, path_dirs }
+    }
+}
+
+/// `.desktop` 文件中用到的字段
+#[derive(Debug, Default)]
+struct DesktopEntry {
+    name: String,
+    exec: String,
+    icon: Option<String>,
+    terminal: bool,
+    /// 不是应用、被隐藏或不显示
+    hidden: bool,
+}
+
+/// Exec 中启动程序的部分（去掉原有参数）
+#[derive(Debug, Clone, PartialEq)]
+struct ProgramPrefix {
+    tokens: Vec<String>,
+    /// 匹配用的程序名（小写，去掉 `.sh`）或 Flatpak 应用 ID
+    key: String,
+}
+
+impl ProgramPrefix {
+    /// 拆出 Exec 的程序部分：跳过 `env VAR=...`，Flatpak 保留到应用 ID（去掉文件转发参数）
+    fn parse(tokens: &[String]) -> Option<Self> {
+        let mut index = 0;
+        if tokens.first().is_some_and(|t| program_name(t) == "env") {
+            index = 1;
+            while tokens.get(index).is_some_and(|t| t.contains('=') && !t.starts_with('-')) {
+                index += 1;
+            }
+        }
+        let program = tokens.get(index)?;
+        let name = program_name(program);
+
+        if name == "flatpak" && tokens.get(index + 1).map(String::as_str) == Some("run") {
+            let app = (index + 2..tokens.len()).find(|&i| !tokens[i].starts_with('-'))?;
+            let key = tokens[app].to_lowercase();
+            let tokens = tokens[..=app].iter().filter(|t| *t != "--file-forwarding").cloned().collect();
+            return Some(Self { tokens, key });
+        }
+        Some(Self { tokens: tokens[..=index].to_vec(), key: name })
+    }
+
+    /// 启动器对应的程序部分
+    fn of_launcher(launcher: &Launcher) -> Option<Self> {
+        if launcher.is_command {
+            let tokens = CommandTemplate::tokenize(launcher.command.as_deref()?).ok()?;
+            Self::parse(&tokens)
+        } else if !launcher.path.is_empty() {
+            Some(Self { tokens: vec![launcher.path.clone()], key: program_name(&launcher.path) })
+        } else {
+            None
+        }
+    }
+
+    /// 是否为同一个程序：程序部分相同，或程序名相同且任一方不是绝对路径
+    fn same_program(&self, other: &Self) -> bool {
+        if self.tokens == other.tokens {
+            return true;
+        }
+        let absolute = |p: &Self| p.tokens.len() == 1 && Path::new(&p.tokens[0]).is_absolute();
+        self.key == other.key && !(absolute(self) && absolute(other))
+    }
+
+    fn known_app(&self) -> Option<&'static KnownApp> {
+        KNOWN_APPS.iter().find(|app| app.programs.contains(&self.key.as_str()))
+    }
+}
+
+/// 程序名：文件名小写，去掉 `.sh` 后缀
+fn program_name(program: &str) -> String {
+    let name = Path::new(program)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(program)
+        .to_lowercase();
+    name.strip_suffix(".sh").map(str::to_string).unwrap_or(name)
+}
+
+pub struct LauncherDiscovery;
+
+impl LauncherDiscovery {
+    /// 发现已安装的编辑器和终端，跳过与 `existing` 中启动器相同的程序
+    ///
+    /// `.desktop` 文件优先（带名称和图标），每个已知应用在 PATH 中只补充一次。
+    pub fn discover(env: &DiscoveryEnv, existing: &[Launcher]) -> Vec<LauncherSuggestion> {
+        let existing: Vec<ProgramPrefix> = existing.iter().filter_map(ProgramPrefix::of_launcher).collect();
+        let mut found: Vec<(ProgramPrefix, LauncherSuggestion)> = Vec::new();
+        let mut found_apps: HashSet<&'static str> = HashSet::new();
+
+        for (path, entry) in Self::desktop_entries(&env.data_dirs) {
+            let Ok(tokens) = CommandTemplate::tokenize(&entry.exec) else { continue };
+            let Some(prefix) = ProgramPrefix::parse(&tokens) else { continue };
+            let Some(app) = prefix.known_app() else { continue };
+            // 过期的 .desktop 文件（程序已卸载）
+            let program = Path::new(&prefix.tokens[0]);
+            if program.is_absolute() && !program.exists() {
+                continue;
+            }
+            if found.iter().any(|(p, _)| p.tokens == prefix.tokens) {
+                continue;
+            }
+
+            let icon = entry.icon.as_deref().and_then(|icon| Self::resolve_icon(icon, &env.data_dirs));
+            let name = if entry.name.is_empty() { app.name.to_string() } else { entry.name };
+            let suggestion = Self::suggestion(app, name, &prefix, icon, entry.terminal, path.to_string_lossy().into_owned());
+            found_apps.insert(app.name);
+            found.push((prefix, suggestion));
+        }
+
+        for app in KNOWN_APPS.iter().filter(|app| !found_apps.contains(app.name)) {
+            let Some(path) = app.programs.iter().find_map(|program| Self::find_program(program, &env.path_dirs)) else {
+                continue;
+            };
+            let path = path.to_string_lossy().into_owned();
+            let prefix = ProgramPrefix { key: program_name(&path), tokens: vec![path.clone()] };
+            let icon = Self::resolve_icon(app.icon, &env.data_dirs);
+            let suggestion = Self::suggestion(app, app.name.to_string(), &prefix, icon, app.in_terminal, path);
+            found.push((prefix, suggestion));
+        }
+
+        found
+            .into_iter()
+            .filter(|(prefix, _)| !existing.iter().any(|e| e.same_program(prefix)))
+            .map(|(_, suggestion)| suggestion)
+            .collect()
+    }
+
+    fn suggestion(
+        app: &KnownApp,
+        name: String,
+        prefix: &ProgramPrefix,
+        icon: Option<String>,
+        in_terminal: bool,
+        source: String,
+    ) -> LauncherSuggestion {
+        let program: Vec<String> = prefix.tokens.iter().map(|t| CommandTemplate::quote_posix(t)).collect();
+        let mut launcher = Launcher::new(String::new(), name, String::new());
+        launcher.is_command = true;
+        launcher.command = Some(format!("{} {}", program.join(" "), app.args));
+        launcher.in_terminal = in_terminal;
+        launcher.icon_path = icon;
+        LauncherSuggestion { launcher, kind: app.kind, source }
+    }
+
+    /// 各数据目录 `applications` 下的 `.desktop` 文件；同一 ID 以先出现的目录为准
+    fn desktop_entries(data_dirs: &[PathBuf]) -> Vec<(PathBuf, DesktopEntry)> {
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        for dir in data_dirs {
+            let apps_dir = dir.join("applications");
+            let mut files: Vec<PathBuf> = WalkDir::new(&apps_dir)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .map(|e| e.into_path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "desktop"))
+                .collect();
+            files.sort();
+
+            for path in files {
+                // 子目录中的文件 ID 用 `-` 连接
+                let id = path.strip_prefix(&apps_dir)
+                    .map(|p| p.to_string_lossy().replace('/', "-"))
+                    .unwrap_or_default();
+                if !seen.insert(id) {
+                    continue;
+                }
+                let Ok(content) = fs::read_to_string(&path) else { continue };
+                let entry = Self::parse_desktop_entry(&content);
+                if !entry.hidden && !entry.exec.is_empty() {
+                    entries.push((path, entry));
+                }
+            }
+        }
+        entries
+    }
+
+    /// 解析 `[Desktop Entry]` 组
+    fn parse_desktop_entry(content: &str) -> DesktopEntry {
+        let mut entry = DesktopEntry::default();
+        let mut in_group = false;
+        let mut is_application = false;
+        for line in content.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_group = line == "[Desktop Entry]";
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=').filter(|_| in_group) else { continue };
+            let value = value.trim();
+            match key.trim() {
+                "Name" => entry.name = value.to_string(),
+                // 规范要求 `%` 写作 `%%`
+                "Exec" => entry.exec = value.replace("%%", "%"),
+                "Icon" if !value.is_empty() => entry.icon = Some(value.to_string()),
+                "Terminal" => entry.terminal = value == "true",
+                "Type" => is_application = value == "Application",
+                "NoDisplay" | "Hidden" if value == "true" => entry.hidden = true,
+                _ => {}
+            }
+        }
+        entry.hidden |= !is_application;
+        entry
+    }
+
+    /// 图标名解析为文件路径：hicolor 主题中较大的尺寸优先，其次 pixmaps
+    fn resolve_icon(icon: &str, data_dirs: &[PathBuf]) -> Option<String> {
+        let path = Path::new(icon);
+        if path.is_absolute() {
+            return path.is_file().then(|| icon.to_string());
+        }
+        let candidates = data_dirs.iter().flat_map(|dir| {
+            ICON_SIZES
+                .iter()
+                .map(move |size| {
+                    let ext = if *size == "scalable" { "svg" } else { "png" };
+                    dir.join(format!("icons/hicolor/{}/apps/{}.{}", size, icon, ext))
+                })
+                .chain(["png", "svg", "xpm"].map(|ext| dir.join(format!("pixmaps/{}.{}", icon, ext))))
+        });
+        candidates
+            .into_iter()
+            .find(|p| p.is_file())
+            .map(|p| p.to_string_lossy().into_owned())
+    }
+
+    fn find_program(name: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+        // Flatpak 应用 ID 不是可执行文件名
+        if name.contains('.') {
+            return None;
+        }
+        dirs.iter()
+            .flat_map(|dir| [dir.join(name), dir.join(format!("{}.sh", name))])
+            .find(|path| LauncherHealthCheck::is_executable(path))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::services::test_utils::{executable_script, TempDir};
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn desktop(name: &str, exec: &str, extra: &str) -> String {
+        format!("[Desktop Entry]\nType=Application\nName={}\nExec={}\n{}\n[Desktop Action new]\nExec=ignored\n", name, exec, extra)
+    }
+
+    fn commands(suggestions: &[LauncherSuggestion]) -> Vec<(&str, &str)> {
+        suggestions
+            .iter()
+            .map(|s| (s.launcher.name.as_str(), s.launcher.command.as_deref().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_exec_prefix() {
+        let parse = |exec: &str| ProgramPrefix::parse(&CommandTemplate::tokenize(exec).unwrap()).unwrap();
+
+        let flatpak = parse("/usr/bin/flatpak run --branch=stable --command=code --file-forwarding com.visualstudio.code --reuse-window @@ %F @@");
+        assert_eq!(flatpak.tokens, ["/usr/bin/flatpak", "run", "--branch=stable", "--command=code", "com.visualstudio.code"]);
+        assert_eq!(flatpak.key, "com.visualstudio.code");
+
+        let snap = parse("env BAMF_DESKTOP_FILE_HINT=/var/lib/snapd/desktop/applications/code_code.desktop /snap/bin/code --force-user-env %F");
+        assert_eq!(snap.tokens.len(), 3);
+        assert_eq!(snap.key, "code");
+
+        let toolbox = parse(r#""/home/me/JetBrains Toolbox/apps/idea/bin/idea.sh" %u"#);
+        assert_eq!(toolbox.key, "idea");
+        assert_eq!(toolbox.known_app().unwrap().name, "IntelliJ IDEA");
+    }
+
+    #[test]
+    fn test_discover_desktop_entries_path_and_dedup() {
+        let tmp = TempDir::new();
+        let user = tmp.join("user");
+        let system = tmp.join("system");
+        let bin = tmp.join("bin");
+
+        write(&system.join("applications/code.desktop"), &desktop("Visual Studio Code", "code --unity-launch %F", "Icon=vscode"));
+        write(&system.join("icons/hicolor/256x256/apps/vscode.png"), "");
+        write(&system.join("icons/hicolor/48x48/apps/vscode.png"), "");
+        write(&system.join("applications/code-url-handler.desktop"), &desktop("Code URL", "code --open-url %U", "NoDisplay=true"));
+        write(&system.join("applications/org.wezfurlong.wezterm.desktop"), &desktop("WezTerm", "wezterm start --cwd .", ""));
+        write(&system.join("applications/nvim.desktop"), &desktop("Neovim", "nvim %F", "Terminal=true"));
+        write(&system.join("applications/gimp.desktop"), &desktop("GIMP", "gimp %U", ""));
+        // 用户目录中的同名文件覆盖并隐藏系统的 Konsole
+        write(&user.join("applications/org.kde.konsole.desktop"), &desktop("Konsole", "konsole", "Hidden=true"));
+        write(&system.join("applications/org.kde.konsole.desktop"), &desktop("Konsole", "konsole", ""));
+        write(&user.join("applications/kde/zed.desktop"), &desktop("Zed", "/opt/missing/zed %U", ""));
+
+        executable_script(&bin.join("kitty"), "");
+        executable_script(&bin.join("idea.sh"), "");
+        executable_script(&bin.join("cursor"), "");
+
+        let env = DiscoveryEnv { data_dirs: vec![user, system.clone()], path_dirs: vec![bin.clone()] };
+        let mut existing = Launcher::new("1".to_string(), "My Cursor".to_string(), String::new());
+        existing.is_command = true;
+        existing.command = Some("cursor {project}".to_string());
+
+        let suggestions = LauncherDiscovery::discover(&env, &[existing]);
+        let kitty = format!("{} --directory {{project}}", bin.join("kitty").display());
+        let idea = format!("{} {{project}}", bin.join("idea.sh").display());
+        assert_eq!(commands(&suggestions), vec![
+            ("Visual Studio Code", "code {project}"),
+            ("Neovim", "nvim {project}"),
+            ("WezTerm", "wezterm start --cwd {project}"),
+            ("IntelliJ IDEA", idea.as_str()),
+            ("Kitty", kitty.as_str()),
+        ]);
+
+        let code = &suggestions[0];
+        assert_eq!(code.kind, LauncherKind::Editor);
+        assert_eq!(code.launcher.icon_path, Some(system.join("icons/hicolor/256x256/apps/vscode.png").to_string_lossy().into_owned()));
+        assert!(suggestions[1].launcher.in_terminal);
+        assert_eq!(suggestions[4].kind, LauncherKind::Terminal);
+    }
+}
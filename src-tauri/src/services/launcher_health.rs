@@ -0,0 +1,241 @@
+//! 启动器健康检查：程序是否存在且可执行、命令中的程序能否在 PATH 中找到、图标文件是否存在
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use crate::models::launcher::{HealthStatus, LauncherHealth, Launcher};
+use super::command_template::CommandTemplate;
+use super::launcher_service::LauncherService;
+
+/// 通过 shell 执行时不对应 PATH 中程序的第一个词（内建命令和关键字）
+#[cfg(not(windows))]
+const SHELL_BUILTINS: &[&str] = &[
+    "cd", "export", "exec", "source", ".", "eval", "set", "unset", "alias", "echo", "printf", "test", "[",
+    "true", "false", ":", "command", "builtin", "ulimit", "umask", "trap", "wait",
+    "if", "for", "while", "until", "case", "function", "time", "!", "{", "(",
+];
+#[cfg(windows)]
+const SHELL_BUILTINS: &[&str] = &[
+    "cd", "chdir", "pushd", "popd", "set", "setlocal", "call", "start", "echo", "if", "for", "title",
+    "type", "dir", "copy", "del", "move", "ren", "mkdir", "md", "rmdir", "rd", "cls", "ver", "@echo",
+];
+
+pub struct LauncherHealthCheck;
+
+impl LauncherHealthCheck {
+    /// 按当前进程的 PATH 检查所有启动器
+    pub fn check_all(launchers: &[Launcher], terminal: Option<&str>) -> Vec<LauncherHealth> {
+        let path_var = std::env::var_os("PATH").unwrap_or_default();
+        launchers.iter().map(|l| Self::check(l, terminal, &path_var)).collect()
+    }
+
+    /// 检查单个启动器；`terminal` 为配置的终端命令（`in_terminal` 的启动器用到）
+    pub fn check(launcher: &Launcher, terminal: Option<&str>, path_var: &OsStr) -> LauncherHealth {
+        let mut broken = Vec::new();
+        let mut warnings = Vec::new();
+
+        if launcher.is_command {
+            match launcher.command.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+                None => broken.push("启动命令为空".to_string()),
+                Some(command) => match CommandTemplate::tokenize(command) {
+                    Err(e) => broken.push(format!("命令模板无法解析: {}", e)),
+                    // 通过 shell 执行时第一个词是内建命令或 shell 语法时无法检查
+                    Ok(tokens) if launcher.run_in_shell && tokens.first().is_some_and(|t| Self::is_shell_syntax(t)) => {}
+                    Ok(tokens) => {
+                        if let Some(program) = Self::program_of(&tokens) {
+                            broken.extend(Self::check_program(program, path_var).err());
+                        }
+                    }
+                },
+            }
+        } else {
+            broken.extend(Self::check_app(&launcher.path, path_var).err());
+        }
+
+        if launcher.in_terminal {
+            match LauncherService::terminal_template(terminal, path_var).map(|t| CommandTemplate::tokenize(&t)) {
+                Err(e) => broken.push(e),
+                Ok(Err(e)) => broken.push(format!("终端命令无法解析: {}", e)),
+                Ok(Ok(tokens)) => {
+                    if let Some(program) = Self::program_of(&tokens) {
+                        broken.extend(Self::check_program(program, path_var).err().map(|e| format!("终端: {}", e)));
+                    }
+                }
+            }
+        }
+
+        if let Some(icon) = launcher.icon_path.as_deref().filter(|i| !i.is_empty()) {
+            if !Path::new(icon).is_file() {
+                warnings.push(format!("图标文件不存在: {}", icon));
+            }
+        }
+
+        let status = if !broken.is_empty() {
+            HealthStatus::Broken
+        } else if !warnings.is_empty() {
+            HealthStatus::Warning
+        } else {
+            HealthStatus::Healthy
+        };
+        broken.extend(warnings);
+
+        LauncherHealth {
+            launcher_id: launcher.id.clone(),
+            status,
+            reasons: broken,
+            checked_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// shell 中的内建命令、关键字，或以重定向、子 shell、变量赋值等 shell 语法开头
+    fn is_shell_syntax(word: &str) -> bool {
+        SHELL_BUILTINS.iter().any(|b| word.eq_ignore_ascii_case(b))
+            || word.starts_with(['(', '{', '<', '>', '|', '&', ';', '$', '`', '!', '%'])
+            || word.split_once('=').is_some_and(|(name, _)| {
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            })
+    }
+
+    /// 命令中要执行的程序：跳过 `env VAR=...`；由占位符决定时无法检查，返回 None
+    fn program_of(tokens: &[String]) -> Option<&str> {
+        let mut tokens = tokens.iter().map(String::as_str);
+        let mut program = tokens.next()?;
+        if Path::new(program).file_name().is_some_and(|n| n == "env") {
+            program = tokens.find(|t| !t.contains('=') || t.starts_with('-'))?;
+        }
+        (!program.contains('{')).then_some(program)
+    }
+
+    /// 应用模式的路径（macOS 上可以是 `open -a` 能识别的应用名）
+    fn check_app(path: &str, path_var: &OsStr) -> Result<(), String> {
+        if path.trim().is_empty() {
+            return Err("未设置应用路径".to_string());
+        }
+        if cfg!(target_os = "macos") {
+            if Path::new(path).is_absolute() && !Path::new(path).exists() {
+                return Err(format!("应用不存在: {}", path));
+            }
+            return Ok(());
+        }
+        Self::check_program(path, path_var)
+    }
+
+    /// 检查程序：带路径的必须存在且可执行，只有名称的必须能在 PATH 中找到
+    fn check_program(program: &str, path_var: &OsStr) -> Result<(), String> {
+        let path = Path::new(program);
+        if path.components().count() == 1 && !path.is_absolute() {
+            return match Self::find_in_path(program, path_var) {
+                Some(_) => Ok(()),
+                None => Err(format!("在 PATH 中找不到程序 `{}`", program)),
+            };
+        }
+        // 相对路径按项目目录解析，无法提前检查
+        if !path.is_absolute() {
+            return Ok(());
+        }
+        if !path.exists() {
+            return Err(format!("程序不存在: {}", program));
+        }
+        if !Self::is_executable(path) {
+            return Err(format!("程序不可执行: {}", program));
+        }
+        Ok(())
+    }
+
+    /// 在 PATH 中查找可执行文件（Windows 上按 PATHEXT 补全扩展名）
+    pub fn find_in_path(name: &str, path_var: &OsStr) -> Option<PathBuf> {
+        let extensions: Vec<String> = if cfg!(windows) && Path::new(name).extension().is_none() {
+            std::env::var("PATHEXT")
+                .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+                .split(';')
+                .filter(|ext| !ext.is_empty())
+                .map(str::to_string)
+                .collect()
+        } else {
+            vec![String::new()]
+        };
+        std::env::split_paths(path_var)
+            .flat_map(|dir| extensions.iter().map(move |ext| dir.join(format!("{}{}", name, ext))))
+            .find(|path| Self::is_executable(path))
+    }
+
+    /// 是否为可执行文件
+    #[cfg(unix)]
+    pub fn is_executable(path: &Path) -> bool {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    }
+
+    #[cfg(not(unix))]
+    pub fn is_executable(path: &Path) -> bool {
+        path.is_file()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use crate::services::test_utils::{executable_script, TempDir};
+
+    fn command_launcher(command: &str) -> Launcher {
+        let mut launcher = Launcher::new("l1".to_string(), "test".to_string(), String::new());
+        launcher.is_command = true;
+        launcher.command = Some(command.to_string());
+        launcher
+    }
+
+    #[test]
+    fn test_check_launchers() {
+        let tmp = TempDir::new();
+        let bin = tmp.join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        for name in ["editor", "kitty"] {
+            executable_script(&bin.join(name), "");
+        }
+        fs::write(bin.join("plain"), "").unwrap();
+        let path_var = bin.clone().into_os_string();
+        let check = |launcher: &Launcher, terminal: Option<&str>| {
+            let health = LauncherHealthCheck::check(launcher, terminal, &path_var);
+            (health.status, health.reasons)
+        };
+
+        assert_eq!(check(&command_launcher("editor --new-window {project}"), None), (HealthStatus::Healthy, vec![]));
+        assert_eq!(check(&command_launcher("env A=1 editor {project}"), None).0, HealthStatus::Healthy);
+        // shell 命令的第一个词是内建命令或 shell 语法时不检查
+        let mut shell = command_launcher("cd {project} && missing .");
+        shell.run_in_shell = true;
+        assert_eq!(check(&shell, None).0, HealthStatus::Healthy);
+        shell.command = Some("FOO=1 missing {project}".to_string());
+        assert_eq!(check(&shell, None).0, HealthStatus::Healthy);
+        // 其余情况照常检查（旧版本保存的命令启动器都通过 shell 执行）
+        shell.command = Some("missing {project}".to_string());
+        assert_eq!(
+            check(&shell, None),
+            (HealthStatus::Broken, vec!["在 PATH 中找不到程序 `missing`".to_string()]),
+        );
+        shell.command = Some("editor {project} > /dev/null".to_string());
+        assert_eq!(check(&shell, None).0, HealthStatus::Healthy);
+
+        assert_eq!(
+            check(&command_launcher("missing {project}"), None),
+            (HealthStatus::Broken, vec!["在 PATH 中找不到程序 `missing`".to_string()]),
+        );
+        let plain = bin.join("plain").to_string_lossy().into_owned();
+        assert_eq!(check(&command_launcher(&format!("'{}' {{project}}", plain)), None).1, [format!("程序不可执行: {}", plain)]);
+
+        let mut app = Launcher::new("l2".to_string(), "app".to_string(), bin.join("gone").to_string_lossy().into_owned());
+        app.icon_path = Some(tmp.join("icon.png").to_string_lossy().into_owned());
+        let (status, reasons) = check(&app, None);
+        assert_eq!(status, HealthStatus::Broken);
+        assert_eq!(reasons.len(), 2);
+        assert!(reasons[0].starts_with("程序不存在") && reasons[1].starts_with("图标文件不存在"));
+
+        app.path = bin.join("editor").to_string_lossy().into_owned();
+        assert_eq!(check(&app, None).0, HealthStatus::Warning);
+
+        let mut terminal = command_launcher("editor {project}");
+        terminal.in_terminal = true;
+        assert_eq!(check(&terminal, Some("kitty --directory {cwd}")).0, HealthStatus::Healthy);
+        assert_eq!(check(&terminal, Some("wezterm start --")).1, ["终端: 在 PATH 中找不到程序 `wezterm`"]);
+    }
+}
@@ -1,130 +1,625 @@
-use std::process::Command;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
 use std::process::Stdio;
-use std::io::Read;
-use crate::models::launcher::Launcher;
+use std::io::{Read, Seek, SeekFrom};
+use std::time::{Duration, Instant};
+use crate::models::launcher::{HookResult, HookStage, LaunchHook, LaunchHooks, LaunchReport, LaunchSettings, Launcher, ToolEnv};
+use super::command_template::{CommandTemplate, ShellKind};
+use super::launch_env::LaunchEnv;
+use super::launch_hooks::{self, HookRunner};
+use super::launcher_health::LauncherHealthCheck;
 use anyhow::Result;
 
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// 保留的 stderr 长度（字节，取末尾）
+const STDERR_LIMIT: usize = 4000;
+
+/// 未配置终端命令时按顺序查找的终端及其命令模板（`$TERMINAL` 优先）
+#[cfg(target_os = "linux")]
+const LINUX_TERMINALS: &[(&str, &str)] = &[
+    ("gnome-terminal", "gnome-terminal --"),
+    ("konsole", "konsole -e"),
+    ("kitty", "kitty"),
+    ("alacritty", "alacritty -e"),
+    ("xterm", "xterm -e"),
+    ("x-terminal-emulator", "x-terminal-emulator -e"),
+];
+
+/// 启动方式（决定各平台的启动细节）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LaunchKind {
+    /// 应用模式：用应用打开项目目录
+    App,
+    /// 命令模式：执行命令模板
+    Command,
+    /// 在终端中运行（应用或命令）
+    Terminal,
+}
+
+/// 启动后在后台观察到的事件
+#[derive(Debug)]
+pub enum LaunchEvent {
+    /// 进程在观察期内以失败状态退出
+    Failed { exit_code: Option<i32>, stderr: String },
+    /// 启动后钩子执行完毕
+    PostHooks(Vec<HookResult>),
+}
+
+/// 准备好的一次启动：参数、工作目录和环境变量都已确定
+pub struct LaunchPlan {
+    pub settings: LaunchSettings,
+    project_path: String,
+    env: Vec<(String, String)>,
+    /// 需要移除的环境变量（工具链环境中 unset 的变量）
+    env_remove: Vec<String>,
+    /// 启动器上配置的钩子
+    hooks: LaunchHooks,
+    kind: LaunchKind,
+}
+
+/// 启动的进程的 stderr 写入的文件（每次启动一个）
+///
+/// 不使用管道：启动器退出后进程写 stderr 不会因管道关闭而收到 SIGPIPE。
+/// 观察期结束后删除（进程仍持有的文件描述符不受影响）。
+struct StderrLog {
+    path: Option<PathBuf>,
+}
+
+impl StderrLog {
+    /// 在 `dir` 中创建文件；失败时进程继承启动器的 stderr
+    fn create(dir: &Path) -> (Self, Stdio) {
+        let path = dir.join(format!("{}.log", uuid::Uuid::new_v4()));
+        match fs::create_dir_all(dir).and_then(|_| File::create(&path)) {
+            Ok(file) => (Self { path: Some(path) }, Stdio::from(file)),
+            Err(e) => {
+                eprintln!("[Launcher] 无法创建 stderr 文件 {}: {}", path.display(), e);
+                (Self { path: None }, Stdio::inherit())
+            }
+        }
+    }
+
+    /// 文件末尾部分
+    fn tail(&self) -> String {
+        let Some(mut file) = self.path.as_ref().and_then(|p| File::open(p).ok()) else {
+            return String::new();
+        };
+        let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let _ = file.seek(SeekFrom::Start(len.saturating_sub(STDERR_LIMIT as u64 * 2)));
+        let mut buf = Vec::new();
+        let _ = file.read_to_end(&mut buf);
+        launch_hooks::tail(String::from_utf8_lossy(&buf).trim(), STDERR_LIMIT)
+    }
+}
+
+impl Drop for StderrLog {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
 pub struct LauncherService;
 
 impl LauncherService {
-    /// 使用启动器打开项目
-    pub fn launch(launcher: &Launcher, project_path: &str) -> Result<()> {
-        if launcher.is_command {
-            // 命令模式：执行自定义命令
-            if let Some(cmd) = &launcher.command {
-                Self::execute_command(cmd, project_path)?;
+    /// 根据启动器设置计算实际执行的参数、工作目录和环境变量
+    ///
+    /// `terminal` 为配置的终端命令，启动器设置了 `in_terminal` 时用它包装要运行的参数。
+    /// 环境变量的优先级：启动器的 `env` > `.env` > 工具链环境（`tool_env`）。
+    pub fn prepare(
+        launcher: &Launcher,
+        project_path: &str,
+        vars: &HashMap<String, String>,
+        terminal: Option<&str>,
+        tool_env: Option<&ToolEnv>,
+    ) -> Result<LaunchPlan> {
+        let cwd = Self::working_dir(launcher, project_path, vars)?;
+        let mut vars = vars.clone();
+        vars.insert("cwd".to_string(), cwd.clone());
+
+        let mut argv = if launcher.is_command {
+            let template = launcher.command.as_deref()
+                .filter(|c| !c.trim().is_empty())
+                .ok_or_else(|| anyhow::anyhow!("启动命令为空"))?;
+            Self::command_argv(template, launcher.run_in_shell, &vars)?
+        } else {
+            if launcher.path.is_empty() {
+                anyhow::bail!("未设置应用路径");
+            }
+            Self::app_argv(&launcher.path, project_path)
+        };
+
+        let kind = if launcher.in_terminal {
+            let mut wrapped = Self::terminal_argv(terminal, &vars)?;
+            wrapped.append(&mut argv);
+            argv = wrapped;
+            LaunchKind::Terminal
+        } else if launcher.is_command {
+            LaunchKind::Command
+        } else {
+            LaunchKind::App
+        };
+
+        // .env 在前，启动器的 env 覆盖同名变量
+        let mut extra_env = Vec::new();
+        let mut dotenv = None;
+        if launcher.load_dotenv {
+            if let Some((path, vars)) = LaunchEnv::load_dotenv(Path::new(project_path)) {
+                dotenv = Some(path);
+                extra_env.extend(vars);
+            }
+        }
+        extra_env.extend(launcher.env.iter().map(|(key, value)| (key.clone(), CommandTemplate::expand(value, &vars))));
+
+        let mut env_names: Vec<String> = extra_env.iter().map(|(key, _)| key.clone()).collect();
+        env_names.sort();
+        env_names.dedup();
+
+        let mut env = Vec::new();
+        let mut env_remove = Vec::new();
+        for (key, value) in tool_env.iter().flat_map(|t| &t.vars) {
+            match value {
+                Some(value) => env.push((key.clone(), value.clone())),
+                None => env_remove.push(key.clone()),
+            }
+        }
+        env.extend(extra_env);
+
+        Ok(LaunchPlan {
+            settings: LaunchSettings {
+                argv,
+                cwd,
+                env: env_names,
+                dotenv,
+                toolchain: tool_env.map(|t| t.sources.clone()).unwrap_or_default(),
+                toolchain_errors: tool_env.map(|t| t.errors.clone()).unwrap_or_default(),
+                run_in_shell: launcher.is_command && launcher.run_in_shell,
+                in_terminal: launcher.in_terminal,
+            },
+            project_path: project_path.to_string(),
+            env,
+            env_remove,
+            hooks: launcher.hooks.clone(),
+            kind,
+        })
+    }
+
+    /// 按计划打开项目
+    ///
+    /// 先执行启动前钩子（启动器的在前、项目的在后），设置了 `abort_on_failure` 的钩子失败时中止启动。
+    /// 进程启动后立即返回，由后台线程观察 `grace` 时长：期间以失败状态退出时发送 [`LaunchEvent::Failed`]。
+    /// 有启动后钩子时等待进程退出后执行（项目的在前），结果通过 [`LaunchEvent::PostHooks`] 发送。
+    /// 进程的 stderr 写入 `stderr_dir` 中的临时文件，失败时读取其末尾部分。
+    pub fn launch<F>(
+        plan: &LaunchPlan,
+        project_hooks: &LaunchHooks,
+        grace: Duration,
+        stderr_dir: &Path,
+        on_event: F,
+    ) -> Result<LaunchReport>
+    where
+        F: FnMut(LaunchEvent) + Send + 'static,
+    {
+        let pre_hooks: Vec<_> = plan.hooks.pre_launch.iter().chain(&project_hooks.pre_launch).collect();
+        let hooks = HookRunner::run_all(pre_hooks.iter().copied(), HookStage::PreLaunch, &plan.project_path);
+        if let Some(failed) = hooks.last().filter(|r| !r.success && pre_hooks[hooks.len() - 1].abort_on_failure) {
+            anyhow::bail!("{}", failed.failure_message());
+        }
+
+        let post_hooks: Vec<_> = project_hooks.post_launch.iter()
+            .chain(&plan.hooks.post_launch)
+            .cloned()
+            .collect();
+
+        println!("[Launcher] 执行命令: {:?} (工作目录: {})", plan.settings.argv, plan.settings.cwd);
+        let (stderr, stderr_stdio) = StderrLog::create(stderr_dir);
+        let child = Self::spawn(plan, !post_hooks.is_empty(), stderr_stdio)
+            .map_err(|e| Self::describe_spawn_error(plan, e))?;
+
+        let project_path = plan.project_path.clone();
+        std::thread::spawn(move || Self::supervise(child, stderr, grace, &post_hooks, &project_path, on_event));
+
+        Ok(LaunchReport { hooks, settings: plan.settings.clone() })
+    }
+
+    /// 启动进程，stderr 写入 `stderr`
+    fn spawn(plan: &LaunchPlan, wait: bool, stderr: Stdio) -> std::io::Result<Child> {
+        let mut cmd = Self::process(plan, wait);
+        cmd.stdin(Stdio::null()).stderr(stderr);
+
+        #[cfg(target_os = "windows")]
+        if plan.kind == LaunchKind::Command {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            cmd.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        cmd.spawn()
+    }
+
+    /// 观察启动的进程：`grace` 内失败退出时报告，之后回收进程并执行启动后钩子
+    fn supervise<F>(
+        mut child: Child,
+        stderr: StderrLog,
+        grace: Duration,
+        post_hooks: &[LaunchHook],
+        project_path: &str,
+        mut on_event: F,
+    ) where
+        F: FnMut(LaunchEvent),
+    {
+        let started = Instant::now();
+        let exited = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break Some(status),
+                Ok(None) if started.elapsed() >= grace => break None,
+                Ok(None) => std::thread::sleep(POLL_INTERVAL),
+                Err(_) => break None,
             }
+        };
+
+        if let Some(status) = exited.filter(|s| !s.success()) {
+            let stderr = stderr.tail();
+            eprintln!("[Launcher] 启动失败 ({}): 退出码 {:?} {}", project_path, status.code(), stderr);
+            on_event(LaunchEvent::Failed { exit_code: status.code(), stderr });
+        }
+        drop(stderr);
+
+        // 等待退出（回收进程）
+        if exited.is_none() {
+            let _ = child.wait();
+        }
+        if !post_hooks.is_empty() {
+            on_event(LaunchEvent::PostHooks(HookRunner::run_all(post_hooks, HookStage::PostLaunch, project_path)));
+        }
+    }
+
+    /// 程序不存在时系统只返回 "No such file or directory"，补充是哪个程序
+    fn describe_spawn_error(plan: &LaunchPlan, e: std::io::Error) -> anyhow::Error {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            anyhow::anyhow!("找不到程序 `{}`，启动器对应的应用可能已卸载或移动", plan.settings.argv[0])
         } else {
-            // 应用模式：直接调用应用打开项目
-            Self::open_with_app(&launcher.path, project_path)?;
+            e.into()
         }
-        Ok(())
+    }
+
+    /// 工作目录：启动器设置的目录（支持占位符，相对路径按项目目录解析），默认项目目录
+    fn working_dir(launcher: &Launcher, project_path: &str, vars: &HashMap<String, String>) -> Result<String> {
+        let Some(dir) = launcher.working_dir.as_deref().map(str::trim).filter(|d| !d.is_empty()) else {
+            return Ok(project_path.to_string());
+        };
+        let path = Path::new(project_path).join(CommandTemplate::expand(dir, vars));
+        if !path.is_dir() {
+            anyhow::bail!("工作目录不存在: {}", path.display());
+        }
+        Ok(path.to_string_lossy().into_owned())
+    }
+
+    /// 实际使用的终端命令模板：配置的终端，未配置时为平台默认终端（在 `path_var` 中查找）
+    pub fn terminal_template(terminal: Option<&str>, path_var: &OsStr) -> std::result::Result<String, String> {
+        match terminal.filter(|t| !t.trim().is_empty()) {
+            Some(terminal) => Ok(terminal.to_string()),
+            None => Self::default_terminal(std::env::var("TERMINAL").ok().as_deref(), path_var),
+        }
+    }
+
+    /// Linux：`$TERMINAL` 指定的终端，否则为已安装的常见终端中的第一个
+    #[cfg(target_os = "linux")]
+    fn default_terminal(env_terminal: Option<&str>, path_var: &OsStr) -> std::result::Result<String, String> {
+        let installed = |program: &str| {
+            if program.contains('/') {
+                LauncherHealthCheck::is_executable(Path::new(program))
+            } else {
+                LauncherHealthCheck::find_in_path(program, path_var).is_some()
+            }
+        };
+
+        // 含空白的路径无法直接写入模板，跳过
+        if let Some(program) = env_terminal.map(str::trim).filter(|t| !t.is_empty() && !t.contains(char::is_whitespace)) {
+            if installed(program) {
+                let name = Path::new(program).file_name().and_then(|n| n.to_str()).unwrap_or(program);
+                return Ok(match LINUX_TERMINALS.iter().find(|(known, _)| *known == name) {
+                    Some((_, template)) => template.replacen(name, program, 1),
+                    None => format!("{} -e", program),
+                });
+            }
+        }
+        LINUX_TERMINALS.iter()
+            .find(|(program, _)| installed(program))
+            .map(|(_, template)| template.to_string())
+            .ok_or_else(|| {
+                let tried: Vec<&str> = LINUX_TERMINALS.iter().map(|(program, _)| *program).collect();
+                format!("未找到可用的终端（已尝试 $TERMINAL、{}），请在设置中配置终端命令", tried.join("、"))
+            })
     }
 
     #[cfg(target_os = "windows")]
-    fn open_with_app(app_path: &str, project_path: &str) -> Result<()> {
-        use std::path::Path;
+    fn default_terminal(_env_terminal: Option<&str>, _path_var: &OsStr) -> std::result::Result<String, String> {
+        Ok("wt -d {cwd}".to_string())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    fn default_terminal(_env_terminal: Option<&str>, _path_var: &OsStr) -> std::result::Result<String, String> {
+        Err("未配置终端命令，请在设置中填写".to_string())
+    }
+
+    /// 终端命令展开后的参数（要运行的参数追加在其后）
+    fn terminal_argv(terminal: Option<&str>, vars: &HashMap<String, String>) -> Result<Vec<String>> {
+        let path_var = std::env::var_os("PATH").unwrap_or_default();
+        let template = Self::terminal_template(terminal, &path_var).map_err(anyhow::Error::msg)?;
+        CommandTemplate::render_argv(&template, vars)
+    }
+
+    /// 命令模板展开后的参数列表；通过 shell 执行时为 `sh -c <命令行>` / `cmd /C <命令行>`
+    fn command_argv(template: &str, run_in_shell: bool, vars: &HashMap<String, String>) -> Result<Vec<String>> {
+        if !run_in_shell {
+            let argv = CommandTemplate::render_argv(template, vars)?;
+
+            // cmd.exe 不按常规规则解析参数，含占位符的参数展开后不能有 cmd 的特殊字符
+            #[cfg(target_os = "windows")]
+            if Self::is_cmd_exe(&argv[0]) {
+                let tokens = CommandTemplate::tokenize(template)?;
+                if tokens.iter().zip(&argv).any(|(t, a)| t != a && !CommandTemplate::is_cmd_safe_arg(a)) {
+                    anyhow::bail!("占位符的值包含 cmd 的特殊字符，无法作为 cmd 参数传递");
+                }
+            }
+
+            return Ok(argv);
+        }
+        let shell = ShellKind::native();
+        let line = CommandTemplate::render_shell(template, vars, shell)?;
+        Ok(match shell {
+            ShellKind::Posix => vec!["sh".to_string(), "-c".to_string(), line],
+            ShellKind::Cmd => vec!["cmd".to_string(), "/C".to_string(), line],
+        })
+    }
 
+    /// 应用模式的参数列表
+    #[cfg(target_os = "windows")]
+    fn app_argv(app_path: &str, project_path: &str) -> Vec<String> {
         let app_name = Path::new(app_path)
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("")
             .to_lowercase();
 
-        println!("[Launcher] 执行命令: {} {}", app_path, project_path);
-
         // Windows Terminal 需要 -d 参数指定工作目录
         if app_name == "wt.exe" || app_name == "windowsterminal.exe" {
-            Command::new(app_path)
-                .args(["-d", project_path])
-                .spawn()?;
+            vec![app_path.to_string(), "-d".to_string(), project_path.to_string()]
         } else {
-            Command::new(app_path)
-                .arg(project_path)
-                .spawn()?;
+            vec![app_path.to_string(), project_path.to_string()]
         }
-        Ok(())
     }
 
     #[cfg(target_os = "macos")]
-    fn open_with_app(app_path: &str, project_path: &str) -> Result<()> {
-        println!("[Launcher] 执行命令: open -a \"{}\" \"{}\"", app_path, project_path);
-        let output = Command::new("open")
-            .arg("-a")
-            .arg(app_path)
-            .arg(project_path)
-            .output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let msg = stderr.trim();
-            anyhow::bail!("{}", if msg.is_empty() { "应用启动失败" } else { msg });
-        }
-        Ok(())
+    fn app_argv(app_path: &str, project_path: &str) -> Vec<String> {
+        vec!["open".to_string(), "-a".to_string(), app_path.to_string(), project_path.to_string()]
     }
 
     #[cfg(target_os = "linux")]
-    fn open_with_app(app_path: &str, project_path: &str) -> Result<()> {
-        println!("[Launcher] 执行命令: {} {}", app_path, project_path);
-        Command::new(app_path)
-            .arg(project_path)
-            .spawn()?;
-        Ok(())
+    fn app_argv(app_path: &str, project_path: &str) -> Vec<String> {
+        vec![app_path.to_string(), project_path.to_string()]
     }
 
-    fn execute_command(cmd: &str, project_path: &str) -> Result<()> {
-        let cmd_replaced = cmd.replace("{project}", project_path);
-        println!("[Launcher] 执行命令: {}", cmd_replaced);
+    /// 按计划构建进程：参数、工作目录、环境变量
+    #[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
+    fn process(plan: &LaunchPlan, wait: bool) -> Command {
+        let argv = &plan.settings.argv;
 
+        // 通过 cmd 执行时命令行已按 cmd 规则加好引号，原样传给 cmd
         #[cfg(target_os = "windows")]
-        {
+        let mut cmd = if plan.kind == LaunchKind::Command && plan.settings.run_in_shell {
             use std::os::windows::process::CommandExt;
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            let mut cmd = Command::new(&argv[0]);
+            cmd.arg(&argv[1]).raw_arg(&argv[2]);
+            cmd
+        } else {
+            let mut cmd = Command::new(&argv[0]);
+            cmd.args(&argv[1..]);
+            cmd
+        };
 
-            // 解析命令，提取可执行文件和参数
-            let parts: Vec<&str> = cmd_replaced.split_whitespace().collect();
-            if let Some((exe, _)) = parts.split_first() {
-                // 重新组装参数，替换 {project} 后的参数
-                let args_str: String = cmd_replaced
-                    .trim_start_matches(exe)
-                    .trim()
-                    .to_string();
-
-                Command::new(exe)
-                    .raw_arg(&args_str)
-                    .creation_flags(CREATE_NO_WINDOW)
-                    .spawn()?;
+        #[cfg(not(target_os = "windows"))]
+        let mut cmd = {
+            let mut cmd = Command::new(&argv[0]);
+            // `open` 默认不等待应用退出，有启动后钩子时加 -W
+            #[cfg(target_os = "macos")]
+            if plan.kind == LaunchKind::App {
+                if wait {
+                    cmd.arg("-W");
+                }
+                cmd.args(Self::open_env_args(plan));
             }
+            cmd.args(&argv[1..]);
+            cmd
+        };
+
+        cmd.current_dir(&plan.settings.cwd);
+        for key in &plan.env_remove {
+            cmd.env_remove(key);
         }
+        cmd.envs(plan.env.iter().map(|(key, value)| (key, value)));
+        cmd
+    }
 
-        #[cfg(not(target_os = "windows"))]
-        {
-            let mut child = Command::new("sh")
-                .args(["-c", &cmd_replaced])
-                .stderr(Stdio::piped())
-                .spawn()?;
-
-            // 短暂等待以捕获立即失败（如 command not found）
-            std::thread::sleep(std::time::Duration::from_millis(300));
-            if let Some(status) = child.try_wait()? {
-                if !status.success() {
-                    let stderr = child.stderr.take()
-                        .map(|mut s| {
-                            let mut buf = String::new();
-                            s.read_to_string(&mut buf).ok();
-                            buf
-                        })
-                        .unwrap_or_default();
-                    let msg = stderr.trim().to_string();
-                    anyhow::bail!("{}", if msg.is_empty() {
-                        format!("命令执行失败，退出码: {}", status.code().unwrap_or(-1))
-                    } else {
-                        msg
-                    });
-                }
+    /// `open` 通过 LaunchServices 启动应用，环境变量需要用 `--env` 传给应用
+    /// （应用已在运行时不会生效）
+    #[cfg(target_os = "macos")]
+    fn open_env_args(plan: &LaunchPlan) -> Vec<String> {
+        plan.env.iter()
+            .flat_map(|(key, value)| ["--env".to_string(), format!("{}={}", key, value)])
+            .collect()
+    }
+
+    #[cfg(target_os = "windows")]
+    fn is_cmd_exe(program: &str) -> bool {
+        let name = Path::new(program)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        name == "cmd" || name == "cmd.exe"
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::sync::mpsc;
+    use crate::services::test_utils::TempDir;
+
+    fn command_launcher(command: &str) -> Launcher {
+        let mut launcher = Launcher::new("l1".to_string(), "test".to_string(), String::new());
+        launcher.is_command = true;
+        launcher.command = Some(command.to_string());
+        launcher
+    }
+
+    fn vars(project: &str) -> HashMap<String, String> {
+        HashMap::from([
+            ("project".to_string(), project.to_string()),
+            ("name".to_string(), "app".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_prepare_working_dir_env_and_terminal() {
+        let tmp = TempDir::new();
+        let project = tmp.0.to_str().unwrap();
+        fs::create_dir_all(tmp.join("web")).unwrap();
+        fs::write(tmp.join(".env"), "PORT=3000\nNAME=from-dotenv\n").unwrap();
+
+        let mut launcher = command_launcher("code {project}");
+        let plan = LauncherService::prepare(&launcher, project, &vars(project), None, None).unwrap();
+        assert_eq!(plan.settings.argv, ["code", project]);
+        assert_eq!(plan.settings.cwd, project);
+        assert!(plan.settings.env.is_empty() && plan.settings.dotenv.is_none());
+
+        launcher.working_dir = Some("web".to_string());
+        launcher.load_dotenv = true;
+        launcher.env.insert("NAME".to_string(), "{name}-dev".to_string());
+        launcher.in_terminal = true;
+        let tool_env = ToolEnv {
+            sources: vec!["direnv".to_string()],
+            vars: BTreeMap::from([
+                ("NAME".to_string(), Some("from-direnv".to_string())),
+                ("GOPATH".to_string(), Some("/go".to_string())),
+                ("OLD".to_string(), None),
+            ]),
+            ..Default::default()
+        };
+        let plan = LauncherService::prepare(&launcher, project, &vars(project), Some("kitty --directory {cwd} --"), Some(&tool_env)).unwrap();
+        let web = tmp.join("web").to_string_lossy().into_owned();
+        assert_eq!(plan.settings.argv, ["kitty", "--directory", web.as_str(), "--", "code", project]);
+        assert_eq!(plan.settings.cwd, web);
+        assert_eq!(plan.settings.env, ["NAME", "PORT"]);
+        assert_eq!(plan.settings.dotenv, Some(tmp.join(".env").to_string_lossy().into_owned()));
+        assert_eq!(plan.settings.toolchain, ["direnv"]);
+        // 启动器的 env 覆盖 .env 和工具链环境
+        assert_eq!(plan.env.first().unwrap(), &("GOPATH".to_string(), "/go".to_string()));
+        assert_eq!(plan.env.last().unwrap(), &("NAME".to_string(), "app-dev".to_string()));
+        assert_eq!(plan.env_remove, ["OLD"]);
+
+        launcher.working_dir = Some("missing".to_string());
+        assert!(LauncherService::prepare(&launcher, project, &vars(project), None, None).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_default_terminal_detection() {
+        use crate::services::test_utils::executable_script;
+
+        let tmp = TempDir::new();
+        let bin = tmp.join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        let path_var = bin.clone().into_os_string();
+        let install = |name: &str| executable_script(&bin.join(name), "");
+
+        let err = LauncherService::default_terminal(None, &path_var).unwrap_err();
+        assert!(err.contains("未找到可用的终端") && err.contains("xterm"));
+
+        install("xterm");
+        install("kitty");
+        assert_eq!(LauncherService::default_terminal(None, &path_var).unwrap(), "kitty");
+        // $TERMINAL 优先；已知终端使用其参数，未安装时忽略
+        assert_eq!(LauncherService::default_terminal(Some("xterm"), &path_var).unwrap(), "xterm -e");
+        assert_eq!(LauncherService::default_terminal(Some("wezterm"), &path_var).unwrap(), "kitty");
+        install("foot");
+        assert_eq!(LauncherService::default_terminal(Some("foot"), &path_var).unwrap(), "foot -e");
+        let kitty = bin.join("kitty").to_string_lossy().into_owned();
+        assert_eq!(LauncherService::default_terminal(Some(&kitty), &path_var).unwrap(), kitty);
+
+        // 配置的终端不受影响
+        assert_eq!(LauncherService::terminal_template(Some("wezterm start --"), &path_var).unwrap(), "wezterm start --");
+    }
+
+    #[test]
+    fn test_launch_uses_working_dir_and_env() {
+        let tmp = TempDir::new();
+        let project = tmp.0.to_str().unwrap();
+        fs::create_dir_all(tmp.join("sub")).unwrap();
+        fs::write(tmp.join(".env"), "GREETING='hello world'\n").unwrap();
+
+        let mut launcher = command_launcher(r#"sh -c "pwd > ../out.txt; printf %s \"$GREETING\" >> ../out.txt""#);
+        launcher.working_dir = Some("sub".to_string());
+        launcher.load_dotenv = true;
+
+        let plan = LauncherService::prepare(&launcher, project, &vars(project), None, None).unwrap();
+        let report = LauncherService::launch(&plan, &LaunchHooks::default(), Duration::from_secs(1), &tmp.join("stderr"), |_| {}).unwrap();
+        assert_eq!(report.settings, plan.settings);
+
+        let out = tmp.join("out.txt");
+        for _ in 0..50 {
+            if fs::read_to_string(&out).is_ok_and(|s| s.ends_with("world")) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        let expected = format!("{}\nhello world", tmp.join("sub").display());
+        assert_eq!(fs::read_to_string(&out).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_late_failure_reported_within_grace() {
+        let tmp = TempDir::new();
+        let project = tmp.0.to_str().unwrap();
+        let stderr_dir = tmp.join("stderr");
+        let post_hooks = LaunchHooks {
+            pre_launch: Vec::new(),
+            post_launch: vec![LaunchHook { command: "true".to_string(), timeout_secs: 5, abort_on_failure: false }],
+        };
+        let launch = |command: &str, grace: Duration| {
+            let plan = LauncherService::prepare(&command_launcher(command), project, &vars(project), None, None).unwrap();
+            let (tx, rx) = mpsc::channel();
+            let started = Instant::now();
+            LauncherService::launch(&plan, &post_hooks, grace, &stderr_dir, move |event| {
+                let _ = tx.send(event);
+            })
+            .unwrap();
+            // 不等待观察期
+            assert!(started.elapsed() < Duration::from_millis(250));
+            rx
+        };
+
+        let rx = launch(r#"sh -c "sleep 0.3; echo boom >&2; exit 3""#, Duration::from_secs(3));
+        match rx.recv_timeout(Duration::from_secs(5)).unwrap() {
+            LaunchEvent::Failed { exit_code, stderr } => {
+                assert_eq!(exit_code, Some(3));
+                assert_eq!(stderr, "boom");
             }
+            event => panic!("unexpected event: {:?}", event),
         }
+        assert!(matches!(rx.recv_timeout(Duration::from_secs(5)).unwrap(), LaunchEvent::PostHooks(_)));
 
-        Ok(())
+        // 观察期之后的退出不算启动失败
+        let rx = launch(r#"sh -c "sleep 0.5; exit 1""#, Duration::from_millis(100));
+        assert!(matches!(rx.recv_timeout(Duration::from_secs(5)).unwrap(), LaunchEvent::PostHooks(_)));
+        // 观察期结束后删除 stderr 文件
+        assert_eq!(fs::read_dir(&stderr_dir).unwrap().count(), 0);
     }
 }
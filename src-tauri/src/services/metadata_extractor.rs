@@ -0,0 +1,546 @@
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use crate::models::project::ProjectMetadata;
+
+/// README 摘要最大长度（字符数）
+const MAX_DESCRIPTION_CHARS: usize = 200;
+
+pub struct MetadataExtractor;
+
+impl MetadataExtractor {
+    /// 从清单文件和 README 提取项目描述信息
+    ///
+    /// 多个清单同时存在时按顺序取第一个非空值，描述缺失时回退到 README 首段
+    pub fn extract(project_path: &str) -> Option<ProjectMetadata> {
+        let path = Path::new(project_path);
+
+        let sources = [
+            Self::read(path, "package.json").and_then(|c| Self::parse_json_manifest(&c)),
+            Self::read(path, "Cargo.toml").and_then(|c| Self::parse_cargo_toml(&c)),
+            Self::read(path, "pyproject.toml").and_then(|c| Self::parse_pyproject(&c)),
+            Self::read(path, "composer.json").and_then(|c| Self::parse_json_manifest(&c)),
+            Self::read(path, "pubspec.yaml").and_then(|c| Self::parse_pubspec(&c)),
+        ];
+
+        let mut metadata = ProjectMetadata::default();
+        for source in sources.into_iter().flatten() {
+            metadata.merge(source);
+        }
+
+        if metadata.description.is_none() {
+            metadata.description = Self::readme_summary(path);
+        }
+
+        if metadata.is_empty() {
+            None
+        } else {
+            Some(metadata)
+        }
+    }
+
+    fn read(path: &Path, file: &str) -> Option<String> {
+        fs::read_to_string(path.join(file)).ok()
+    }
+
+    /// 解析 package.json / composer.json（字段名一致）
+    fn parse_json_manifest(content: &str) -> Option<ProjectMetadata> {
+        let json: Value = serde_json::from_str(content).ok()?;
+
+        // license 可能是字符串、{ "type": ... } 或数组（composer）
+        let license = match &json["license"] {
+            Value::String(s) => Some(s.clone()),
+            Value::Object(o) => o.get("type").and_then(|t| t.as_str()).map(|s| s.to_string()),
+            Value::Array(a) => {
+                let items: Vec<&str> = a.iter().filter_map(|v| v.as_str()).collect();
+                (!items.is_empty()).then(|| items.join(" OR "))
+            }
+            _ => None,
+        };
+
+        Some(ProjectMetadata {
+            description: Self::non_empty(json["description"].as_str()),
+            homepage: Self::non_empty(json["homepage"].as_str()),
+            license,
+            keywords: json["keywords"]
+                .as_array()
+                .map(|a| a.iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect())
+                .unwrap_or_default(),
+        })
+    }
+
+    fn parse_cargo_toml(content: &str) -> Option<ProjectMetadata> {
+        let doc = TomlSection::parse(content)?;
+        let package = TomlSection::find(&doc, "package")?;
+        Some(ProjectMetadata {
+            description: package.string("description"),
+            homepage: package.string("homepage").or_else(|| package.string("repository")),
+            license: package.string("license"),
+            keywords: package.array("keywords"),
+        })
+    }
+
+    /// 解析 pyproject.toml（PEP 621 `[project]`，回退到 `[tool.poetry]`）
+    fn parse_pyproject(content: &str) -> Option<ProjectMetadata> {
+        let doc = TomlSection::parse(content)?;
+        let mut metadata = ProjectMetadata::default();
+
+        if let Some(project) = TomlSection::find(&doc, "project") {
+            let urls = TomlSection::find(&doc, "project.urls");
+            metadata.merge(ProjectMetadata {
+                description: project.string("description"),
+                homepage: urls.as_ref().and_then(|u| {
+                    u.string("Homepage")
+                        .or_else(|| u.string("homepage"))
+                        .or_else(|| u.string("Repository"))
+                        .or_else(|| u.string("repository"))
+                }),
+                // license 可能是字符串或 { text = "..." }
+                license: project.string("license").or_else(|| project.table("license")?.string("text")),
+                keywords: project.array("keywords"),
+            });
+        }
+
+        if let Some(poetry) = TomlSection::find(&doc, "tool.poetry") {
+            metadata.merge(ProjectMetadata {
+                description: poetry.string("description"),
+                homepage: poetry.string("homepage").or_else(|| poetry.string("repository")),
+                license: poetry.string("license"),
+                keywords: poetry.array("keywords"),
+            });
+        }
+
+        (!metadata.is_empty()).then_some(metadata)
+    }
+
+    /// 解析 pubspec.yaml 顶层字段（只处理简单标量、`>`/`|` 块标量和 topics 列表）
+    fn parse_pubspec(content: &str) -> Option<ProjectMetadata> {
+        let mut metadata = ProjectMetadata::default();
+        let mut in_topics = false;
+        // 正在读取的块标量：(字段, 是否折叠, 已读取的行)
+        let mut block: Option<(&str, bool, Vec<&str>)> = None;
+
+        for line in content.lines() {
+            let is_top_level = !line.starts_with(' ') && !line.starts_with('\t');
+
+            if let Some((_, _, lines)) = &mut block {
+                if !is_top_level || line.trim().is_empty() {
+                    lines.push(line.trim());
+                    continue;
+                }
+            }
+            if let Some((key, folded, lines)) = block.take() {
+                Self::set_pubspec_field(&mut metadata, key, Self::block_scalar(&lines, folded));
+            }
+
+            if in_topics {
+                if let Some(item) = line.trim().strip_prefix("- ") {
+                    metadata.keywords.push(Self::unquote(item.trim()));
+                    continue;
+                }
+                if is_top_level && !line.trim().is_empty() {
+                    in_topics = false;
+                }
+            }
+
+            if !is_top_level {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else { continue };
+            let key = key.trim();
+            let value = value.trim();
+            if key == "topics" {
+                in_topics = true;
+            } else if let Some(indicator) = value.strip_prefix(['>', '|']) {
+                // 块标量：`>` 折叠为一行，`|` 保留换行；忽略 `-`/`+` 和缩进指示符
+                if indicator.chars().all(|c| matches!(c, '-' | '+' | '1'..='9')) {
+                    block = Some((key, value.starts_with('>'), Vec::new()));
+                }
+            } else {
+                Self::set_pubspec_field(&mut metadata, key, Self::unquote(value));
+            }
+        }
+        if let Some((key, folded, lines)) = block {
+            Self::set_pubspec_field(&mut metadata, key, Self::block_scalar(&lines, folded));
+        }
+
+        (!metadata.is_empty()).then_some(metadata)
+    }
+
+    fn set_pubspec_field(metadata: &mut ProjectMetadata, key: &str, value: String) {
+        if value.is_empty() {
+            return;
+        }
+        match key {
+            "description" => metadata.description = Some(value),
+            "homepage" => metadata.homepage = Some(value),
+            "repository" if metadata.homepage.is_none() => metadata.homepage = Some(value),
+            _ => {}
+        }
+    }
+
+    /// 合并块标量的各行（已去掉缩进）：折叠时空行分段、其余换行变为空格
+    fn block_scalar(lines: &[&str], folded: bool) -> String {
+        if !folded {
+            return lines.join("\n").trim().to_string();
+        }
+        let mut text = String::new();
+        for line in lines {
+            if line.is_empty() {
+                text.push('\n');
+            } else {
+                if !text.is_empty() && !text.ends_with('\n') {
+                    text.push(' ');
+                }
+                text.push_str(line);
+            }
+        }
+        text.trim().to_string()
+    }
+
+    /// 读取 README 首段作为描述
+    fn readme_summary(path: &Path) -> Option<String> {
+        let names = ["README.md", "readme.md", "Readme.md", "README.markdown", "README.rst", "README.txt", "README"];
+        let content = names.iter().find_map(|n| Self::read(path, n))?;
+        Self::first_paragraph(&content)
+    }
+
+    /// 跳过标题、徽章、HTML 和代码块，取第一段正文
+    fn first_paragraph(content: &str) -> Option<String> {
+        let mut paragraph: Vec<String> = Vec::new();
+        let mut in_code_block = false;
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+
+            if trimmed.starts_with("```") {
+                in_code_block = !in_code_block;
+                continue;
+            }
+            if in_code_block {
+                continue;
+            }
+
+            if trimmed.is_empty() {
+                if !paragraph.is_empty() {
+                    break;
+                }
+                continue;
+            }
+
+            let is_decoration = trimmed.starts_with('#')
+                || trimmed.starts_with('<')
+                || trimmed.starts_with("[![")
+                || trimmed.starts_with("![")
+                || trimmed.starts_with("---")
+                || trimmed.starts_with("===")
+                || trimmed.starts_with("..")
+                || trimmed.starts_with('|');
+            if is_decoration {
+                if !paragraph.is_empty() {
+                    break;
+                }
+                continue;
+            }
+
+            paragraph.push(Self::strip_markdown(trimmed));
+        }
+
+        let text = paragraph.join(" ");
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+
+        if text.chars().count() > MAX_DESCRIPTION_CHARS {
+            let truncated: String = text.chars().take(MAX_DESCRIPTION_CHARS).collect();
+            Some(format!("{}…", truncated.trim_end()))
+        } else {
+            Some(text.to_string())
+        }
+    }
+
+    /// 去掉行内 Markdown 标记：链接保留文字，成对的强调和行内代码符号去掉符号保留内容
+    ///
+    /// 不成对的符号和单词内的下划线（`snake_case`、`__init__`、`foo*bar`）原样保留。
+    fn strip_markdown(line: &str) -> String {
+        let chars: Vec<char> = line.chars().collect();
+        let mut result = String::with_capacity(line.len());
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let run = Self::run_len(&chars, i);
+            match c {
+                '[' => {
+                    // [text](url) -> text
+                    let Some(close) = chars[i + 1..].iter().position(|&ch| ch == ']').map(|p| i + 1 + p) else {
+                        result.push(c);
+                        i += 1;
+                        continue;
+                    };
+                    result.push_str(&Self::strip_markdown(&chars[i + 1..close].iter().collect::<String>()));
+                    i = close + 1;
+                    if chars.get(i) == Some(&'(') {
+                        if let Some(end) = chars[i..].iter().position(|&ch| ch == ')') {
+                            i += end + 1;
+                        }
+                    }
+                }
+                // `code` / ``code``：找同样长度的反引号
+                '`' => match Self::find_closing(&chars, i + run, c, run, |_, _| true) {
+                    Some(close) => {
+                        result.extend(&chars[i + run..close]);
+                        i = close + run;
+                    }
+                    None => {
+                        result.extend(&chars[i..i + run]);
+                        i += run;
+                    }
+                },
+                // *em* / **strong** / ***both***，_em_（双下划线多为 `__init__` 之类的名字，不处理）
+                '*' | '_' => {
+                    let is_word = |ch: Option<&char>| ch.is_some_and(|ch| ch.is_alphanumeric());
+                    let opens = run <= if c == '*' { 3 } else { 1 }
+                        && chars.get(i + run).is_some_and(|ch| !ch.is_whitespace())
+                        && (c == '*' || !is_word(i.checked_sub(1).and_then(|p| chars.get(p))));
+                    let close = opens.then(|| {
+                        Self::find_closing(&chars, i + run, c, run, |chars, k| {
+                            !chars[k - 1].is_whitespace() && (c == '*' || !is_word(chars.get(k + run)))
+                        })
+                    }).flatten();
+                    match close {
+                        Some(close) => {
+                            result.push_str(&Self::strip_markdown(&chars[i + run..close].iter().collect::<String>()));
+                            i = close + run;
+                        }
+                        None => {
+                            result.extend(&chars[i..i + run]);
+                            i += run;
+                        }
+                    }
+                }
+                _ => {
+                    result.push(c);
+                    i += 1;
+                }
+            }
+        }
+        result
+    }
+
+    /// 从 `start` 开始连续相同字符的个数
+    fn run_len(chars: &[char], start: usize) -> usize {
+        chars[start..].iter().take_while(|&&ch| ch == chars[start]).count()
+    }
+
+    /// 从 `from` 开始查找恰好 `len` 个 `marker` 组成、且满足 `accept` 的结束标记，返回其位置
+    fn find_closing(
+        chars: &[char],
+        from: usize,
+        marker: char,
+        len: usize,
+        accept: impl Fn(&[char], usize) -> bool,
+    ) -> Option<usize> {
+        let mut k = from;
+        while k < chars.len() {
+            if chars[k] != marker {
+                k += 1;
+                continue;
+            }
+            let run = Self::run_len(chars, k);
+            if run == len && k > from && accept(chars, k) {
+                return Some(k);
+            }
+            k += run;
+        }
+        None
+    }
+
+    fn unquote(value: &str) -> String {
+        value.trim_matches('"').trim_matches('\'').to_string()
+    }
+
+    fn non_empty(value: Option<&str>) -> Option<String> {
+        value.map(|s| s.trim()).filter(|s| !s.is_empty()).map(|s| s.to_string())
+    }
+}
+
+/// TOML 文档中的一个表（`[a.b]` 段或内联表）
+pub(crate) struct TomlSection<'a>(&'a toml::Table);
+
+impl<'a> TomlSection<'a> {
+    /// 解析 TOML 文档；有语法错误时返回 None
+    pub(crate) fn parse(content: &str) -> Option<toml::Table> {
+        content.parse().ok()
+    }
+
+    /// 按点分隔的名称查找表，如 `tool.poetry`
+    pub(crate) fn find(doc: &'a toml::Table, name: &str) -> Option<Self> {
+        name.split('.')
+            .try_fold(doc, |table, key| table.get(key)?.as_table())
+            .map(Self)
+    }
+
+    /// 表中所有键（保持文件中的顺序）
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &'a str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    pub(crate) fn string(&self, key: &str) -> Option<String> {
+        MetadataExtractor::non_empty(self.0.get(key)?.as_str())
+    }
+
+    fn array(&self, key: &str) -> Vec<String> {
+        self.0.get(key)
+            .and_then(toml::Value::as_array)
+            .map(|items| items.iter().filter_map(|v| MetadataExtractor::non_empty(v.as_str())).collect())
+            .unwrap_or_default()
+    }
+
+    /// `key = { ... }` 形式的内联表
+    fn table(&self, key: &str) -> Option<Self> {
+        self.0.get(key)?.as_table().map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_package_json() {
+        let content = r#"{
+            "name": "web",
+            "description": "Customer dashboard",
+            "homepage": "https://example.com",
+            "license": { "type": "MIT" },
+            "keywords": ["dashboard", "vue"]
+        }"#;
+        let meta = MetadataExtractor::parse_json_manifest(content).unwrap();
+        assert_eq!(meta.description.as_deref(), Some("Customer dashboard"));
+        assert_eq!(meta.homepage.as_deref(), Some("https://example.com"));
+        assert_eq!(meta.license.as_deref(), Some("MIT"));
+        assert_eq!(meta.keywords, vec!["dashboard", "vue"]);
+    }
+
+    #[test]
+    fn test_parse_composer_license_array() {
+        let content = r#"{ "license": ["MIT", "GPL-3.0"] }"#;
+        let meta = MetadataExtractor::parse_json_manifest(content).unwrap();
+        assert_eq!(meta.license.as_deref(), Some("MIT OR GPL-3.0"));
+    }
+
+    #[test]
+    fn test_parse_cargo_toml() {
+        let content = r#"
+[package]
+name = "launcher"
+description = "A project launcher" # 注释
+license = "MIT OR Apache-2.0"
+repository = "https://github.com/example/launcher"
+keywords = [
+    "launcher",
+    "tauri",
+]
+
+[dependencies]
+description = "not this one"
+"#;
+        let meta = MetadataExtractor::parse_cargo_toml(content).unwrap();
+        assert_eq!(meta.description.as_deref(), Some("A project launcher"));
+        assert_eq!(meta.license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(meta.homepage.as_deref(), Some("https://github.com/example/launcher"));
+        assert_eq!(meta.keywords, vec!["launcher", "tauri"]);
+    }
+
+    #[test]
+    fn test_parse_pyproject() {
+        let content = r#"
+[project]
+name = "api"
+description = "Internal API"
+license = { text = "BSD-3-Clause" }
+keywords = ["api", "fastapi"]
+
+[project.urls]
+Homepage = "https://api.example.com"
+"#;
+        let meta = MetadataExtractor::parse_pyproject(content).unwrap();
+        assert_eq!(meta.description.as_deref(), Some("Internal API"));
+        assert_eq!(meta.license.as_deref(), Some("BSD-3-Clause"));
+        assert_eq!(meta.homepage.as_deref(), Some("https://api.example.com"));
+        assert_eq!(meta.keywords, vec!["api", "fastapi"]);
+
+        let poetry = "[tool.poetry]\ndescription = 'Poetry app'\n";
+        let meta = MetadataExtractor::parse_pyproject(poetry).unwrap();
+        assert_eq!(meta.description.as_deref(), Some("Poetry app"));
+    }
+
+    #[test]
+    fn test_parse_toml_edge_cases() {
+        let content = r#"
+[project] # PEP 621
+description = """
+Say "hi" to the
+multi-line API.
+"""
+keywords = [
+    "a, b", # 逗号在字符串中
+    "c #d",
+]
+
+[tool.poetry]
+description = "say \"hi\""
+"#;
+        let meta = MetadataExtractor::parse_pyproject(content).unwrap();
+        assert_eq!(meta.description.as_deref(), Some("Say \"hi\" to the\nmulti-line API."));
+        assert_eq!(meta.keywords, vec!["a, b", "c #d"]);
+
+        let doc = TomlSection::parse(content).unwrap();
+        assert_eq!(TomlSection::find(&doc, "tool.poetry").unwrap().string("description").as_deref(), Some("say \"hi\""));
+        assert!(MetadataExtractor::parse_pyproject("[project\n").is_none());
+    }
+
+    #[test]
+    fn test_parse_pubspec() {
+        let content = "name: app\ndescription: \"A Flutter app\"\nhomepage: https://app.dev\ntopics:\n  - flutter\n  - mobile\nenvironment:\n  sdk: '>=3.0.0'\n";
+        let meta = MetadataExtractor::parse_pubspec(content).unwrap();
+        assert_eq!(meta.description.as_deref(), Some("A Flutter app"));
+        assert_eq!(meta.homepage.as_deref(), Some("https://app.dev"));
+        assert_eq!(meta.keywords, vec!["flutter", "mobile"]);
+
+        let folded = "name: app\ndescription: >-\n  A Flutter app\n  for notes.\n\n  Offline first.\nversion: 1.0.0\n";
+        let meta = MetadataExtractor::parse_pubspec(folded).unwrap();
+        assert_eq!(meta.description.as_deref(), Some("A Flutter app for notes.\nOffline first."));
+
+        let literal = "description: |\n  Line one\n  Line two\nhomepage: https://app.dev\n";
+        let meta = MetadataExtractor::parse_pubspec(literal).unwrap();
+        assert_eq!(meta.description.as_deref(), Some("Line one\nLine two"));
+        assert_eq!(meta.homepage.as_deref(), Some("https://app.dev"));
+    }
+
+    #[test]
+    fn test_strip_markdown_keeps_unpaired_markers() {
+        assert_eq!(
+            MetadataExtractor::strip_markdown("Use `snake_case` and **bold** or _em_ text"),
+            "Use snake_case and bold or em text",
+        );
+        assert_eq!(
+            MetadataExtractor::strip_markdown("Calls __init__ on my_module, foo*bar and a * b"),
+            "Calls __init__ on my_module, foo*bar and a * b",
+        );
+        assert_eq!(MetadataExtractor::strip_markdown("See ``a `b` c`` or *[docs](https://x)*"), "See a `b` c or docs");
+    }
+
+    #[test]
+    fn test_readme_first_paragraph() {
+        let content = "# Title\n\n[![CI](https://ci/badge.svg)](https://ci)\n\nA **fast** tool for\n[launching](https://x) projects.\n\nSecond paragraph.\n";
+        assert_eq!(
+            MetadataExtractor::first_paragraph(content).as_deref(),
+            Some("A fast tool for launching projects.")
+        );
+        assert_eq!(MetadataExtractor::first_paragraph("# Only a title\n"), None);
+    }
+}
@@ -4,3 +4,30 @@ pub mod type_detector;
 pub mod launcher_service;
 pub mod shortcut_manager;
 pub mod monitor_utils;
+pub mod job_manager;
+pub mod metadata_extractor;
+pub mod git_service;
+pub mod repository_report;
+pub mod git_sync;
+pub mod git_branch;
+pub mod git_hosting;
+pub mod git_clone;
+pub mod template_service;
+pub mod disk_usage;
+pub mod language_stats;
+pub mod process_scanner;
+pub mod task_discovery;
+pub mod task_runner;
+pub mod launch_hooks;
+pub mod command_template;
+pub mod supervisor;
+pub mod launch_env;
+pub mod launch_log;
+pub mod env_resolver;
+pub mod launcher_health;
+pub mod project_ranking;
+pub mod project_suggestions;
+#[cfg(target_os = "linux")]
+pub mod launcher_discovery;
+#[cfg(test)]
+pub mod test_utils;
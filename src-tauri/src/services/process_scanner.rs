@@ -0,0 +1,279 @@
+//! 查找在项目目录中运行的进程（Linux: 扫描 /proc）
+//! 通过 `/proc/<pid>/cwd` 和命令行中的绝对路径归属到项目，端口来自 `/proc/net/tcp{,6}`
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use anyhow::Result;
+use crate::models::process::{ProjectProcess, RunningProject};
+
+/// TCP LISTEN 状态
+const TCP_LISTEN: &str = "0A";
+
+pub struct ProcessScanner;
+
+impl ProcessScanner {
+    /// 扫描运行在各项目中的进程，`projects` 为 (路径, 名称)
+    #[cfg(target_os = "linux")]
+    pub fn scan(projects: &[(String, String)]) -> Vec<RunningProject> {
+        Self::scan_in(Path::new("/proc"), projects)
+    }
+
+    /// 其他平台暂不支持
+    #[cfg(not(target_os = "linux"))]
+    pub fn scan(_projects: &[(String, String)]) -> Vec<RunningProject> {
+        Vec::new()
+    }
+
+    /// 终止进程（SIGTERM，`force` 时 SIGKILL）
+    #[cfg(unix)]
+    pub fn terminate(pid: u32, force: bool) -> Result<()> {
+        let signal = if force { "-KILL" } else { "-TERM" };
+        let output = Command::new("kill").args([signal, &pid.to_string()]).output()?;
+        if !output.status.success() {
+            let msg = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            anyhow::bail!("{}", if msg.is_empty() { format!("无法终止进程 {}", pid) } else { msg });
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    pub fn terminate(pid: u32, force: bool) -> Result<()> {
+        let mut cmd = Command::new("taskkill");
+        cmd.args(["/PID", &pid.to_string()]);
+        if force {
+            cmd.arg("/F");
+        }
+        let output = cmd.output()?;
+        if !output.status.success() {
+            anyhow::bail!("无法终止进程 {}", pid);
+        }
+        Ok(())
+    }
+
+    /// 终止以 `pid` 为组长的整个进程组（Windows 上结束进程树）
+    #[cfg(unix)]
+    pub fn terminate_group(pid: u32, force: bool) -> Result<()> {
+        let signal = if force { "-KILL" } else { "-TERM" };
+        let output = Command::new("kill").args([signal, "--", &format!("-{}", pid)]).output()?;
+        if !output.status.success() {
+            let msg = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            anyhow::bail!("{}", if msg.is_empty() { format!("无法终止进程组 {}", pid) } else { msg });
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    pub fn terminate_group(pid: u32, _force: bool) -> Result<()> {
+        let output = Command::new("taskkill").args(["/T", "/F", "/PID", &pid.to_string()]).output()?;
+        if !output.status.success() {
+            anyhow::bail!("无法终止进程 {}", pid);
+        }
+        Ok(())
+    }
+
+    /// 在指定 proc 根目录下扫描（便于测试）
+    pub fn scan_in(proc_root: &Path, projects: &[(String, String)]) -> Vec<RunningProject> {
+        let Ok(entries) = fs::read_dir(proc_root) else { return Vec::new() };
+        let own_pid = std::process::id();
+
+        let mut listening = HashMap::new();
+        for table in ["net/tcp", "net/tcp6"] {
+            if let Ok(content) = fs::read_to_string(proc_root.join(table)) {
+                listening.extend(Self::parse_listening(&content));
+            }
+        }
+
+        let mut grouped: HashMap<usize, Vec<ProjectProcess>> = HashMap::new();
+        for entry in entries.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse::<u32>().ok()) else { continue };
+            if pid == own_pid {
+                continue;
+            }
+            let dir = entry.path();
+
+            // 内核线程没有命令行
+            let args = Self::read_cmdline(&dir);
+            if args.is_empty() {
+                continue;
+            }
+            let cwd = fs::read_link(dir.join("cwd")).ok();
+
+            let Some(index) = Self::match_project(projects, cwd.as_deref(), &args) else { continue };
+
+            let name = fs::read_to_string(dir.join("comm"))
+                .map(|s| s.trim().to_string())
+                .unwrap_or_else(|_| args[0].clone());
+
+            grouped.entry(index).or_default().push(ProjectProcess {
+                pid,
+                name,
+                command: args.join(" "),
+                cwd: cwd.map(|c| c.to_string_lossy().into_owned()),
+                ports: Self::socket_ports(&dir, &listening),
+            });
+        }
+
+        let mut running: Vec<RunningProject> = grouped
+            .into_iter()
+            .map(|(index, mut processes)| {
+                processes.sort_by_key(|p| p.pid);
+                RunningProject {
+                    path: projects[index].0.clone(),
+                    name: projects[index].1.clone(),
+                    processes,
+                }
+            })
+            .collect();
+        running.sort_by(|a, b| a.path.cmp(&b.path));
+        running
+    }
+
+    /// 解析 /proc/net/tcp，返回 socket inode -> 监听端口
+    pub fn parse_listening(content: &str) -> HashMap<u64, u16> {
+        content
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() < 10 || fields[3] != TCP_LISTEN {
+                    return None;
+                }
+                let port = u16::from_str_radix(fields[1].rsplit(':').next()?, 16).ok()?;
+                let inode = fields[9].parse::<u64>().ok()?;
+                (inode != 0).then_some((inode, port))
+            })
+            .collect()
+    }
+
+    /// 按工作目录或命令行中的绝对路径匹配项目（嵌套项目取最深的一个）
+    pub fn match_project(projects: &[(String, String)], cwd: Option<&Path>, args: &[String]) -> Option<usize> {
+        let candidates = cwd
+            .into_iter()
+            .map(Path::to_path_buf)
+            .chain(args.iter().filter(|a| a.starts_with('/')).map(PathBuf::from));
+
+        let mut best: Option<usize> = None;
+        for candidate in candidates {
+            for (index, (path, _)) in projects.iter().enumerate() {
+                if !candidate.starts_with(path) {
+                    continue;
+                }
+                if best.is_none_or(|b| path.len() > projects[b].0.len()) {
+                    best = Some(index);
+                }
+            }
+        }
+        best
+    }
+
+    fn read_cmdline(dir: &Path) -> Vec<String> {
+        fs::read(dir.join("cmdline"))
+            .map(|bytes| {
+                bytes
+                    .split(|&b| b == 0)
+                    .filter(|a| !a.is_empty())
+                    .map(|a| String::from_utf8_lossy(a).into_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 进程打开的监听 socket 对应的端口
+    fn socket_ports(dir: &Path, listening: &HashMap<u64, u16>) -> Vec<u16> {
+        if listening.is_empty() {
+            return Vec::new();
+        }
+        let Ok(fds) = fs::read_dir(dir.join("fd")) else { return Vec::new() };
+
+        let mut ports: Vec<u16> = fds
+            .flatten()
+            .filter_map(|fd| fs::read_link(fd.path()).ok())
+            .filter_map(|target| {
+                let target = target.to_string_lossy().into_owned();
+                let inode = target.strip_prefix("socket:[")?.strip_suffix(']')?.parse::<u64>().ok()?;
+                listening.get(&inode).copied()
+            })
+            .collect();
+        ports.sort_unstable();
+        ports.dedup();
+        ports
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use crate::services::test_utils::TempDir;
+
+    const TCP: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 4242 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:A1B2 0100007F:1F90 01 00000000:00000000 00:00000000 00000000  1000        0 4343 1 0000000000000000 20 4 30 10 -1
+";
+
+    fn fake_process(proc_root: &Path, pid: u32, cwd: &Path, cmdline: &[&str], sockets: &[u64]) {
+        let dir = proc_root.join(pid.to_string());
+        fs::create_dir_all(dir.join("fd")).unwrap();
+        symlink(cwd, dir.join("cwd")).unwrap();
+        fs::write(dir.join("cmdline"), cmdline.join("\0") + "\0").unwrap();
+        fs::write(dir.join("comm"), format!("{}\n", cmdline[0].rsplit('/').next().unwrap())).unwrap();
+        for (i, inode) in sockets.iter().enumerate() {
+            symlink(format!("socket:[{}]", inode), dir.join("fd").join(i.to_string())).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_parse_listening() {
+        let listening = ProcessScanner::parse_listening(TCP);
+        assert_eq!(listening, HashMap::from([(4242, 8080)]));
+    }
+
+    #[test]
+    fn test_match_project_prefers_deepest() {
+        let projects = vec![
+            ("/work/mono".to_string(), "mono".to_string()),
+            ("/work/mono/apps/web".to_string(), "web".to_string()),
+            ("/work/other".to_string(), "other".to_string()),
+        ];
+        let m = |cwd: &str, args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+            ProcessScanner::match_project(&projects, Some(Path::new(cwd)), &args)
+        };
+        assert_eq!(m("/work/mono/apps/web/src", &["node"]), Some(1));
+        assert_eq!(m("/work/mono", &["node"]), Some(0));
+        assert_eq!(m("/home/u", &["code", "/work/other"]), Some(2));
+        // 仅前缀相同的目录不算
+        assert_eq!(m("/work/other-tool", &["sh"]), None);
+    }
+
+    #[test]
+    fn test_scan_fake_proc() {
+        let tmp = TempDir::new();
+        let proc_root = tmp.join("proc");
+        let project = tmp.join("app");
+        let elsewhere = tmp.join("elsewhere");
+        fs::create_dir_all(proc_root.join("net")).unwrap();
+        fs::create_dir_all(&project).unwrap();
+        fs::create_dir_all(&elsewhere).unwrap();
+        fs::write(proc_root.join("net/tcp"), TCP).unwrap();
+
+        let vite = project.join("node_modules/.bin/vite");
+        fake_process(&proc_root, 100, &project, &["node", vite.to_str().unwrap()], &[4242, 4343]);
+        fake_process(&proc_root, 200, &elsewhere, &["/usr/bin/nvim", project.join("src/main.ts").to_str().unwrap()], &[]);
+        fake_process(&proc_root, 300, &elsewhere, &["bash"], &[]);
+        fs::create_dir_all(proc_root.join("self")).unwrap();
+
+        let projects = vec![(project.to_string_lossy().into_owned(), "app".to_string())];
+        let running = ProcessScanner::scan_in(&proc_root, &projects);
+
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].name, "app");
+        let processes = &running[0].processes;
+        assert_eq!(processes.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![100, 200]);
+        assert_eq!(processes[0].ports, vec![8080]);
+        assert_eq!(processes[1].name, "nvim");
+        assert!(processes[0].command.starts_with("node "));
+    }
+}
@@ -0,0 +1,154 @@
+//! 项目列表排序：置顶项目始终在前，其余按设置的排序方式排列
+//!
+//! frecency 按启动日志计分：每次成功的启动按距今时间落入的分段计分后累加，
+//! 因此近期频繁使用的项目会超过很久以前大量打开过的项目。
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use crate::models::config::FrecencyBucket;
+use crate::models::launcher::LaunchLogEntry;
+use crate::models::project::Project;
+
+pub struct ProjectRanking;
+
+impl ProjectRanking {
+    /// 按排序方式排序；`history` 只在 frecency 模式下使用
+    pub fn sort(
+        projects: &mut [Project],
+        sort_by: &str,
+        history: &[LaunchLogEntry],
+        buckets: &[FrecencyBucket],
+        now: DateTime<Utc>,
+    ) {
+        let scores = if sort_by == "frecency" {
+            Self::frecency_scores(projects, history, buckets, now)
+        } else {
+            HashMap::new()
+        };
+        let score = |p: &Project| scores.get(&p.path).copied().unwrap_or(0);
+
+        projects.sort_by(|a, b| {
+            // 置顶项目始终在前
+            b.top.cmp(&a.top).then_with(|| match sort_by {
+                "frecency" => score(b).cmp(&score(a))
+                    .then_with(|| Self::by_last_opened(a, b))
+                    .then_with(|| Self::by_name(a, b)),
+                "last_opened" => Self::by_last_opened(a, b).then_with(|| Self::by_name(a, b)),
+                "name" => Self::by_name(a, b),
+                // 默认按打开次数降序，相同则按名称升序
+                _ => b.hits.cmp(&a.hits).then_with(|| Self::by_name(a, b)),
+            })
+        });
+    }
+
+    /// 各项目的 frecency 分数（项目路径 -> 分数）
+    ///
+    /// 启动日志中没有记录的项目（如日志出现之前打开过）按 `last_opened` 计一次启动。
+    pub fn frecency_scores(
+        projects: &[Project],
+        history: &[LaunchLogEntry],
+        buckets: &[FrecencyBucket],
+        now: DateTime<Utc>,
+    ) -> HashMap<String, u64> {
+        let mut scores: HashMap<String, u64> = HashMap::new();
+        for entry in history.iter().filter(|e| e.success) {
+            let weight = Self::weight(&entry.time, buckets, now);
+            *scores.entry(entry.project_path.clone()).or_default() += weight;
+        }
+        for project in projects {
+            if !scores.contains_key(&project.path) {
+                if let Some(time) = &project.last_opened {
+                    scores.insert(project.path.clone(), Self::weight(time, buckets, now));
+                }
+            }
+        }
+        scores
+    }
+
+    /// 某个时间的启动所得的分数：取覆盖该时间的最短分段，与分段的配置顺序无关
+    fn weight(time: &str, buckets: &[FrecencyBucket], now: DateTime<Utc>) -> u64 {
+        let Ok(time) = DateTime::parse_from_rfc3339(time) else {
+            return 0;
+        };
+        let age = now.signed_duration_since(time);
+        buckets.iter()
+            .filter(|b| age <= chrono::Duration::days(b.days as i64))
+            .min_by_key(|b| b.days)
+            .map_or(0, |b| b.weight as u64)
+    }
+
+    /// 最近打开的在前，从未打开的在最后
+    fn by_last_opened(a: &Project, b: &Project) -> Ordering {
+        let time = |p: &Project| p.last_opened.as_deref()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok());
+        time(b).cmp(&time(a))
+    }
+
+    fn by_name(a: &Project, b: &Project) -> Ordering {
+        a.name.to_lowercase().cmp(&b.name.to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::project::VersionControl;
+    use crate::services::test_utils::launch_entry;
+
+    fn project(name: &str, hits: u32, top: bool) -> Project {
+        let mut project = Project::new(format!("/p/{}", name), name.to_string(), VersionControl::Git);
+        project.hits = hits;
+        project.top = top;
+        project
+    }
+
+    fn launch(project: &str, days_ago: i64, now: DateTime<Utc>) -> LaunchLogEntry {
+        let time = (now - chrono::Duration::days(days_ago)).to_rfc3339();
+        launch_entry("", &format!("/p/{}", project), "code", &time)
+    }
+
+    fn names(projects: &[Project]) -> Vec<&str> {
+        projects.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_frecency_prefers_recent_use() {
+        let now = Utc::now();
+        let buckets = vec![
+            FrecencyBucket { days: 7, weight: 100 },
+            FrecencyBucket { days: 365, weight: 1 },
+        ];
+        let mut projects = vec![project("old", 500, false), project("week", 10, false), project("pinned", 0, true)];
+
+        // old 去年打开了 200 次，week 本周打开了 5 次
+        let mut history: Vec<_> = (0..200).map(|_| launch("old", 300, now)).collect();
+        history.extend((0..5).map(|i| launch("week", i, now)));
+        let mut failed = launch("old", 0, now);
+        failed.success = false;
+        history.push(failed);
+
+        ProjectRanking::sort(&mut projects, "frecency", &history, &buckets, now);
+        assert_eq!(names(&projects), ["pinned", "week", "old"]);
+
+        ProjectRanking::sort(&mut projects, "hits", &history, &buckets, now);
+        assert_eq!(names(&projects), ["pinned", "old", "week"]);
+
+        ProjectRanking::sort(&mut projects, "name", &history, &buckets, now);
+        assert_eq!(names(&projects), ["pinned", "old", "week"]);
+
+        // 没有启动记录时按 last_opened 计一次
+        projects[1].last_opened = Some((now - chrono::Duration::days(1)).to_rfc3339());
+        let scores = ProjectRanking::frecency_scores(&projects, &[], &buckets, now);
+        assert_eq!(scores.get("/p/old"), Some(&100));
+        assert_eq!(scores.get("/p/week"), None);
+
+        // 分段顺序不影响计分
+        let reversed: Vec<_> = buckets.iter().rev().cloned().collect();
+        let history: Vec<_> = (0..5).map(|i| launch("week", i, now)).collect();
+        assert_eq!(
+            ProjectRanking::frecency_scores(&[], &history, &reversed, now),
+            ProjectRanking::frecency_scores(&[], &history, &buckets, now),
+        );
+    }
+}
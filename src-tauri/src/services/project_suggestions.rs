@@ -0,0 +1,193 @@
+//! 根据启动记录推荐项目：
+//! - 时段偏好：过去一段时间内，在每周同一天（或同为工作日/周末）、相近时段打开过的项目
+//! - 共同打开：刚打开的项目之后，通常紧接着会打开的项目
+
+use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Timelike, Weekday};
+use crate::models::launcher::{LaunchLogEntry, ProjectSuggestion, SuggestionReason};
+use crate::models::project::Project;
+
+/// 计算时段偏好时只看最近这么多天的记录
+const AFFINITY_DAYS: i64 = 90;
+/// 时段偏好的最低得分（避免只打开过一次的项目被推荐）
+const MIN_AFFINITY: f64 = 1.5;
+/// 在这段时间内打开的项目视为当前正在使用，作为共同打开的依据，且不再推荐
+const SESSION_MINUTES: i64 = 120;
+/// 打开某个项目后这段时间内打开的其他项目视为一起打开
+const CO_LAUNCH_MINUTES: i64 = 30;
+/// 共同打开至少出现的次数和比例
+const MIN_CO_LAUNCHES: u32 = 2;
+const MIN_CO_RATIO: f64 = 0.3;
+/// 共同打开相对时段偏好的权重
+const CO_LAUNCH_WEIGHT: f64 = 2.0;
+
+pub struct ProjectSuggestions;
+
+impl ProjectSuggestions {
+    /// 按得分从高到低返回推荐；`now` 的时区决定时段（使用本地时间）
+    pub fn suggest(
+        history: &[LaunchLogEntry],
+        projects: &[Project],
+        now: DateTime<FixedOffset>,
+        limit: usize,
+    ) -> Vec<ProjectSuggestion> {
+        let known: HashSet<&str> = projects.iter().map(|p| p.path.as_str()).collect();
+        let mut launches: Vec<(DateTime<FixedOffset>, &str)> = history.iter()
+            .filter(|e| e.success && known.contains(e.project_path.as_str()))
+            .filter_map(|e| {
+                let time = DateTime::parse_from_rfc3339(&e.time).ok()?.with_timezone(&now.timezone());
+                (time <= now).then_some((time, e.project_path.as_str()))
+            })
+            .collect();
+        launches.sort_by_key(|(time, _)| *time);
+
+        // 当前会话中打开的项目，最近的在前
+        let session_start = now - Duration::minutes(SESSION_MINUTES);
+        let mut open: Vec<&str> = Vec::new();
+        for (_, path) in launches.iter().rev().take_while(|(time, _)| *time >= session_start) {
+            if !open.contains(path) {
+                open.push(path);
+            }
+        }
+
+        let affinity = Self::time_affinity(&launches, now);
+        let max_affinity = affinity.values().copied().fold(0.0, f64::max);
+        let co_launch = Self::co_launch(&launches, &open);
+
+        let mut suggestions: Vec<ProjectSuggestion> = known.iter()
+            .filter(|path| !open.contains(path))
+            .filter_map(|&path| {
+                let time_score = affinity.get(path)
+                    .filter(|&&a| a >= MIN_AFFINITY)
+                    .map_or(0.0, |a| a / max_affinity);
+                let co = co_launch.get(path);
+                let co_score = co.map_or(0.0, |(ratio, _)| ratio * CO_LAUNCH_WEIGHT);
+                if time_score == 0.0 && co_score == 0.0 {
+                    return None;
+                }
+                let reason = match co {
+                    Some((_, after)) if co_score >= time_score => SuggestionReason::CoLaunch { after: after.to_string() },
+                    _ => SuggestionReason::TimeOfDay,
+                };
+                Some(ProjectSuggestion {
+                    project_path: path.to_string(),
+                    score: time_score + co_score,
+                    reason,
+                })
+            })
+            .collect();
+        suggestions.sort_by(|a, b| {
+            b.score.total_cmp(&a.score).then_with(|| a.project_path.cmp(&b.project_path))
+        });
+        suggestions.truncate(limit);
+        suggestions
+    }
+
+    /// 时段偏好：每次启动按与现在的时段、星期的接近程度计分
+    fn time_affinity<'a>(launches: &[(DateTime<FixedOffset>, &'a str)], now: DateTime<FixedOffset>) -> HashMap<&'a str, f64> {
+        let since = now - Duration::days(AFFINITY_DAYS);
+        let mut scores: HashMap<&str, f64> = HashMap::new();
+        for (time, path) in launches.iter().filter(|(time, _)| *time >= since) {
+            let diff = time.hour().abs_diff(now.hour());
+            let hour = match diff.min(24 - diff) {
+                0 => 1.0,
+                1 => 0.5,
+                _ => continue,
+            };
+            let day = if time.weekday() == now.weekday() {
+                1.0
+            } else if Self::is_weekend(time.weekday()) == Self::is_weekend(now.weekday()) {
+                0.5
+            } else {
+                continue;
+            };
+            *scores.entry(path).or_default() += hour * day;
+        }
+        scores
+    }
+
+    /// 共同打开：对当前会话中的每个项目，统计其之后紧接着打开其他项目的比例，
+    /// 返回 项目 -> (比例, 之前打开的项目)，取比例最高的
+    fn co_launch<'a>(launches: &[(DateTime<FixedOffset>, &'a str)], open: &[&'a str]) -> HashMap<&'a str, (f64, &'a str)> {
+        let window = Duration::minutes(CO_LAUNCH_MINUTES);
+        let mut best: HashMap<&str, (f64, &str)> = HashMap::new();
+        for &anchor in open {
+            let mut anchor_launches = 0u32;
+            let mut followers: HashMap<&str, u32> = HashMap::new();
+            for (i, (time, path)) in launches.iter().enumerate() {
+                if *path != anchor {
+                    continue;
+                }
+                anchor_launches += 1;
+                let following: HashSet<&str> = launches[i + 1..].iter()
+                    .take_while(|(t, _)| *t - *time <= window)
+                    .map(|(_, p)| *p)
+                    .filter(|p| *p != anchor)
+                    .collect();
+                for path in following {
+                    *followers.entry(path).or_default() += 1;
+                }
+            }
+            for (path, count) in followers {
+                let ratio = count as f64 / anchor_launches as f64;
+                if count < MIN_CO_LAUNCHES || ratio < MIN_CO_RATIO {
+                    continue;
+                }
+                if best.get(path).is_none_or(|(r, _)| ratio > *r) {
+                    best.insert(path, (ratio, anchor));
+                }
+            }
+        }
+        best
+    }
+
+    fn is_weekend(day: Weekday) -> bool {
+        matches!(day, Weekday::Sat | Weekday::Sun)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::project::VersionControl;
+    use crate::services::test_utils::launch_entry;
+
+    fn launch(path: &str, time: DateTime<FixedOffset>) -> LaunchLogEntry {
+        launch_entry("", path, "code", &time.to_rfc3339())
+    }
+
+    #[test]
+    fn test_suggest_by_time_and_co_launch() {
+        // 周一 09:30（东八区）
+        let now = DateTime::parse_from_rfc3339("2024-03-18T09:30:00+08:00").unwrap();
+        let projects: Vec<Project> = ["web", "api", "docs", "blog"].iter()
+            .map(|name| Project::new(format!("/p/{}", name), name.to_string(), VersionControl::Git))
+            .collect();
+        let days_ago = |days: i64, hour: u32| {
+            (now - Duration::days(days)).with_hour(hour).unwrap().with_minute(0).unwrap()
+        };
+
+        let mut history = Vec::new();
+        // 过去几个周一上午打开 docs，晚上打开 blog
+        for week in 1..=3 {
+            history.push(launch("/p/docs", days_ago(7 * week, 9)));
+            history.push(launch("/p/blog", days_ago(7 * week, 21)));
+        }
+        // 打开 web 之后通常打开 api
+        for day in [2, 3, 4] {
+            history.push(launch("/p/web", days_ago(day, 14)));
+            history.push(launch("/p/api", days_ago(day, 14) + Duration::minutes(5)));
+        }
+        history.push(launch("/p/removed", days_ago(1, 9)));
+        // 刚刚打开了 web
+        history.push(launch("/p/web", now - Duration::minutes(10)));
+
+        let suggestions = ProjectSuggestions::suggest(&history, &projects, now, 5);
+        let paths: Vec<_> = suggestions.iter().map(|s| s.project_path.as_str()).collect();
+        assert_eq!(paths, ["/p/api", "/p/docs"]);
+        assert_eq!(suggestions[0].reason, SuggestionReason::CoLaunch { after: "/p/web".to_string() });
+        assert_eq!(suggestions[1].reason, SuggestionReason::TimeOfDay);
+
+        assert_eq!(ProjectSuggestions::suggest(&history, &projects, now, 1).len(), 1);
+    }
+}
@@ -0,0 +1,256 @@
+use rayon::prelude::*;
+use crate::models::git::{RepositoryIssue, RepositoryReport, RepositoryStatus};
+use crate::models::project::{Project, VersionControl};
+use super::git_service::{GitService, GIT_TIMEOUT};
+
+pub struct RepositoryReporter;
+
+impl RepositoryReporter {
+    /// 并行评估所有 Git 项目
+    pub fn build(projects: &[Project], needs_attention_only: bool) -> RepositoryReport {
+        let mut repositories: Vec<RepositoryStatus> = projects
+            .par_iter()
+            .filter(|p| matches!(p.version_control, VersionControl::Git))
+            .map(|p| Self::evaluate(&p.name, &p.path))
+            .collect();
+
+        let total = repositories.len();
+        let needs_attention = repositories.iter().filter(|r| r.needs_attention()).count();
+
+        if needs_attention_only {
+            repositories.retain(|r| r.needs_attention());
+        }
+        repositories.sort_by_key(|r| r.name.to_lowercase());
+
+        RepositoryReport {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            total,
+            needs_attention,
+            repositories,
+        }
+    }
+
+    /// 评估单个仓库
+    pub fn evaluate(name: &str, path: &str) -> RepositoryStatus {
+        let git = match GitService::read_info(path) {
+            Ok(git) => git,
+            Err(e) => {
+                return RepositoryStatus {
+                    name: name.to_string(),
+                    path: path.to_string(),
+                    git: None,
+                    stashes: 0,
+                    issues: vec![RepositoryIssue::Error],
+                    error: Some(e.to_string()),
+                };
+            }
+        };
+
+        let stashes = GitService::run(path, &["stash", "list"], GIT_TIMEOUT)
+            .map(|out| out.lines().count() as u32)
+            .unwrap_or(0);
+
+        let mut issues = Vec::new();
+        if git.staged + git.unstaged > 0 {
+            issues.push(RepositoryIssue::Dirty);
+        }
+        if git.untracked > 0 {
+            issues.push(RepositoryIssue::Untracked);
+        }
+        if git.conflicted > 0 {
+            issues.push(RepositoryIssue::Conflicted);
+        }
+        if stashes > 0 {
+            issues.push(RepositoryIssue::Stashed);
+        }
+        if git.detached {
+            issues.push(RepositoryIssue::Detached);
+        } else if git.upstream.is_none() {
+            issues.push(RepositoryIssue::NoUpstream);
+        }
+        if git.ahead > 0 {
+            issues.push(RepositoryIssue::Ahead);
+        }
+        if git.behind > 0 {
+            issues.push(RepositoryIssue::Behind);
+        }
+
+        RepositoryStatus {
+            name: name.to_string(),
+            path: path.to_string(),
+            git: Some(git),
+            stashes,
+            issues,
+            error: None,
+        }
+    }
+
+    /// 导出为 Markdown 表格
+    pub fn to_markdown(report: &RepositoryReport) -> String {
+        let mut out = String::new();
+        out.push_str("# Repository Report\n\n");
+        out.push_str(&format!(
+            "Generated at {} · {} repositories · {} need attention\n\n",
+            report.generated_at, report.total, report.needs_attention
+        ));
+        out.push_str("| Project | Branch | Status | Path |\n");
+        out.push_str("| --- | --- | --- | --- |\n");
+
+        for repo in &report.repositories {
+            let branch = repo
+                .git
+                .as_ref()
+                .map(|g| {
+                    if g.detached {
+                        format!("({})", g.head.as_deref().unwrap_or("detached"))
+                    } else {
+                        g.branch.clone().unwrap_or_default()
+                    }
+                })
+                .unwrap_or_default();
+
+            out.push_str(&format!(
+                "| {} | {} | {} | `{}` |\n",
+                Self::escape(&repo.name),
+                Self::escape(&branch),
+                Self::escape(&Self::describe(repo)),
+                repo.path,
+            ));
+        }
+        out
+    }
+
+    /// 状态的简短描述，如 "3 modified, 2 ahead"
+    fn describe(repo: &RepositoryStatus) -> String {
+        if let Some(error) = &repo.error {
+            return format!("error: {}", error);
+        }
+        let Some(git) = &repo.git else { return String::new() };
+
+        let parts: Vec<String> = repo
+            .issues
+            .iter()
+            .map(|issue| match issue {
+                RepositoryIssue::Dirty => format!("{} modified", git.staged + git.unstaged),
+                RepositoryIssue::Untracked => format!("{} untracked", git.untracked),
+                RepositoryIssue::Conflicted => format!("{} conflicted", git.conflicted),
+                RepositoryIssue::Stashed => format!("{} stashed", repo.stashes),
+                RepositoryIssue::Ahead => format!("{} ahead", git.ahead),
+                RepositoryIssue::Behind => format!("{} behind", git.behind),
+                RepositoryIssue::NoUpstream => "no upstream".to_string(),
+                RepositoryIssue::Detached => "detached".to_string(),
+                RepositoryIssue::Error => "error".to_string(),
+            })
+            .collect();
+
+        if parts.is_empty() {
+            "clean".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    fn escape(value: &str) -> String {
+        value.replace('|', "\\|").replace('\n', " ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use crate::services::test_utils::{file_url, git, repo_with_remote, TempDir};
+
+    fn project(path: &Path) -> Project {
+        let name = path.file_name().unwrap().to_str().unwrap().to_string();
+        Project::new(path.to_str().unwrap().to_string(), name, VersionControl::Git)
+    }
+
+    #[test]
+    fn test_clean_repo_needs_no_attention() {
+        let tmp = TempDir::new();
+        let (_, work) = repo_with_remote(&tmp);
+
+        let status = RepositoryReporter::evaluate("work", work.to_str().unwrap());
+        assert!(status.issues.is_empty(), "{:?}", status);
+        assert!(!status.needs_attention());
+    }
+
+    #[test]
+    fn test_dirty_stashed_and_ahead() {
+        let tmp = TempDir::new();
+        let (_, work) = repo_with_remote(&tmp);
+
+        fs::write(work.join("README.md"), "changed\n").unwrap();
+        git(&work, &["stash"]);
+        fs::write(work.join("new.txt"), "new\n").unwrap();
+        git(&work, &["add", "new.txt"]);
+        git(&work, &["commit", "-m", "local"]);
+        fs::write(work.join("README.md"), "dirty\n").unwrap();
+        fs::write(work.join("untracked.txt"), "?\n").unwrap();
+
+        let status = RepositoryReporter::evaluate("work", work.to_str().unwrap());
+        assert_eq!(status.stashes, 1);
+        for issue in [RepositoryIssue::Dirty, RepositoryIssue::Untracked, RepositoryIssue::Stashed, RepositoryIssue::Ahead] {
+            assert!(status.issues.contains(&issue), "missing {:?} in {:?}", issue, status.issues);
+        }
+        assert!(status.needs_attention());
+    }
+
+    #[test]
+    fn test_no_upstream_and_behind() {
+        let tmp = TempDir::new();
+        let (remote, work) = repo_with_remote(&tmp);
+
+        // 另一个克隆推送新提交，使 work 落后
+        let other = tmp.join("other");
+        git(&tmp.0, &["clone", &file_url(&remote), "other"]);
+        fs::write(other.join("b.txt"), "b\n").unwrap();
+        git(&other, &["add", "."]);
+        git(&other, &["commit", "-m", "remote change"]);
+        git(&other, &["push"]);
+        git(&work, &["fetch"]);
+
+        let behind = RepositoryReporter::evaluate("work", work.to_str().unwrap());
+        assert_eq!(behind.issues, vec![RepositoryIssue::Behind]);
+        assert!(!behind.needs_attention());
+
+        git(&work, &["checkout", "-b", "feature"]);
+        let no_upstream = RepositoryReporter::evaluate("work", work.to_str().unwrap());
+        assert_eq!(no_upstream.issues, vec![RepositoryIssue::NoUpstream]);
+        assert!(no_upstream.needs_attention());
+    }
+
+    #[test]
+    fn test_build_report_filters_and_markdown() {
+        let tmp = TempDir::new();
+        let (_, work) = repo_with_remote(&tmp);
+        let clean = tmp.join("clean");
+        git(&tmp.0, &["clone", &file_url(&tmp.join("remote.git")), "clean"]);
+        fs::write(work.join("dirty.txt"), "x\n").unwrap();
+
+        let mut svn = project(&tmp.0);
+        svn.version_control = VersionControl::Svn;
+        let projects = vec![project(&work), project(&clean), svn];
+
+        let report = RepositoryReporter::build(&projects, false);
+        assert_eq!(report.total, 2);
+        assert_eq!(report.needs_attention, 1);
+        assert_eq!(report.repositories.len(), 2);
+
+        let filtered = RepositoryReporter::build(&projects, true);
+        assert_eq!(filtered.repositories.len(), 1);
+        assert_eq!(filtered.repositories[0].name, "work");
+
+        let markdown = RepositoryReporter::to_markdown(&filtered);
+        assert!(markdown.contains("| work | main | 1 untracked |"), "{}", markdown);
+    }
+
+    #[test]
+    fn test_missing_repo_reports_error() {
+        let status = RepositoryReporter::evaluate("gone", "/nonexistent/project-launcher/repo");
+        assert_eq!(status.issues, vec![RepositoryIssue::Error]);
+        assert!(status.error.is_some());
+    }
+}
@@ -0,0 +1,603 @@
+//! 托管长时间运行的项目进程（开发服务器、watch 等）
+//! 进程独立于搜索框会话存活，保留最近的输出，崩溃后可按指数退避自动重启，
+//! 停止服务或退出应用时结束整个进程组。输出按 `LOG_FLUSH_INTERVAL` 批量发送
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use anyhow::Result;
+use crate::models::service::{ServiceInfo, ServiceLogLine, ServiceStatus};
+use crate::models::task::{OutputStream, ProjectTask};
+use super::process_scanner::ProcessScanner;
+
+/// 每个服务保留的输出行数
+const LOG_CAPACITY: usize = 1000;
+/// 重启退避：1s, 2s, 4s … 最多 30s
+const BACKOFF_BASE: Duration = Duration::from_secs(1);
+const BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// 运行超过该时长视为稳定，重启计数清零
+const STABLE_AFTER: Duration = Duration::from_secs(60);
+/// 连续崩溃超过该次数后放弃重启
+const MAX_RESTARTS: u32 = 10;
+/// 停止时等待进程响应 SIGTERM 的时间，超时后强制结束
+const STOP_GRACE: Duration = Duration::from_secs(3);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// 输出事件的发送间隔
+const LOG_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+/// 进程退出后等待输出读取完成的时间（后台子进程可能仍持有管道）
+const READER_GRACE: Duration = Duration::from_millis(500);
+
+/// Supervisor 事件回调
+pub enum SupervisorEvent {
+    /// 上次发送以来的新输出
+    Logs(Vec<ServiceLogLine>),
+    Changed(ServiceInfo),
+    Removed(String),
+}
+
+struct Service {
+    info: ServiceInfo,
+    argv: Vec<String>,
+    logs: VecDeque<ServiceLogLine>,
+    stop_requested: Arc<AtomicBool>,
+}
+
+#[derive(Clone)]
+struct Shared {
+    services: Arc<Mutex<HashMap<String, Service>>>,
+    on_event: Arc<dyn Fn(SupervisorEvent) + Send + Sync>,
+    /// 尚未发送的输出
+    pending_logs: Arc<Mutex<Vec<ServiceLogLine>>>,
+}
+
+pub struct Supervisor {
+    shared: Shared,
+}
+
+impl Supervisor {
+    pub fn new<F>(on_event: F) -> Self
+    where
+        F: Fn(SupervisorEvent) + Send + Sync + 'static,
+    {
+        let shared = Shared {
+            services: Arc::new(Mutex::new(HashMap::new())),
+            on_event: Arc::new(on_event),
+            pending_logs: Arc::new(Mutex::new(Vec::new())),
+        };
+
+        // 定时批量发送输出；Supervisor 及其监管线程都结束后退出
+        let pending = Arc::downgrade(&shared.pending_logs);
+        let on_event = shared.on_event.clone();
+        std::thread::spawn(move || flush_logs(pending, on_event.as_ref()));
+
+        Self { shared }
+    }
+
+    /// 以托管服务方式启动任务；同一项目的同一任务复用已有条目（保留日志）
+    pub fn start(&self, project_path: &str, task: &ProjectTask, auto_restart: bool) -> Result<ServiceInfo> {
+        if task.command.is_empty() {
+            anyhow::bail!("任务命令为空");
+        }
+
+        let id = {
+            let mut services = self.shared.services.lock().unwrap();
+            let existing = services
+                .values()
+                .find(|s| s.info.project_path == project_path && s.info.name == task.name && s.info.source == task.source)
+                .map(|s| s.info.id.clone());
+
+            match existing {
+                Some(id) => {
+                    let service = services.get_mut(&id).unwrap();
+                    if service.info.status.is_active() {
+                        anyhow::bail!("服务已在运行");
+                    }
+                    service.argv = task.command.clone();
+                    service.info.command = task.command.join(" ");
+                    service.info.auto_restart = auto_restart;
+                    id
+                }
+                None => {
+                    let id = uuid::Uuid::new_v4().to_string();
+                    services.insert(id.clone(), Service {
+                        info: ServiceInfo {
+                            id: id.clone(),
+                            project_path: project_path.to_string(),
+                            name: task.name.clone(),
+                            source: task.source,
+                            command: task.command.join(" "),
+                            status: ServiceStatus::Stopped,
+                            pid: None,
+                            auto_restart,
+                            restarts: 0,
+                            started_at: None,
+                            exit_code: None,
+                            error: None,
+                        },
+                        argv: task.command.clone(),
+                        logs: VecDeque::new(),
+                        stop_requested: Arc::new(AtomicBool::new(false)),
+                    });
+                    id
+                }
+            }
+        };
+
+        self.launch(&id)
+    }
+
+    /// 停止服务（结束整个进程组），不再自动重启
+    pub fn stop(&self, id: &str) -> Result<()> {
+        let pid = {
+            let services = self.shared.services.lock().unwrap();
+            let service = services.get(id).ok_or_else(|| anyhow::anyhow!("服务不存在"))?;
+            if !service.info.status.is_active() {
+                anyhow::bail!("服务未在运行");
+            }
+            service.stop_requested.store(true, Ordering::SeqCst);
+            service.info.pid
+        };
+
+        // 等待重启期间没有进程，监管线程会自行退出
+        match pid {
+            Some(pid) => ProcessScanner::terminate_group(pid, false),
+            None => Ok(()),
+        }
+    }
+
+    /// 重启服务：停止当前进程（如在运行）后重新启动
+    pub fn restart(&self, id: &str) -> Result<ServiceInfo> {
+        if self.is_active(id)? {
+            self.stop(id)?;
+            self.wait_stopped(&[id.to_string()]);
+        }
+        self.launch(id)
+    }
+
+    /// 移除已停止的服务及其日志
+    pub fn remove(&self, id: &str) -> Result<()> {
+        {
+            let mut services = self.shared.services.lock().unwrap();
+            let service = services.get(id).ok_or_else(|| anyhow::anyhow!("服务不存在"))?;
+            if service.info.status.is_active() {
+                anyhow::bail!("请先停止服务");
+            }
+            services.remove(id);
+        }
+        (self.shared.on_event)(SupervisorEvent::Removed(id.to_string()));
+        Ok(())
+    }
+
+    /// 所有服务（按项目和名称排序）
+    pub fn list(&self) -> Vec<ServiceInfo> {
+        let mut services: Vec<ServiceInfo> = self
+            .shared
+            .services
+            .lock()
+            .unwrap()
+            .values()
+            .map(|s| s.info.clone())
+            .collect();
+        services.sort_by(|a, b| (&a.project_path, &a.name).cmp(&(&b.project_path, &b.name)));
+        services
+    }
+
+    /// 服务最近的输出（旧的在前）
+    pub fn logs(&self, id: &str) -> Result<Vec<ServiceLogLine>> {
+        let services = self.shared.services.lock().unwrap();
+        let service = services.get(id).ok_or_else(|| anyhow::anyhow!("服务不存在"))?;
+        Ok(service.logs.iter().cloned().collect())
+    }
+
+    /// 停止所有服务（退出应用时调用），等待片刻后强制结束仍未退出的进程组
+    pub fn stop_all(&self) {
+        let ids: Vec<String> = self
+            .list()
+            .into_iter()
+            .filter(|s| s.status.is_active())
+            .map(|s| s.id)
+            .collect();
+        for id in &ids {
+            let _ = self.stop(id);
+        }
+        self.wait_stopped(&ids);
+    }
+
+    fn is_active(&self, id: &str) -> Result<bool> {
+        let services = self.shared.services.lock().unwrap();
+        let service = services.get(id).ok_or_else(|| anyhow::anyhow!("服务不存在"))?;
+        Ok(service.info.status.is_active())
+    }
+
+    /// 等待服务退出，超过 STOP_GRACE 后强制结束
+    fn wait_stopped(&self, ids: &[String]) {
+        let deadline = Instant::now() + STOP_GRACE;
+        let mut forced = false;
+        loop {
+            let remaining: Vec<Option<u32>> = {
+                let services = self.shared.services.lock().unwrap();
+                ids.iter()
+                    .filter_map(|id| services.get(id))
+                    .filter(|s| s.info.status.is_active())
+                    .map(|s| s.info.pid)
+                    .collect()
+            };
+            if remaining.is_empty() {
+                return;
+            }
+            if Instant::now() >= deadline {
+                if forced {
+                    return;
+                }
+                for pid in remaining.into_iter().flatten() {
+                    let _ = ProcessScanner::terminate_group(pid, true);
+                }
+                forced = true;
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// 启动进程并交给监管线程
+    fn launch(&self, id: &str) -> Result<ServiceInfo> {
+        // 先在锁内标记为运行中，防止并发启动同一服务
+        let (argv, cwd, stop) = {
+            let mut services = self.shared.services.lock().unwrap();
+            let service = services.get_mut(id).ok_or_else(|| anyhow::anyhow!("服务不存在"))?;
+            if service.info.status.is_active() {
+                anyhow::bail!("服务已在运行");
+            }
+            service.stop_requested = Arc::new(AtomicBool::new(false));
+            service.info.status = ServiceStatus::Running;
+            service.info.restarts = 0;
+            service.info.exit_code = None;
+            service.info.error = None;
+            (service.argv.clone(), service.info.project_path.clone(), service.stop_requested.clone())
+        };
+
+        let child = match spawn(&argv, &cwd) {
+            Ok(child) => child,
+            Err(e) => {
+                self.shared.update(id, |info| {
+                    info.status = ServiceStatus::Crashed;
+                    info.error = Some(e.to_string());
+                });
+                return Err(e);
+            }
+        };
+
+        let info = self.shared.mark_running(id, &child, &stop);
+        let shared = self.shared.clone();
+        let id = id.to_string();
+        std::thread::spawn(move || shared.supervise(&id, child, &argv, &cwd, &stop));
+
+        info.ok_or_else(|| anyhow::anyhow!("服务不存在"))
+    }
+}
+
+impl Shared {
+    /// 监管线程：等待进程退出，按需退避重启
+    fn supervise(&self, id: &str, mut child: Child, argv: &[String], cwd: &str, stop: &AtomicBool) {
+        loop {
+            let started = Instant::now();
+            let readers = self.attach_output(id, &mut child);
+            let status = child.wait();
+
+            // 进程已被回收，进程组号可能被复用，之后不再向它发送信号
+            if let Some(service) = self.services.lock().unwrap().get_mut(id) {
+                service.info.pid = None;
+            }
+            // 留在进程组中的后台子进程可能仍持有管道，只等待片刻，之后的输出由读取线程继续记录
+            let deadline = Instant::now() + READER_GRACE;
+            for reader in readers {
+                while !reader.is_finished() && Instant::now() < deadline {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                if reader.is_finished() {
+                    let _ = reader.join();
+                }
+            }
+
+            let mut delay = None;
+            self.update(id, |info| {
+                info.pid = None;
+                info.exit_code = status.as_ref().ok().and_then(|s| s.code());
+                if let Err(e) = &status {
+                    info.error = Some(e.to_string());
+                }
+                let success = status.as_ref().is_ok_and(|s| s.success());
+
+                if stop.load(Ordering::SeqCst) || success {
+                    info.status = ServiceStatus::Stopped;
+                } else if !info.auto_restart {
+                    info.status = ServiceStatus::Crashed;
+                } else {
+                    if started.elapsed() >= STABLE_AFTER {
+                        info.restarts = 0;
+                    }
+                    if info.restarts >= MAX_RESTARTS {
+                        info.status = ServiceStatus::Crashed;
+                        info.error = Some(format!("连续重启 {} 次后放弃", MAX_RESTARTS));
+                    } else {
+                        delay = Some(backoff(info.restarts));
+                        info.restarts += 1;
+                        info.status = ServiceStatus::Restarting;
+                    }
+                }
+            });
+
+            let Some(delay) = delay else { return };
+            if !sleep_unless_stopped(stop, delay) {
+                self.update(id, |info| info.status = ServiceStatus::Stopped);
+                return;
+            }
+
+            child = match spawn(argv, cwd) {
+                Ok(child) => child,
+                Err(e) => {
+                    self.update(id, |info| {
+                        info.status = ServiceStatus::Crashed;
+                        info.error = Some(e.to_string());
+                    });
+                    return;
+                }
+            };
+            self.mark_running(id, &child, stop);
+        }
+    }
+
+    /// 记录新进程；如果期间已请求停止，立即结束它
+    fn mark_running(&self, id: &str, child: &Child, stop: &AtomicBool) -> Option<ServiceInfo> {
+        let pid = child.id();
+        let info = {
+            let mut services = self.services.lock().unwrap();
+            let service = services.get_mut(id)?;
+            service.info.status = ServiceStatus::Running;
+            service.info.pid = Some(pid);
+            service.info.started_at = Some(chrono::Utc::now().to_rfc3339());
+            service.info.clone()
+        };
+        // stop() 在锁内设置标志并读取 pid，两边至少有一方能看到对方
+        if stop.load(Ordering::SeqCst) {
+            let _ = ProcessScanner::terminate_group(pid, false);
+        }
+        (self.on_event)(SupervisorEvent::Changed(info.clone()));
+        Some(info)
+    }
+
+    /// 修改服务信息并发送变更事件（回调在锁外调用）
+    fn update(&self, id: &str, f: impl FnOnce(&mut ServiceInfo)) {
+        let info = {
+            let mut services = self.services.lock().unwrap();
+            let Some(service) = services.get_mut(id) else { return };
+            f(&mut service.info);
+            service.info.clone()
+        };
+        (self.on_event)(SupervisorEvent::Changed(info));
+    }
+
+    fn attach_output(&self, id: &str, child: &mut Child) -> Vec<JoinHandle<()>> {
+        [
+            (child.stdout.take().map(|s| Box::new(s) as Box<dyn Read + Send>), OutputStream::Stdout),
+            (child.stderr.take().map(|s| Box::new(s) as Box<dyn Read + Send>), OutputStream::Stderr),
+        ]
+        .into_iter()
+        .filter_map(|(pipe, stream)| pipe.map(|p| (p, stream)))
+        .map(|(pipe, stream)| {
+            let shared = self.clone();
+            let id = id.to_string();
+            std::thread::spawn(move || {
+                let mut reader = BufReader::new(pipe);
+                let mut buf = Vec::new();
+                while reader.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+                    let line = String::from_utf8_lossy(&buf).trim_end_matches(['\r', '\n']).to_string();
+                    buf.clear();
+                    shared.push_log(&id, stream, line);
+                }
+            })
+        })
+        .collect()
+    }
+
+    fn push_log(&self, id: &str, stream: OutputStream, line: String) {
+        let log = ServiceLogLine {
+            service_id: id.to_string(),
+            stream,
+            line,
+            time: chrono::Utc::now().to_rfc3339(),
+        };
+        {
+            let mut services = self.services.lock().unwrap();
+            let Some(service) = services.get_mut(id) else { return };
+            if service.logs.len() >= LOG_CAPACITY {
+                service.logs.pop_front();
+            }
+            service.logs.push_back(log.clone());
+        }
+        self.pending_logs.lock().unwrap().push(log);
+    }
+}
+
+fn spawn(argv: &[String], cwd: &str) -> Result<Child> {
+    let (program, args) = argv.split_first().ok_or_else(|| anyhow::anyhow!("任务命令为空"))?;
+    let mut cmd = Command::new(program);
+    cmd.args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    // 独立进程组，停止时连同子进程一起结束
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+
+    Ok(cmd.spawn()?)
+}
+
+/// 每隔 `LOG_FLUSH_INTERVAL` 发送一次积累的输出
+fn flush_logs(pending: Weak<Mutex<Vec<ServiceLogLine>>>, on_event: &(dyn Fn(SupervisorEvent) + Send + Sync)) {
+    loop {
+        std::thread::sleep(LOG_FLUSH_INTERVAL);
+        let Some(pending) = pending.upgrade() else { return };
+        let logs = std::mem::take(&mut *pending.lock().unwrap());
+        if !logs.is_empty() {
+            on_event(SupervisorEvent::Logs(logs));
+        }
+    }
+}
+
+/// 第 `restarts` 次重启前的等待时间
+fn backoff(restarts: u32) -> Duration {
+    BACKOFF_BASE.saturating_mul(1 << restarts.min(16)).min(BACKOFF_MAX)
+}
+
+/// 分段睡眠，期间请求停止则返回 false
+fn sleep_unless_stopped(stop: &AtomicBool, duration: Duration) -> bool {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline {
+        if stop.load(Ordering::SeqCst) {
+            return false;
+        }
+        std::thread::sleep(POLL_INTERVAL.min(deadline - Instant::now()));
+    }
+    !stop.load(Ordering::SeqCst)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use crate::models::task::TaskSource;
+    use crate::services::test_utils::TempDir;
+
+    fn shell_task(name: &str, script: &str) -> ProjectTask {
+        ProjectTask {
+            name: name.to_string(),
+            source: TaskSource::Npm,
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            description: None,
+        }
+    }
+
+    fn supervisor() -> (Supervisor, mpsc::Receiver<ServiceInfo>) {
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        let supervisor = Supervisor::new(move |event| {
+            if let SupervisorEvent::Changed(info) = event {
+                let _ = tx.lock().unwrap().send(info);
+            }
+        });
+        (supervisor, rx)
+    }
+
+    fn wait_for(rx: &mpsc::Receiver<ServiceInfo>, status: ServiceStatus) -> ServiceInfo {
+        loop {
+            let info = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+            if info.status == status {
+                return info;
+            }
+        }
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        assert_eq!(backoff(0), Duration::from_secs(1));
+        assert_eq!(backoff(3), Duration::from_secs(8));
+        assert_eq!(backoff(40), BACKOFF_MAX);
+    }
+
+    #[test]
+    fn test_log_ring_buffer_and_reuse() {
+        let tmp = TempDir::new();
+        let dir = tmp.0.to_str().unwrap();
+        let (supervisor, rx) = supervisor();
+        let task = shell_task("dev", &format!("seq 1 {}", LOG_CAPACITY + 5));
+
+        let info = supervisor.start(dir, &task, false).unwrap();
+        assert_eq!(info.status, ServiceStatus::Running);
+        let stopped = wait_for(&rx, ServiceStatus::Stopped);
+        assert_eq!(stopped.exit_code, Some(0));
+
+        let logs = supervisor.logs(&info.id).unwrap();
+        assert_eq!(logs.len(), LOG_CAPACITY);
+        assert_eq!(logs[0].line, "6");
+        assert_eq!(logs.last().unwrap().line, (LOG_CAPACITY + 5).to_string());
+
+        // 再次启动同一任务复用条目
+        let again = supervisor.start(dir, &task, false).unwrap();
+        assert_eq!(again.id, info.id);
+        wait_for(&rx, ServiceStatus::Stopped);
+        assert_eq!(supervisor.list().len(), 1);
+        supervisor.remove(&info.id).unwrap();
+        assert!(supervisor.list().is_empty());
+    }
+
+    #[test]
+    fn test_logs_are_sent_in_batches() {
+        let tmp = TempDir::new();
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        let supervisor = Supervisor::new(move |event| {
+            if let SupervisorEvent::Logs(lines) = event {
+                let _ = tx.lock().unwrap().send(lines.len());
+            }
+        });
+        supervisor.start(tmp.0.to_str().unwrap(), &shell_task("dev", "seq 1 200"), false).unwrap();
+
+        let mut batches = 0;
+        let mut lines = 0;
+        while lines < 200 {
+            lines += rx.recv_timeout(Duration::from_secs(10)).unwrap();
+            batches += 1;
+        }
+        assert_eq!(lines, 200);
+        assert!(batches < 200);
+    }
+
+    #[test]
+    fn test_crash_without_restart() {
+        let tmp = TempDir::new();
+        let (supervisor, rx) = supervisor();
+        let info = supervisor
+            .start(tmp.0.to_str().unwrap(), &shell_task("dev", "echo boom >&2; exit 2"), false)
+            .unwrap();
+
+        let crashed = wait_for(&rx, ServiceStatus::Crashed);
+        assert_eq!(crashed.exit_code, Some(2));
+        let logs = supervisor.logs(&info.id).unwrap();
+        assert_eq!(logs[0].stream, OutputStream::Stderr);
+        assert_eq!(logs[0].line, "boom");
+    }
+
+    #[test]
+    fn test_restarts_after_crash_and_stops_group() {
+        let tmp = TempDir::new();
+        let marker = tmp.join("crashed-once");
+        let survivor = tmp.join("survived");
+        let script = format!(
+            "if [ -f {m} ]; then (sleep 1; touch {s}) & sleep 30; else touch {m}; exit 1; fi",
+            m = marker.display(),
+            s = survivor.display(),
+        );
+        let (supervisor, rx) = supervisor();
+        let info = supervisor.start(tmp.0.to_str().unwrap(), &shell_task("dev", &script), true).unwrap();
+
+        let restarting = wait_for(&rx, ServiceStatus::Restarting);
+        assert_eq!(restarting.restarts, 1);
+        let running = wait_for(&rx, ServiceStatus::Running);
+        assert!(running.pid.is_some());
+
+        supervisor.stop_all();
+        assert_eq!(supervisor.list()[0].status, ServiceStatus::Stopped);
+        std::thread::sleep(Duration::from_millis(1200));
+        assert!(!survivor.exists());
+        assert!(supervisor.stop(&info.id).is_err());
+    }
+}
@@ -0,0 +1,421 @@
+//! 发现项目中可运行的任务
+//! 支持 package.json scripts、Makefile、justfile、Taskfile、Cargo bin/example、composer scripts、pyproject scripts
+
+use std::fs;
+use std::path::Path;
+use serde_json::Value;
+use crate::models::task::{ProjectTask, TaskSource};
+use super::metadata_extractor::TomlSection;
+
+pub struct TaskDiscovery;
+
+impl TaskDiscovery {
+    /// 发现项目中的所有任务
+    pub fn discover(project_path: &str) -> Vec<ProjectTask> {
+        let path = Path::new(project_path);
+        let mut tasks = Vec::new();
+
+        if let Some(content) = Self::read(path, &["package.json"]) {
+            tasks.extend(Self::npm_scripts(&content, Self::package_manager(path)));
+        }
+        if let Some(content) = Self::read(path, &["Makefile", "makefile", "GNUmakefile"]) {
+            tasks.extend(Self::make_targets(&content));
+        }
+        if let Some(content) = Self::read(path, &["justfile", "Justfile", ".justfile"]) {
+            tasks.extend(Self::just_recipes(&content));
+        }
+        if let Some(content) = Self::read(path, &["Taskfile.yml", "Taskfile.yaml", "taskfile.yml", "taskfile.yaml"]) {
+            tasks.extend(Self::taskfile_tasks(&content));
+        }
+        if let Some(content) = Self::read(path, &["Cargo.toml"]) {
+            tasks.extend(Self::cargo_targets(path, &content));
+        }
+        if let Some(content) = Self::read(path, &["composer.json"]) {
+            tasks.extend(Self::composer_scripts(&content));
+        }
+        if let Some(content) = Self::read(path, &["pyproject.toml"]) {
+            tasks.extend(Self::pyproject_scripts(path, &content));
+        }
+
+        tasks
+    }
+
+    fn read(path: &Path, names: &[&str]) -> Option<String> {
+        names.iter().find_map(|name| fs::read_to_string(path.join(name)).ok())
+    }
+
+    /// 根据锁文件选择包管理器
+    fn package_manager(path: &Path) -> &'static str {
+        if path.join("pnpm-lock.yaml").exists() {
+            "pnpm"
+        } else if path.join("yarn.lock").exists() {
+            "yarn"
+        } else if path.join("bun.lockb").exists() || path.join("bun.lock").exists() {
+            "bun"
+        } else {
+            "npm"
+        }
+    }
+
+    fn task(name: &str, source: TaskSource, command: &[&str], description: Option<String>) -> ProjectTask {
+        ProjectTask {
+            name: name.to_string(),
+            source,
+            command: command.iter().map(|s| s.to_string()).collect(),
+            description,
+        }
+    }
+
+    fn npm_scripts(content: &str, manager: &str) -> Vec<ProjectTask> {
+        let Ok(json) = serde_json::from_str::<Value>(content) else { return Vec::new() };
+        let Some(scripts) = json.get("scripts").and_then(|s| s.as_object()) else { return Vec::new() };
+
+        scripts
+            .iter()
+            .map(|(name, script)| {
+                Self::task(name, TaskSource::Npm, &[manager, "run", name], script.as_str().map(|s| s.to_string()))
+            })
+            .collect()
+    }
+
+    fn composer_scripts(content: &str) -> Vec<ProjectTask> {
+        let Ok(json) = serde_json::from_str::<Value>(content) else { return Vec::new() };
+        let Some(scripts) = json.get("scripts").and_then(|s| s.as_object()) else { return Vec::new() };
+        let descriptions = json.get("scripts-descriptions").and_then(|d| d.as_object());
+
+        scripts
+            .keys()
+            .map(|name| {
+                let description = descriptions
+                    .and_then(|d| d.get(name))
+                    .and_then(|d| d.as_str())
+                    .map(|s| s.to_string());
+                Self::task(name, TaskSource::Composer, &["composer", "run-script", name], description)
+            })
+            .collect()
+    }
+
+    /// Makefile 目标：行首 `name:`，排除变量赋值、模式规则和 `.PHONY` 等特殊目标；
+    /// 支持 `target: ## 描述` 形式的说明
+    fn make_targets(content: &str) -> Vec<ProjectTask> {
+        let mut tasks: Vec<ProjectTask> = Vec::new();
+
+        for line in content.lines() {
+            if line.starts_with(['\t', ' ', '#', '.']) {
+                continue;
+            }
+            let Some((head, rest)) = line.split_once(':') else { continue };
+            if rest.starts_with('=') || head.contains(['=', '%', '$']) {
+                continue;
+            }
+            let description = rest
+                .split_once("##")
+                .map(|(_, d)| d.trim().to_string())
+                .filter(|d| !d.is_empty());
+
+            for name in head.split_whitespace() {
+                if !tasks.iter().any(|t| t.name == name) {
+                    tasks.push(Self::task(name, TaskSource::Make, &["make", name], description.clone()));
+                }
+            }
+        }
+        tasks
+    }
+
+    /// justfile 配方：行首 `name args:`，上一行的 `#` 注释作为说明
+    fn just_recipes(content: &str) -> Vec<ProjectTask> {
+        const KEYWORDS: &[&str] = &["set", "alias", "export", "import", "mod"];
+        let mut tasks = Vec::new();
+        let mut comment: Option<String> = None;
+
+        for line in content.lines() {
+            if let Some(text) = line.strip_prefix('#') {
+                comment = Some(text.trim().to_string()).filter(|c| !c.is_empty() && !c.starts_with('!'));
+                continue;
+            }
+            if line.is_empty() || line.starts_with([' ', '\t', '[']) {
+                if !line.starts_with('[') {
+                    comment = None;
+                }
+                continue;
+            }
+
+            let description = comment.take();
+            let Some((head, _)) = line.split_once(':') else { continue };
+            if line.contains(":=") {
+                continue;
+            }
+            let Some(name) = head.split_whitespace().next().map(|n| n.trim_start_matches('@')) else { continue };
+            if name.starts_with('_') || KEYWORDS.contains(&name) {
+                continue;
+            }
+            tasks.push(Self::task(name, TaskSource::Just, &["just", name], description));
+        }
+        tasks
+    }
+
+    /// Taskfile：`tasks:` 下第一层缩进的键为任务名，`desc:` 为说明
+    fn taskfile_tasks(content: &str) -> Vec<ProjectTask> {
+        let mut tasks: Vec<ProjectTask> = Vec::new();
+        let mut in_tasks = false;
+        let mut task_indent: Option<usize> = None;
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - line.trim_start().len();
+
+            if indent == 0 {
+                in_tasks = trimmed == "tasks:";
+                task_indent = None;
+                continue;
+            }
+            if !in_tasks {
+                continue;
+            }
+
+            let level = *task_indent.get_or_insert(indent);
+            if indent == level {
+                if let Some(name) = trimmed.strip_suffix(':').or_else(|| trimmed.split_once(": ").map(|(k, _)| k)) {
+                    let name = name.trim_matches(['"', '\'']);
+                    tasks.push(Self::task(name, TaskSource::Taskfile, &["task", name], None));
+                }
+            } else if let (Some(desc), Some(task)) = (trimmed.strip_prefix("desc:"), tasks.last_mut()) {
+                if task.description.is_none() {
+                    task.description = Some(desc.trim().trim_matches(['"', '\'']).to_string());
+                }
+            }
+        }
+        tasks.retain(|t| !t.name.starts_with('_'));
+        tasks
+    }
+
+    /// Cargo：`[[bin]]`、src/main.rs、src/bin/*、examples/*
+    fn cargo_targets(path: &Path, content: &str) -> Vec<ProjectTask> {
+        let doc = TomlSection::parse(content).unwrap_or_default();
+
+        // [[bin]] 段中的 name
+        let mut bins: Vec<String> = doc.get("bin")
+            .and_then(toml::Value::as_array)
+            .map(|targets| {
+                targets.iter()
+                    .filter_map(|t| t.get("name")?.as_str())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if bins.is_empty() && path.join("src/main.rs").exists() {
+            if let Some(name) = TomlSection::find(&doc, "package").and_then(|p| p.string("name")) {
+                bins.push(name);
+            }
+        }
+        bins.extend(Self::rust_targets(&path.join("src/bin")));
+
+        let mut tasks: Vec<ProjectTask> = Vec::new();
+        for bin in bins {
+            if !tasks.iter().any(|t| t.name == bin) {
+                tasks.push(Self::task(&bin, TaskSource::Cargo, &["cargo", "run", "--bin", &bin], None));
+            }
+        }
+        for example in Self::rust_targets(&path.join("examples")) {
+            let name = format!("example:{}", example);
+            tasks.push(Self::task(&name, TaskSource::Cargo, &["cargo", "run", "--example", &example], None));
+        }
+        tasks
+    }
+
+    /// 目录中的 `name.rs` 或 `name/main.rs`
+    fn rust_targets(dir: &Path) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(dir) else { return Vec::new() };
+        let mut names: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.is_dir() {
+                    path.join("main.rs").exists().then(|| entry.file_name().to_string_lossy().into_owned())
+                } else if path.extension().is_some_and(|e| e == "rs") {
+                    path.file_stem().map(|s| s.to_string_lossy().into_owned())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// pyproject：`[project.scripts]`、`[tool.poetry.scripts]`、`[tool.pdm.scripts]`
+    fn pyproject_scripts(path: &Path, content: &str) -> Vec<ProjectTask> {
+        let doc = TomlSection::parse(content).unwrap_or_default();
+        let mut tasks: Vec<ProjectTask> = Vec::new();
+
+        if let Some(pdm) = TomlSection::find(&doc, "tool.pdm.scripts") {
+            for name in pdm.keys().filter(|k| !k.starts_with('_')) {
+                tasks.push(Self::task(name, TaskSource::Pyproject, &["pdm", "run", name], None));
+            }
+        }
+
+        // 入口脚本通过项目的环境管理工具运行
+        let runner: &[&str] = if path.join("uv.lock").exists() {
+            &["uv", "run"]
+        } else if path.join("poetry.lock").exists() || TomlSection::find(&doc, "tool.poetry").is_some() {
+            &["poetry", "run"]
+        } else {
+            &[]
+        };
+
+        for section in ["project.scripts", "tool.poetry.scripts"] {
+            let Some(scripts) = TomlSection::find(&doc, section) else { continue };
+            for name in scripts.keys() {
+                if tasks.iter().any(|t| t.name == name) {
+                    continue;
+                }
+                let mut command = runner.to_vec();
+                command.push(name);
+                tasks.push(Self::task(name, TaskSource::Pyproject, &command, scripts.string(name)));
+            }
+        }
+        tasks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_utils::TempDir;
+
+    fn names(tasks: &[ProjectTask]) -> Vec<&str> {
+        tasks.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_npm_and_composer_scripts() {
+        let npm = TaskDiscovery::npm_scripts(r#"{"scripts": {"dev": "vite", "build": "vite build"}}"#, "pnpm");
+        assert_eq!(names(&npm), vec!["build", "dev"]);
+        assert_eq!(npm[1].command, vec!["pnpm", "run", "dev"]);
+        assert_eq!(npm[1].description.as_deref(), Some("vite"));
+
+        let composer = TaskDiscovery::composer_scripts(
+            r#"{"scripts": {"test": "phpunit"}, "scripts-descriptions": {"test": "Run tests"}}"#,
+        );
+        assert_eq!(composer[0].command, vec!["composer", "run-script", "test"]);
+        assert_eq!(composer[0].description.as_deref(), Some("Run tests"));
+    }
+
+    #[test]
+    fn test_make_targets() {
+        let makefile = "\
+CC := gcc
+VERSION = 1
+.PHONY: build test
+build: deps ## Build the app
+\t$(CC) main.c
+test lint:
+\t./test.sh
+%.o: %.c
+\t$(CC) -c $<
+$(BIN): main.o
+";
+        let tasks = TaskDiscovery::make_targets(makefile);
+        assert_eq!(names(&tasks), vec!["build", "test", "lint"]);
+        assert_eq!(tasks[0].description.as_deref(), Some("Build the app"));
+        assert_eq!(tasks[0].command, vec!["make", "build"]);
+    }
+
+    #[test]
+    fn test_just_recipes() {
+        let justfile = "\
+set dotenv-load
+version := \"1.0\"
+
+# Run the dev server
+dev port=\"3000\":
+    cargo run -- --port {{port}}
+
+[private]
+_helper:
+    echo hi
+
+@test *args: build
+    cargo test {{args}}
+";
+        let tasks = TaskDiscovery::just_recipes(justfile);
+        assert_eq!(names(&tasks), vec!["dev", "test"]);
+        assert_eq!(tasks[0].description.as_deref(), Some("Run the dev server"));
+        assert_eq!(tasks[1].description, None);
+    }
+
+    #[test]
+    fn test_taskfile_tasks() {
+        let taskfile = "\
+version: '3'
+vars:
+  NAME: app
+tasks:
+  build:
+    desc: Build the binary
+    cmds:
+      - go build
+  lint: golangci-lint run
+  _internal:
+    cmds: [echo]
+";
+        let tasks = TaskDiscovery::taskfile_tasks(taskfile);
+        assert_eq!(names(&tasks), vec!["build", "lint"]);
+        assert_eq!(tasks[0].description.as_deref(), Some("Build the binary"));
+        assert_eq!(tasks[1].command, vec!["task", "lint"]);
+    }
+
+    #[test]
+    fn test_cargo_targets() {
+        let tmp = TempDir::new();
+        fs::create_dir_all(tmp.join("src/bin")).unwrap();
+        fs::create_dir_all(tmp.join("examples/demo")).unwrap();
+        fs::write(tmp.join("src/main.rs"), "").unwrap();
+        fs::write(tmp.join("src/bin/tool.rs"), "").unwrap();
+        fs::write(tmp.join("examples/simple.rs"), "").unwrap();
+        fs::write(tmp.join("examples/demo/main.rs"), "").unwrap();
+
+        let tasks = TaskDiscovery::cargo_targets(&tmp.0, "[package]\nname = \"app\"\n");
+        assert_eq!(names(&tasks), vec!["app", "tool", "example:demo", "example:simple"]);
+        assert_eq!(tasks[3].command, vec!["cargo", "run", "--example", "simple"]);
+
+        let explicit = TaskDiscovery::cargo_targets(&tmp.0, "[package]\nname = \"app\"\n\n[[bin]]\nname = \"server\"\npath = \"src/main.rs\"\n");
+        assert_eq!(names(&explicit)[..2], ["server", "tool"]);
+    }
+
+    #[test]
+    fn test_pyproject_scripts() {
+        let tmp = TempDir::new();
+        fs::write(tmp.join("uv.lock"), "").unwrap();
+        let content = "\
+[project]
+name = \"svc\"
+
+[project.scripts]
+serve = \"svc.main:run\"
+
+[tool.pdm.scripts]
+lint = \"ruff check .\"
+";
+        let tasks = TaskDiscovery::pyproject_scripts(&tmp.0, content);
+        assert_eq!(names(&tasks), vec!["lint", "serve"]);
+        assert_eq!(tasks[0].command, vec!["pdm", "run", "lint"]);
+        assert_eq!(tasks[1].command, vec!["uv", "run", "serve"]);
+    }
+
+    #[test]
+    fn test_discover_combines_sources() {
+        let tmp = TempDir::new();
+        fs::write(tmp.join("package.json"), r#"{"scripts": {"dev": "vite"}}"#).unwrap();
+        fs::write(tmp.join("yarn.lock"), "").unwrap();
+        fs::write(tmp.join("Makefile"), "deploy:\n\t./deploy.sh\n").unwrap();
+
+        let tasks = TaskDiscovery::discover(tmp.0.to_str().unwrap());
+        assert_eq!(names(&tasks), vec!["dev", "deploy"]);
+        assert_eq!(tasks[0].command[0], "yarn");
+    }
+}
@@ -0,0 +1,331 @@
+//! 运行项目任务：流式输出、退出状态、停止和运行历史
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use anyhow::Result;
+use crate::models::task::{OutputStream, ProjectTask, TaskOutput, TaskRun, TaskRunStatus};
+use super::process_scanner::ProcessScanner;
+
+/// 每个项目保留的运行记录数
+const HISTORY_LIMIT: usize = 20;
+/// 进程退出后等待输出读取完成的时间（后台子进程可能仍持有管道）
+const READER_GRACE: Duration = Duration::from_millis(500);
+
+/// 任务事件回调
+pub enum TaskEvent {
+    Output(TaskOutput),
+    Finished(TaskRun),
+}
+
+struct RunningTask {
+    run: TaskRun,
+    stop_requested: Arc<AtomicBool>,
+}
+
+pub struct TaskRunner {
+    running: Arc<Mutex<HashMap<String, RunningTask>>>,
+    history: Arc<TaskHistory>,
+}
+
+impl TaskRunner {
+    pub fn new(history_path: PathBuf) -> Self {
+        Self {
+            running: Arc::new(Mutex::new(HashMap::new())),
+            history: Arc::new(TaskHistory::new(history_path)),
+        }
+    }
+
+    /// 在项目目录启动任务，输出逐行通过 `on_event` 回调，结束后写入历史
+    pub fn start<F>(&self, project_path: &str, task: &ProjectTask, on_event: F) -> Result<TaskRun>
+    where
+        F: Fn(TaskEvent) + Send + Sync + 'static,
+    {
+        let (program, args) = task.command.split_first().ok_or_else(|| anyhow::anyhow!("任务命令为空"))?;
+
+        let mut cmd = Command::new(program);
+        cmd.args(args)
+            .current_dir(project_path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        // 独立进程组，停止时连同子进程一起结束
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.process_group(0);
+        }
+
+        let mut child = cmd.spawn()?;
+        let run = TaskRun {
+            run_id: uuid::Uuid::new_v4().to_string(),
+            project_path: project_path.to_string(),
+            task_name: task.name.clone(),
+            source: task.source,
+            command: task.command.join(" "),
+            pid: Some(child.id()),
+            status: TaskRunStatus::Running,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+            exit_code: None,
+            error: None,
+        };
+
+        let stop_requested = Arc::new(AtomicBool::new(false));
+        self.running.lock().unwrap().insert(run.run_id.clone(), RunningTask {
+            run: run.clone(),
+            stop_requested: stop_requested.clone(),
+        });
+
+        let on_event = Arc::new(on_event);
+        let readers: Vec<_> = [
+            (child.stdout.take().map(|s| Box::new(s) as Box<dyn Read + Send>), OutputStream::Stdout),
+            (child.stderr.take().map(|s| Box::new(s) as Box<dyn Read + Send>), OutputStream::Stderr),
+        ]
+        .into_iter()
+        .filter_map(|(pipe, stream)| pipe.map(|p| (p, stream)))
+        .map(|(pipe, stream)| {
+            let on_event = on_event.clone();
+            let run_id = run.run_id.clone();
+            std::thread::spawn(move || {
+                let mut reader = BufReader::new(pipe);
+                let mut buf = Vec::new();
+                while reader.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+                    let line = String::from_utf8_lossy(&buf).trim_end_matches(['\r', '\n']).to_string();
+                    buf.clear();
+                    on_event(TaskEvent::Output(TaskOutput { run_id: run_id.clone(), stream, line }));
+                }
+            })
+        })
+        .collect();
+
+        let running = self.running.clone();
+        let history = self.history.clone();
+        let mut finished = run.clone();
+        std::thread::spawn(move || {
+            let status = child.wait();
+            // 进程已回收，进程号可能被复用，之后不再向该进程组发送信号
+            if let Some(task) = running.lock().unwrap().get_mut(&finished.run_id) {
+                task.run.pid = None;
+            }
+            // 后台子进程可能一直持有管道，超时后不再等待读取线程
+            let deadline = Instant::now() + READER_GRACE;
+            for reader in readers {
+                while !reader.is_finished() && Instant::now() < deadline {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                if reader.is_finished() {
+                    let _ = reader.join();
+                }
+            }
+
+            finished.finished_at = Some(chrono::Utc::now().to_rfc3339());
+            match status {
+                Ok(status) => {
+                    finished.exit_code = status.code();
+                    finished.status = if stop_requested.load(Ordering::SeqCst) {
+                        TaskRunStatus::Stopped
+                    } else if status.success() {
+                        TaskRunStatus::Succeeded
+                    } else {
+                        TaskRunStatus::Failed
+                    };
+                }
+                Err(e) => {
+                    finished.status = TaskRunStatus::Failed;
+                    finished.error = Some(e.to_string());
+                }
+            }
+
+            running.lock().unwrap().remove(&finished.run_id);
+            history.record(finished.clone());
+            on_event(TaskEvent::Finished(finished));
+        });
+
+        Ok(run)
+    }
+
+    /// 停止任务（结束整个进程组）；进程已退出、正在收尾时不再发送信号
+    pub fn stop(&self, run_id: &str) -> Result<()> {
+        let pid = {
+            let running = self.running.lock().unwrap();
+            let task = running.get(run_id).ok_or_else(|| anyhow::anyhow!("任务不存在或已结束"))?;
+            task.stop_requested.store(true, Ordering::SeqCst);
+            task.run.pid
+        };
+
+        match pid {
+            Some(pid) => ProcessScanner::terminate_group(pid, false),
+            None => Ok(()),
+        }
+    }
+
+    /// 正在运行的任务
+    pub fn running(&self) -> Vec<TaskRun> {
+        let mut runs: Vec<TaskRun> = self.running.lock().unwrap().values().map(|t| t.run.clone()).collect();
+        runs.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        runs
+    }
+
+    /// 项目最近的运行记录（新的在前）
+    pub fn history(&self, project_path: &str) -> Vec<TaskRun> {
+        self.history.get(project_path)
+    }
+}
+
+/// 按项目保存的运行历史（JSON 文件）
+struct TaskHistory {
+    path: PathBuf,
+    runs: Mutex<HashMap<String, VecDeque<TaskRun>>>,
+}
+
+impl TaskHistory {
+    fn new(path: PathBuf) -> Self {
+        let runs = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path, runs: Mutex::new(runs) }
+    }
+
+    fn record(&self, run: TaskRun) {
+        let mut runs = self.runs.lock().unwrap();
+        let entries = runs.entry(run.project_path.clone()).or_default();
+        entries.push_front(run);
+        entries.truncate(HISTORY_LIMIT);
+
+        if let Ok(json) = serde_json::to_string_pretty(&*runs) {
+            if let Some(parent) = self.path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            if let Err(e) = fs::write(&self.path, json) {
+                eprintln!("保存任务历史失败: {}", e);
+            }
+        }
+    }
+
+    fn get(&self, project_path: &str) -> Vec<TaskRun> {
+        self.runs
+            .lock()
+            .unwrap()
+            .get(project_path)
+            .map(|runs| runs.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use crate::models::task::TaskSource;
+    use crate::services::test_utils::TempDir;
+
+    fn shell_task(script: &str) -> ProjectTask {
+        ProjectTask {
+            name: "test".to_string(),
+            source: TaskSource::Make,
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            description: None,
+        }
+    }
+
+    fn collect(runner: &TaskRunner, dir: &str, task: &ProjectTask) -> (TaskRun, mpsc::Receiver<TaskEvent>) {
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        let run = runner
+            .start(dir, task, move |event| {
+                let _ = tx.lock().unwrap().send(event);
+            })
+            .unwrap();
+        (run, rx)
+    }
+
+    fn wait_finished(rx: &mpsc::Receiver<TaskEvent>, output: &mut Vec<(OutputStream, String)>) -> TaskRun {
+        loop {
+            match rx.recv_timeout(Duration::from_secs(10)).unwrap() {
+                TaskEvent::Output(o) => output.push((o.stream, o.line)),
+                TaskEvent::Finished(run) => return run,
+            }
+        }
+    }
+
+    #[test]
+    fn test_streams_output_and_exit_code() {
+        let tmp = TempDir::new();
+        let runner = TaskRunner::new(tmp.join("history.json"));
+        let dir = tmp.0.to_str().unwrap();
+
+        let (run, rx) = collect(&runner, dir, &shell_task("pwd; echo oops >&2; exit 3"));
+        assert_eq!(run.status, TaskRunStatus::Running);
+        assert_eq!(runner.running().len(), 1);
+
+        let mut output = Vec::new();
+        let finished = wait_finished(&rx, &mut output);
+        assert_eq!(finished.status, TaskRunStatus::Failed);
+        assert_eq!(finished.exit_code, Some(3));
+        assert!(output.contains(&(OutputStream::Stdout, tmp.0.to_string_lossy().into_owned())), "{:?}", output);
+        assert!(output.contains(&(OutputStream::Stderr, "oops".to_string())));
+        assert!(runner.running().is_empty());
+    }
+
+    #[test]
+    fn test_stop_kills_process_group() {
+        let tmp = TempDir::new();
+        let runner = TaskRunner::new(tmp.join("history.json"));
+        let marker = tmp.join("survived");
+        let script = format!("(sleep 1; touch {}) & sleep 30", marker.display());
+
+        let (run, rx) = collect(&runner, tmp.0.to_str().unwrap(), &shell_task(&script));
+        std::thread::sleep(Duration::from_millis(200));
+        runner.stop(&run.run_id).unwrap();
+
+        let finished = wait_finished(&rx, &mut Vec::new());
+        assert_eq!(finished.status, TaskRunStatus::Stopped);
+        std::thread::sleep(Duration::from_millis(1200));
+        assert!(!marker.exists());
+        assert!(runner.stop(&run.run_id).is_err());
+    }
+
+    #[test]
+    fn test_background_child_does_not_block_finish() {
+        let tmp = TempDir::new();
+        let runner = TaskRunner::new(tmp.join("history.json"));
+        let dir = tmp.0.to_str().unwrap();
+
+        // 后台的 sleep 继承了输出管道
+        let (run, rx) = collect(&runner, dir, &shell_task("sleep 30 & exit 0"));
+        let finished = wait_finished(&rx, &mut Vec::new());
+        assert_eq!(finished.status, TaskRunStatus::Succeeded);
+        assert!(runner.running().is_empty());
+        assert_eq!(runner.history(dir).len(), 1);
+
+        let _ = Command::new("kill").args(["-KILL", "--", &format!("-{}", run.pid.unwrap())]).status();
+    }
+
+    #[test]
+    fn test_history_is_persisted_and_limited() {
+        let tmp = TempDir::new();
+        let history_path = tmp.join("history.json");
+        let dir = tmp.0.to_str().unwrap();
+        {
+            let runner = TaskRunner::new(history_path.clone());
+            for i in 0..HISTORY_LIMIT + 2 {
+                let (_, rx) = collect(&runner, dir, &shell_task(&format!("exit {}", i % 2)));
+                wait_finished(&rx, &mut Vec::new());
+            }
+        }
+
+        let runner = TaskRunner::new(history_path);
+        let history = runner.history(dir);
+        assert_eq!(history.len(), HISTORY_LIMIT);
+        assert_eq!(history[0].exit_code, Some(((HISTORY_LIMIT + 1) % 2) as i32));
+        assert!(runner.history("/other").is_empty());
+    }
+}
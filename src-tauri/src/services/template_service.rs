@@ -0,0 +1,336 @@
+//! 项目模板：复制模板目录、替换变量、执行创建后命令
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use anyhow::Result;
+use walkdir::WalkDir;
+use crate::models::template::{PostCreateOutcome, ProjectTemplate, TemplateSource};
+use super::command_template::{CommandTemplate, ShellKind};
+use super::git_service::{GitService, GIT_TIMEOUT};
+use super::launch_hooks::HookRunner;
+
+/// 克隆 Git 模板的超时
+const TEMPLATE_CLONE_TIMEOUT: Duration = Duration::from_secs(120);
+/// 每条创建后命令的超时（安装依赖可能较慢）
+const POST_CREATE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// 复制模板时跳过的目录
+const SKIPPED_DIRS: &[&str] = &[".git", ".svn", ".hg"];
+
+pub struct TemplateService;
+
+impl TemplateService {
+    /// 内置变量：project_name、author、year，额外变量可覆盖内置变量
+    pub fn variables(name: &str, author: Option<&str>, extra: &HashMap<String, String>) -> HashMap<String, String> {
+        let author = author
+            .map(|a| a.to_string())
+            .or_else(Self::git_user_name)
+            .unwrap_or_default();
+
+        let mut vars = HashMap::new();
+        vars.insert("project_name".to_string(), name.to_string());
+        vars.insert("author".to_string(), author);
+        vars.insert("year".to_string(), chrono::Local::now().format("%Y").to_string());
+        vars.extend(extra.iter().map(|(k, v)| (k.clone(), v.clone())));
+        vars
+    }
+
+    /// 将模板生成到 `target`（必须不存在），失败时清理目标目录
+    pub fn materialize(template: &ProjectTemplate, target: &Path, vars: &HashMap<String, String>) -> Result<()> {
+        if target.exists() {
+            anyhow::bail!("目标目录已存在: {}", target.display());
+        }
+
+        let result = match &template.source {
+            TemplateSource::Local { path } => Self::copy_rendered(Path::new(path), target, vars),
+            TemplateSource::Git { url, branch } => Self::materialize_git(url, branch.as_deref(), target, vars),
+        };
+
+        if result.is_err() {
+            let _ = fs::remove_dir_all(target);
+        }
+        result
+    }
+
+    /// 在新项目中初始化 Git 仓库
+    pub fn git_init(target: &Path) -> Result<()> {
+        GitService::run(&target.to_string_lossy(), &["init"], GIT_TIMEOUT)?;
+        Ok(())
+    }
+
+    /// 依次执行创建后命令，遇到失败即停止
+    ///
+    /// 变量值（含用户输入的项目名）按当前平台 shell 的规则加引号，不会被 shell 解释；
+    /// 每条命令超过 `POST_CREATE_TIMEOUT` 时结束整个进程组并视为失败。
+    pub fn run_post_create(commands: &[String], dir: &Path, vars: &HashMap<String, String>) -> Vec<PostCreateOutcome> {
+        let mut outcomes = Vec::new();
+
+        for command in commands {
+            let outcome = match Self::render_shell(command, vars, ShellKind::native()) {
+                Ok(command) => {
+                    let run = HookRunner::run_shell(&command, &dir.to_string_lossy(), POST_CREATE_TIMEOUT);
+                    let output = if run.timed_out {
+                        format!("超过 {} 秒未完成，已结束\n{}", POST_CREATE_TIMEOUT.as_secs(), run.output)
+                    } else {
+                        run.output
+                    };
+                    PostCreateOutcome { command, success: run.success, output: output.trim().to_string() }
+                }
+                Err(e) => PostCreateOutcome { command: command.clone(), success: false, output: e.to_string() },
+            };
+
+            let success = outcome.success;
+            outcomes.push(outcome);
+            if !success {
+                break;
+            }
+        }
+        outcomes
+    }
+
+    /// 替换命令中的 `{{ name }}` 变量，值按 `shell` 的规则加引号；未知变量原样保留
+    pub fn render_shell(command: &str, vars: &HashMap<String, String>, shell: ShellKind) -> Result<String> {
+        CommandTemplate::render_shell_with(command, shell, |text| {
+            let inner = text.strip_prefix("{{")?;
+            let end = inner.find("}}")?;
+            vars.get(inner[..end].trim()).map(|value| (end + 4, value.clone()))
+        })
+    }
+
+    /// 替换 `{{ name }}` 形式的变量，未知变量原样保留
+    pub fn render(text: &str, vars: &HashMap<String, String>) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            match after.find("}}") {
+                Some(end) => {
+                    let key = after[..end].trim();
+                    match vars.get(key) {
+                        Some(value) => out.push_str(value),
+                        None => out.push_str(&rest[start..start + 2 + end + 2]),
+                    }
+                    rest = &after[end + 2..];
+                }
+                None => {
+                    out.push_str(&rest[start..]);
+                    rest = "";
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    fn materialize_git(url: &str, branch: Option<&str>, target: &Path, vars: &HashMap<String, String>) -> Result<()> {
+        if url.starts_with('-') {
+            anyhow::bail!("仓库地址无效: {}", url);
+        }
+        let checkout = std::env::temp_dir().join(format!("project-launcher-template-{}", uuid::Uuid::new_v4()));
+        let checkout_str = checkout.to_string_lossy().into_owned();
+
+        let mut args = vec!["clone", "--depth", "1"];
+        if let Some(branch) = branch {
+            args.extend(["--branch", branch]);
+        }
+        args.extend(["--", url, &checkout_str]);
+
+        let temp_dir = std::env::temp_dir();
+        let result = GitService::run(&temp_dir.to_string_lossy(), &args, TEMPLATE_CLONE_TIMEOUT)
+            .and_then(|_| Self::copy_rendered(&checkout, target, vars));
+        let _ = fs::remove_dir_all(&checkout);
+        result
+    }
+
+    /// 复制目录，文件名和文本内容中的变量会被替换，二进制文件原样复制
+    fn copy_rendered(source: &Path, target: &Path, vars: &HashMap<String, String>) -> Result<()> {
+        if !source.is_dir() {
+            anyhow::bail!("模板目录不存在: {}", source.display());
+        }
+
+        let walker = WalkDir::new(source)
+            .min_depth(1)
+            .into_iter()
+            .filter_entry(|e| !(e.file_type().is_dir() && SKIPPED_DIRS.iter().any(|d| e.file_name() == *d)));
+
+        fs::create_dir_all(target)?;
+        for entry in walker {
+            let entry = entry?;
+            let relative = entry.path().strip_prefix(source)?;
+            let rendered = Self::render(&relative.to_string_lossy(), vars);
+            let dest = target.join(&rendered);
+            if !dest.starts_with(target) || Path::new(&rendered).components().any(|c| c.as_os_str() == "..") {
+                anyhow::bail!("模板文件名无效: {}", rendered);
+            }
+
+            if entry.file_type().is_dir() {
+                fs::create_dir_all(&dest)?;
+                continue;
+            }
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            let bytes = fs::read(entry.path())?;
+            match String::from_utf8(bytes) {
+                Ok(text) if !text.contains('\0') => {
+                    fs::write(&dest, Self::render(&text, vars))?;
+                    fs::set_permissions(&dest, entry.metadata()?.permissions())?;
+                }
+                _ => {
+                    fs::copy(entry.path(), &dest)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn git_user_name() -> Option<String> {
+        let output = Command::new("git")
+            .args(["config", "--global", "user.name"])
+            .stdin(Stdio::null())
+            .output()
+            .ok()?;
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !name.is_empty()).then_some(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_utils::{file_url, git, TempDir};
+
+    fn vars() -> HashMap<String, String> {
+        let extra = HashMap::from([("port".to_string(), "8080".to_string())]);
+        TemplateService::variables("demo", Some("Alice"), &extra)
+    }
+
+    fn local_template(path: &Path) -> ProjectTemplate {
+        ProjectTemplate {
+            id: "t".to_string(),
+            name: "service".to_string(),
+            description: None,
+            source: TemplateSource::Local { path: path.to_string_lossy().into_owned() },
+            post_create: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let vars = vars();
+        assert_eq!(TemplateService::render("{{project_name}} by {{ author }}", &vars), "demo by Alice");
+        assert_eq!(TemplateService::render("port={{port}} {{unknown}} {{", &vars), "port=8080 {{unknown}} {{");
+        assert_eq!(vars["year"].len(), 4);
+    }
+
+    #[test]
+    fn test_materialize_local_template() {
+        let tmp = TempDir::new();
+        let source = tmp.join("template");
+        fs::create_dir_all(source.join("src/{{project_name}}")).unwrap();
+        fs::create_dir_all(source.join(".git")).unwrap();
+        fs::write(source.join(".git/HEAD"), "ref").unwrap();
+        fs::write(source.join("README.md"), "# {{project_name}}\n(c) {{year}} {{author}}\n").unwrap();
+        fs::write(source.join("src/{{project_name}}/main.txt"), "listen {{port}}").unwrap();
+        fs::write(source.join("logo.bin"), [0u8, 159, 146, 150, b'{', b'{']).unwrap();
+
+        let target = tmp.join("demo");
+        TemplateService::materialize(&local_template(&source), &target, &vars()).unwrap();
+
+        let readme = fs::read_to_string(target.join("README.md")).unwrap();
+        assert!(readme.starts_with("# demo\n(c) 20"), "{}", readme);
+        assert!(readme.ends_with("Alice\n"));
+        assert_eq!(fs::read_to_string(target.join("src/demo/main.txt")).unwrap(), "listen 8080");
+        assert_eq!(fs::read(target.join("logo.bin")).unwrap(), [0u8, 159, 146, 150, b'{', b'{']);
+        assert!(!target.join(".git").exists());
+
+        // 目标已存在
+        assert!(TemplateService::materialize(&local_template(&source), &target, &vars()).is_err());
+    }
+
+    #[test]
+    fn test_missing_template_cleans_up() {
+        let tmp = TempDir::new();
+        let target = tmp.join("demo");
+        assert!(TemplateService::materialize(&local_template(&tmp.join("missing")), &target, &vars()).is_err());
+        assert!(!target.exists());
+    }
+
+    #[test]
+    fn test_git_template_and_init() {
+        let tmp = TempDir::new();
+        let source = tmp.join("tpl");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("package.json"), r#"{"name": "{{project_name}}"}"#).unwrap();
+        git(&source, &["init"]);
+        git(&source, &["add", "."]);
+        git(&source, &["commit", "-m", "template"]);
+
+        let mut template = local_template(&source);
+        template.source = TemplateSource::Git { url: file_url(&source), branch: Some("main".to_string()) };
+
+        let target = tmp.join("demo");
+        TemplateService::materialize(&template, &target, &vars()).unwrap();
+        assert_eq!(fs::read_to_string(target.join("package.json")).unwrap(), r#"{"name": "demo"}"#);
+        assert!(!target.join(".git").exists());
+
+        TemplateService::git_init(&target).unwrap();
+        assert!(target.join(".git").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_post_create_stops_on_failure() {
+        let tmp = TempDir::new();
+        let commands = vec![
+            "echo {{project_name}} > name.txt".to_string(),
+            "exit 3".to_string(),
+            "touch never.txt".to_string(),
+        ];
+        let outcomes = TemplateService::run_post_create(&commands, &tmp.0, &vars());
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes[0].success);
+        assert_eq!(outcomes[0].command, "echo demo > name.txt");
+        assert!(!outcomes[1].success);
+        assert_eq!(fs::read_to_string(tmp.join("name.txt")).unwrap().trim(), "demo");
+        assert!(!tmp.join("never.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_post_create_quotes_variables() {
+        let tmp = TempDir::new();
+        let name = "x\"; touch pwned; echo \"$(touch pwned2)`touch pwned3`'";
+        let vars = TemplateService::variables(name, Some("Alice"), &HashMap::new());
+        let commands = vec![
+            "printf %s {{project_name}} > a.txt".to_string(),
+            "printf %s \"name: {{ project_name }}\" > b.txt".to_string(),
+            "printf %s '{{project_name}}/{{unknown}}' > c.txt".to_string(),
+        ];
+        let outcomes = TemplateService::run_post_create(&commands, &tmp.0, &vars);
+        assert!(outcomes.iter().all(|o| o.success), "{:?}", outcomes);
+        assert_eq!(fs::read_to_string(tmp.join("a.txt")).unwrap(), name);
+        assert_eq!(fs::read_to_string(tmp.join("b.txt")).unwrap(), format!("name: {}", name));
+        assert_eq!(fs::read_to_string(tmp.join("c.txt")).unwrap(), format!("{}/{{{{unknown}}}}", name));
+        for file in ["pwned", "pwned2", "pwned3"] {
+            assert!(!tmp.join(file).exists());
+        }
+    }
+
+    #[test]
+    fn test_render_shell_for_cmd() {
+        let vars = vars();
+        assert_eq!(
+            TemplateService::render_shell("mkdir \"{{project_name}}\\src\" && echo {{port}}", &vars, ShellKind::Cmd).unwrap(),
+            "mkdir \"demo\\src\" && echo \"8080\"",
+        );
+        let hostile = TemplateService::variables("a%PATH%", None, &HashMap::new());
+        assert!(TemplateService::render_shell("echo {{project_name}}", &hostile, ShellKind::Cmd).is_err());
+    }
+}
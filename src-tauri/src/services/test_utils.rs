@@ -0,0 +1,87 @@
+//! 测试辅助：临时目录、本地 Git 仓库、可执行脚本和启动记录
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::models::launcher::LaunchLogEntry;
+
+/// 测试用临时目录（drop 时删除）
+pub struct TempDir(pub PathBuf);
+
+impl TempDir {
+    pub fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("project-launcher-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    pub fn join(&self, name: &str) -> PathBuf {
+        self.0.join(name)
+    }
+}
+
+impl Default for TempDir {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// 在指定目录执行 git（使用固定身份，避免依赖全局配置）
+pub fn git(dir: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com", "-c", "init.defaultBranch=main"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+}
+
+/// 创建裸仓库作为远程，并克隆出一个包含初始提交的工作仓库
+pub fn repo_with_remote(tmp: &TempDir) -> (PathBuf, PathBuf) {
+    let remote = tmp.join("remote.git");
+    let work = tmp.join("work");
+    git(&tmp.0, &["init", "--bare", "remote.git"]);
+    git(&tmp.0, &["clone", &file_url(&remote), "work"]);
+    fs::write(work.join("README.md"), "hello\n").unwrap();
+    git(&work, &["add", "."]);
+    git(&work, &["commit", "-m", "init"]);
+    git(&work, &["push", "-u", "origin", "HEAD"]);
+    (remote, work)
+}
+
+/// 本地路径对应的 file:// 地址
+pub fn file_url(path: &Path) -> String {
+    format!("file://{}", path.display())
+}
+
+/// 写入可执行的 sh 脚本（`script` 为 shebang 之后的内容），自动创建上级目录
+#[cfg(unix)]
+pub fn executable_script(path: &Path, script: &str) {
+    use std::os::unix::fs::PermissionsExt;
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+/// 一条成功的启动记录；启动器名称为 `launcher_id` 的大写
+pub fn launch_entry(id: &str, project_path: &str, launcher_id: &str, time: &str) -> LaunchLogEntry {
+    LaunchLogEntry {
+        id: id.to_string(),
+        time: time.to_string(),
+        project_path: project_path.to_string(),
+        launcher_id: launcher_id.to_string(),
+        launcher_name: launcher_id.to_uppercase(),
+        settings: None,
+        success: true,
+        error: None,
+        duration_ms: 10,
+    }
+}
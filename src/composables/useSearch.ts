@@ -19,6 +19,9 @@ export function useSearch(projects: Ref<Project[]>) {
         { name: 'name', weight: 2 },
         { name: 'alias', weight: 2 },
         { name: 'path', weight: 1 },
+        { name: 'metadata.description', weight: 0.5 },
+        { name: 'metadata.keywords', weight: 0.5 },
+        { name: 'language_stats.primary', weight: 0.5 },
       ],
       threshold: 0.2,
       ignoreLocation: true,
@@ -38,14 +38,15 @@ const windowsPresets: PresetApp[] = [
     id: 'cmd',
     name: 'CMD',
     icon: 'SquareTerminal',
-    command: 'cmd /k cd /d "{project}"',
+    // 命令在项目目录中启动，无需再 cd（避免路径经过 cmd 解析）
+    command: 'cmd /k',
     platform: 'win32',
   },
   {
     id: 'powershell',
     name: 'PowerShell',
     icon: 'SquareTerminal',
-    command: 'powershell -NoExit -Command "cd \'{project}\'"',
+    command: 'powershell -NoExit',
     platform: 'win32',
   },
 ]
@@ -1,11 +1,13 @@
 import { defineStore } from 'pinia'
 import { invoke } from '@tauri-apps/api/core'
-import type { Launcher } from '@/types'
+import type { LaunchLogEntry, LaunchReport, LaunchTarget, Launcher, LauncherHealth, LauncherLaunchCount, LauncherSuggestion, ProjectSuggestion, ToolEnv } from '@/types'
 
 export const useLauncherStore = defineStore('launcher', {
   state: () => ({
     launchers: [] as Launcher[],
     loading: false,
+    // 启动器健康状态，按启动器 ID
+    health: {} as Record<string, LauncherHealth>,
   }),
 
   getters: {
@@ -51,6 +53,38 @@ export const useLauncherStore = defineStore('launcher', {
       }
     },
 
+    setHealth(results: LauncherHealth[]) {
+      this.health = Object.fromEntries(results.map(h => [h.launcher_id, h]))
+    },
+
+    async loadHealth() {
+      try {
+        this.setHealth(await invoke<LauncherHealth[]>('get_launcher_health'))
+      } catch (error) {
+        console.error('获取启动器状态失败:', error)
+      }
+    },
+
+    // 重新检查所有启动器
+    async checkLaunchers() {
+      try {
+        this.setHealth(await invoke<LauncherHealth[]>('check_launchers'))
+      } catch (error) {
+        console.error('检查启动器失败:', error)
+        throw error
+      }
+    },
+
+    // 自动发现已安装的编辑器和终端（已配置的程序会被跳过）
+    async discoverLaunchers() {
+      try {
+        return await invoke<LauncherSuggestion[]>('discover_launchers')
+      } catch (error) {
+        console.error('发现启动器失败:', error)
+        throw error
+      }
+    },
+
     async removeLauncher(launcherId: string) {
       try {
         await invoke('remove_launcher', { launcherId })
@@ -61,15 +95,44 @@ export const useLauncherStore = defineStore('launcher', {
       }
     },
 
-    async launchProject(projectPath: string, launcherId?: string) {
+    async launchProject(projectPath: string, launcherId?: string, target?: LaunchTarget): Promise<LaunchReport> {
       try {
-        await invoke('launch_project', { projectPath, launcherId })
+        return await invoke<LaunchReport>('launch_project', { projectPath, launcherId, target })
       } catch (error) {
         console.error('启动项目失败:', error)
         throw error
       }
     },
 
+    async previewLauncherCommand(projectPath: string, launcherId?: string, target?: LaunchTarget) {
+      return await invoke<string[]>('preview_launcher_command', { projectPath, launcherId, target })
+    },
+
+    // 项目的工具链环境（direnv / nix / mise / asdf / nvm），refresh 时重新计算
+    async getProjectEnv(projectPath: string, refresh = false) {
+      return await invoke<ToolEnv | null>('get_project_env', { projectPath, refresh })
+    },
+
+    // 最近的启动记录（新的在前）
+    async getRecentLaunches(limit?: number) {
+      return await invoke<LaunchLogEntry[]>('get_recent_launches', { limit })
+    },
+
+    // 项目的启动记录（新的在前）
+    async getProjectLaunchHistory(projectPath: string, limit?: number) {
+      return await invoke<LaunchLogEntry[]>('get_project_launch_history', { projectPath, limit })
+    },
+
+    // 按启动器统计的启动次数
+    async getLauncherLaunchCounts() {
+      return await invoke<LauncherLaunchCount[]>('get_launcher_launch_counts')
+    },
+
+    // 根据时段和共同打开记录推荐的项目
+    async getSuggestions(limit?: number) {
+      return await invoke<ProjectSuggestion[]>('get_suggestions', { limit })
+    },
+
     async registerLauncherShortcut(shortcut: string, launcherId: string) {
       try {
         await invoke('register_launcher_shortcut', { shortcut, launcherId })
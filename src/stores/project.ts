@@ -1,7 +1,7 @@
 import { defineStore } from 'pinia'
 import { invoke } from '@tauri-apps/api/core'
-import type { Project, VersionControl, ProjectType } from '@/types'
-import { useSettingsStore } from './settings'
+import { listen } from '@tauri-apps/api/event'
+import type { Project, VersionControl, ProjectType, JobFinished, LaunchHooks } from '@/types'
 
 export const useProjectStore = defineStore('project', {
   state: () => ({
@@ -18,39 +18,8 @@ export const useProjectStore = defineStore('project', {
   }),
 
   getters: {
-    // 按置顶和排序偏好排序
-    sortedProjects: (state) => {
-      const settingsStore = useSettingsStore()
-      const sortBy = settingsStore.projectSortBy || 'hits'
-
-      return [...state.projects].sort((a, b) => {
-        // 置顶项目始终在前
-        if (a.top && !b.top) return -1
-        if (!a.top && b.top) return 1
-
-        // 根据排序方式排序
-        switch (sortBy) {
-          case 'hits':
-            // 按打开次数降序，相同则按名称升序
-            if (b.hits !== a.hits) return b.hits - a.hits
-            return a.name.localeCompare(b.name)
-
-          case 'last_opened':
-            // 按最近打开时间降序
-            const aTime = a.last_opened ? new Date(a.last_opened).getTime() : 0
-            const bTime = b.last_opened ? new Date(b.last_opened).getTime() : 0
-            if (bTime !== aTime) return bTime - aTime
-            return a.name.localeCompare(b.name)
-
-          case 'name':
-            // 按名称升序
-            return a.name.localeCompare(b.name)
-
-          default:
-            return b.hits - a.hits
-        }
-      })
-    },
+    // 后端已按置顶和排序偏好排好序（get_cached_projects）
+    sortedProjects: (state) => state.projects,
 
     // 筛选后的项目列表
     filteredProjects(): Project[] {
@@ -138,11 +107,8 @@ export const useProjectStore = defineStore('project', {
     async incrementHits(projectPath: string) {
       try {
         await invoke('increment_project_hits', { projectPath })
-        const project = this.projects.find((p) => p.path === projectPath)
-        if (project) {
-          project.hits++
-          project.last_opened = new Date().toISOString()
-        }
+        // 打开次数影响排序，重新获取后端排好序的列表
+        await this.loadProjects()
       } catch (error) {
         console.error('更新打开次数失败:', error)
       }
@@ -151,7 +117,23 @@ export const useProjectStore = defineStore('project', {
     async batchDetectTypes() {
       this.loading = true
       try {
-        await invoke('batch_detect_types')
+        // 后台任务：先监听结束事件，再启动任务
+        const results = new Map<string, JobFinished>()
+        let notify = () => {}
+        const unlisten = await listen<JobFinished>('type-detection-finished', (event) => {
+          results.set(event.payload.job_id, event.payload)
+          notify()
+        })
+        try {
+          const jobId = await invoke<string>('batch_detect_types')
+          while (!results.has(jobId)) {
+            await new Promise<void>((resolve) => (notify = resolve))
+          }
+          const result = results.get(jobId)!
+          if (result.error) throw new Error(result.error)
+        } finally {
+          unlisten()
+        }
         await this.loadProjects()
       } catch (error) {
         console.error('批量检测类型失败:', error)
@@ -161,6 +143,24 @@ export const useProjectStore = defineStore('project', {
       }
     },
 
+    // 后台刷新过期的 Git 状态（不等待完成）
+    async refreshGitInfo() {
+      try {
+        await invoke<string | null>('refresh_git_info')
+      } catch (error) {
+        console.error('刷新 Git 状态失败:', error)
+      }
+    },
+
+    // 后台统计语言构成（文件未变化的项目会被跳过，不等待完成）
+    async refreshLanguageStats() {
+      try {
+        await invoke<string>('calculate_language_stats')
+      } catch (error) {
+        console.error('统计语言构成失败:', error)
+      }
+    },
+
     // 更新项目绑定的启动器
     async updateProjectLauncher(projectPath: string, launcherId: string | null) {
       try {
@@ -179,10 +179,7 @@ export const useProjectStore = defineStore('project', {
     async updateProjectTop(projectPath: string, top: boolean) {
       try {
         await invoke('update_project_top', { projectPath, top })
-        const project = this.projects.find((p) => p.path === projectPath)
-        if (project) {
-          project.top = top
-        }
+        await this.loadProjects()
       } catch (error) {
         console.error('更新项目置顶状态失败:', error)
         throw error
@@ -203,6 +200,19 @@ export const useProjectStore = defineStore('project', {
       }
     },
 
+    async updateProjectHooks(projectPath: string, hooks: LaunchHooks) {
+      try {
+        await invoke('update_project_hooks', { projectPath, hooks })
+        const project = this.projects.find((p) => p.path === projectPath)
+        if (project) {
+          project.hooks = hooks
+        }
+      } catch (error) {
+        console.error('更新项目钩子失败:', error)
+        throw error
+      }
+    },
+
     // 分页操作
     setPage(page: number) {
       if (page >= 1 && page <= this.totalPages) {
@@ -249,7 +259,7 @@ export const useProjectStore = defineStore('project', {
     async addCustomProject(folderPath: string) {
       try {
         const project = await invoke<Project>('add_custom_project', { folderPath })
-        this.projects.push(project)
+        await this.loadProjects()
         return project
       } catch (error) {
         console.error('添加自定义项目失败:', error)
@@ -275,11 +285,7 @@ export const useProjectStore = defineStore('project', {
     async resetProjectHits(projectPath: string) {
       try {
         await invoke('reset_project_hits', { projectPath })
-        const project = this.projects.find((p) => p.path === projectPath)
-        if (project) {
-          project.hits = 0
-          project.last_opened = undefined
-        }
+        await this.loadProjects()
       } catch (error) {
         console.error('重置项目打开次数失败:', error)
         throw error
@@ -290,10 +296,7 @@ export const useProjectStore = defineStore('project', {
     async resetAllProjectHits() {
       try {
         await invoke('reset_all_project_hits')
-        for (const project of this.projects) {
-          project.hits = 0
-          project.last_opened = undefined
-        }
+        await this.loadProjects()
       } catch (error) {
         console.error('重置所有项目打开次数失败:', error)
         throw error
@@ -1,6 +1,6 @@
 import { defineStore } from 'pinia'
 import { invoke } from '@tauri-apps/api/core'
-import type { Config, ProjectSortBy, ThemeMode } from '@/types'
+import type { Config, FrecencyBucket, ProjectSortBy, ThemeMode } from '@/types'
 
 export const useSettingsStore = defineStore('settings', {
   state: () => ({
@@ -62,6 +62,21 @@ export const useSettingsStore = defineStore('settings', {
       await this.saveConfig()
     },
 
+    async setFrecencyBuckets(buckets: FrecencyBucket[]) {
+      this.config.frecency_buckets = buckets
+      await this.saveConfig()
+    },
+
+    async setTerminal(terminal: string) {
+      this.config.terminal = terminal.trim() || null
+      await this.saveConfig()
+    },
+
+    async setLaunchGrace(ms: number) {
+      this.config.launch_grace_ms = ms
+      await this.saveConfig()
+    },
+
     async setAutostart(enable: boolean) {
       try {
         await invoke('set_autostart', { enable })
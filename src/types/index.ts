@@ -9,6 +9,41 @@ export interface Project {
   is_custom: boolean
   last_opened?: string
   alias?: string
+  metadata?: ProjectMetadata
+  git?: GitInfo
+  disk_usage?: DiskUsage
+  language_stats?: LanguageStats
+  hooks?: LaunchHooks
+}
+
+// Git 仓库状态快照
+export interface GitInfo {
+  branch?: string
+  detached: boolean
+  head?: string
+  upstream?: string
+  ahead: number
+  behind: number
+  staged: number
+  unstaged: number
+  untracked: number
+  conflicted: number
+  last_commit?: {
+    hash: string
+    time: string
+    author: string
+    subject: string
+  }
+  remotes: { name: string; url: string }[]
+  refreshed_at: string
+}
+
+// 从清单文件/README 提取的项目描述信息
+export interface ProjectMetadata {
+  description?: string
+  homepage?: string
+  license?: string
+  keywords?: string[]
 }
 
 export interface Launcher {
@@ -17,12 +52,135 @@ export interface Launcher {
   path: string
   command?: string
   is_command: boolean
+  run_in_shell?: boolean
   icon_path?: string
   shortcut?: string | null
+  hooks?: LaunchHooks
+  // 附加的环境变量（值支持占位符）
+  env?: Record<string, string>
+  // 工作目录，相对路径按项目目录解析，默认项目目录
+  working_dir?: string | null
+  load_dotenv?: boolean
+  in_terminal?: boolean
+}
+
+// 启动器健康检查结果（reasons 中无法启动的原因在前）
+export interface LauncherHealth {
+  launcher_id: string
+  status: 'healthy' | 'warning' | 'broken'
+  reasons: string[]
+  checked_at: string
+}
+
+// 自动发现的启动器建议（launcher.id 为空，添加时生成）
+export interface LauncherSuggestion {
+  launcher: Launcher
+  kind: 'editor' | 'terminal'
+  source: string
+}
+
+export interface LaunchHook {
+  command: string
+  timeout_secs: number
+  abort_on_failure: boolean
+}
+
+export interface LaunchHooks {
+  pre_launch?: LaunchHook[]
+  post_launch?: LaunchHook[]
+}
+
+export interface HookResult {
+  project_path: string
+  command: string
+  stage: 'pre_launch' | 'post_launch'
+  success: boolean
+  exit_code?: number
+  timed_out: boolean
+  output: string
+  duration_ms: number
+}
+
+// 一次启动实际使用的设置（环境变量只记录名称）
+export interface LaunchSettings {
+  argv: string[]
+  cwd: string
+  env: string[]
+  dotenv?: string
+  toolchain?: string[]
+  toolchain_errors?: string[]
+  run_in_shell: boolean
+  in_terminal: boolean
+}
+
+// 项目工具链导出的环境（值为 null 表示移除该变量）
+export interface ToolEnv {
+  project_path: string
+  sources: string[]
+  vars: Record<string, string | null>
+  errors: string[]
+  fingerprint: [string, number][]
+  resolved_at: string
+}
+
+// 启动日志中的一条记录（观察期内的失败已合并）
+export interface LaunchLogEntry {
+  id: string
+  time: string
+  project_path: string
+  launcher_id: string
+  launcher_name: string
+  settings?: LaunchSettings
+  success: boolean
+  error?: string
+  duration_ms: number
+}
+
+// 按启动器统计的启动次数
+export interface LauncherLaunchCount {
+  launcher_id: string
+  launcher_name: string
+  count: number
+  failures: number
+  last_used: string | null
+}
+
+// 根据启动记录推荐的项目
+export interface ProjectSuggestion {
+  project_path: string
+  score: number
+  reason: { kind: 'time_of_day' } | { kind: 'co_launch'; after: string }
+}
+
+// 启动后在观察期内以失败状态退出（launch-failed 事件）
+export interface LaunchFailure {
+  time: string
+  project_path: string
+  launcher_id: string
+  launcher_name: string
+  exit_code: number | null
+  stderr: string
+}
+
+export interface LaunchReport {
+  hooks: HookResult[]
+  settings: LaunchSettings
+}
+
+// 启动时定位到的文件（{file} / {line} 占位符）
+export interface LaunchTarget {
+  file?: string
+  line?: number
 }
 
 // 项目列表排序方式
-export type ProjectSortBy = 'hits' | 'last_opened' | 'name'
+export type ProjectSortBy = 'hits' | 'last_opened' | 'name' | 'frecency'
+
+// frecency 时间分段：距今不超过 days 天的启动计 weight 分
+export interface FrecencyBucket {
+  days: number
+  weight: number
+}
 
 // 主题模式
 export type ThemeMode = 'system' | 'light' | 'dark'
@@ -35,6 +193,14 @@ export interface Config {
   autostart: boolean
   theme: ThemeMode
   project_sort_by: ProjectSortBy
+  // frecency 排序的时间衰减分段
+  frecency_buckets?: FrecencyBucket[]
+  git_hosts?: GitHostMapping[]
+  templates?: ProjectTemplate[]
+  // 终端命令，要运行的命令追加在末尾，如 `kitty --directory {cwd}`
+  terminal?: string | null
+  // 启动后观察进程的时长（毫秒），期间以失败状态退出视为启动失败
+  launch_grace_ms?: number
 }
 
 // 删除类型
@@ -128,6 +294,7 @@ export interface ExportData {
     autostart: boolean
     theme: string
     project_sort_by: ProjectSortBy
+    frecency_buckets?: FrecencyBucket[]
   }
   workspaces?: {
     workspaces: string[]
@@ -144,3 +311,305 @@ export interface ExportOptions {
   launchers: boolean
   cache: boolean
 }
+
+// 后台任务进度
+export interface JobProgress {
+  job_id: string
+  done: number
+  total: number
+  percent: number
+}
+
+// 后台任务结束
+export interface JobFinished<S = unknown> {
+  job_id: string
+  cancelled: boolean
+  done: number
+  total: number
+  error?: string
+  summary?: S
+}
+
+// 仓库需要关注的状态
+export type RepositoryIssue =
+  | 'dirty'
+  | 'untracked'
+  | 'conflicted'
+  | 'stashed'
+  | 'ahead'
+  | 'behind'
+  | 'no_upstream'
+  | 'detached'
+  | 'error'
+
+export interface RepositoryStatus {
+  name: string
+  path: string
+  git?: GitInfo
+  stashes: number
+  issues: RepositoryIssue[]
+  error?: string
+}
+
+// 所有 Git 项目的状态报告
+export interface RepositoryReport {
+  generated_at: string
+  total: number
+  needs_attention: number
+  repositories: RepositoryStatus[]
+}
+
+// 批量同步的单仓库结果
+export type GitSyncOutcome = 'up_to_date' | 'fetched' | 'pulled' | 'skipped' | 'failed'
+
+export interface GitSyncResult {
+  path: string
+  outcome: GitSyncOutcome
+  message?: string
+  git?: GitInfo
+}
+
+export interface GitSyncSummary {
+  total: number
+  up_to_date: number
+  fetched: number
+  pulled: number
+  skipped: GitSyncResult[]
+  failures: GitSyncResult[]
+}
+
+// 分支切换器
+export interface GitBranch {
+  name: string
+  upstream?: string
+  last_commit_time?: string
+  is_current: boolean
+}
+
+export interface GitWorktree {
+  path: string
+  branch?: string
+  detached: boolean
+}
+
+export interface BranchList {
+  current?: string
+  local: GitBranch[]
+  recent: string[]
+  worktrees: GitWorktree[]
+}
+
+export type DirtyStrategy = 'refuse' | 'stash'
+
+export interface BranchSwitchOptions {
+  dirty_strategy?: DirtyStrategy
+  worktree?: boolean
+  launch?: boolean
+  launcher_id?: string
+}
+
+export interface BranchSwitchResult {
+  switched: boolean
+  dirty: boolean
+  stashed: boolean
+  branch: string
+  project_path: string
+  worktree: boolean
+}
+
+// 代码托管平台
+export type GitProvider = 'github' | 'gitlab' | 'gitea' | 'bitbucket' | 'unknown'
+
+export type WebPage = 'repo' | 'branch' | 'pull_requests' | 'issues' | 'pipelines'
+
+export interface GitHostMapping {
+  host: string
+  provider: GitProvider
+  web_url?: string
+}
+
+export interface ProjectWebLinks {
+  remote: string
+  provider: GitProvider
+  repo: string
+  branch?: string
+  pull_requests?: string
+  issues?: string
+  pipelines?: string
+}
+
+// 克隆
+export type CloneLayout = 'flat' | 'hosted'
+
+export interface CloneOptions {
+  layout?: CloneLayout
+  folder_name?: string
+  launch?: boolean
+  launcher_id?: string
+}
+
+export interface CloneProgress {
+  url: string
+  path: string
+  phase: string
+  percent?: number
+}
+
+// 项目模板
+export type TemplateSource =
+  | { type: 'local'; path: string }
+  | { type: 'git'; url: string; branch?: string }
+
+export interface ProjectTemplate {
+  id: string
+  name: string
+  description?: string
+  source: TemplateSource
+  post_create?: string[]
+}
+
+export interface TemplateCreateRequest {
+  template_id: string
+  workspace: string
+  name: string
+  author?: string
+  variables?: Record<string, string>
+  launch?: boolean
+  launcher_id?: string
+}
+
+export interface PostCreateOutcome {
+  command: string
+  success: boolean
+  output: string
+}
+
+export interface TemplateCreateResult {
+  project: Project
+  post_create: PostCreateOutcome[]
+}
+
+// 磁盘占用
+export interface ArtifactUsage {
+  path: string
+  size: number
+}
+
+export interface DiskUsage {
+  total: number
+  artifacts: ArtifactUsage[]
+  measured_at: string
+}
+
+export interface DiskUsageSummary {
+  total: number
+  reclaimable: number
+}
+
+export interface CleanupOptions {
+  project_paths?: string[]
+  not_opened_days?: number
+  dry_run?: boolean
+}
+
+export interface ProjectCleanup {
+  path: string
+  name: string
+  artifacts: ArtifactUsage[]
+  reclaimed: number
+  errors?: string[]
+  skipped?: string[]
+}
+
+export interface CleanupReport {
+  dry_run: boolean
+  total_bytes: number
+  projects: ProjectCleanup[]
+}
+
+// 语言统计
+export interface LanguageUsage {
+  name: string
+  files: number
+  lines: number
+}
+
+export interface LanguageStats {
+  languages: LanguageUsage[]
+  total_files: number
+  total_lines: number
+  primary?: string
+  fingerprint: string
+  computed_at: string
+}
+
+// 运行中的进程
+export interface ProjectProcess {
+  pid: number
+  name: string
+  command: string
+  cwd?: string
+  ports?: number[]
+}
+
+export interface RunningProject {
+  path: string
+  name: string
+  processes: ProjectProcess[]
+}
+
+// 项目任务
+export type TaskSource = 'npm' | 'make' | 'just' | 'taskfile' | 'cargo' | 'composer' | 'pyproject'
+
+export interface ProjectTask {
+  name: string
+  source: TaskSource
+  command: string[]
+  description?: string
+}
+
+export type TaskRunStatus = 'running' | 'succeeded' | 'failed' | 'stopped'
+
+export interface TaskRun {
+  run_id: string
+  project_path: string
+  task_name: string
+  source: TaskSource
+  command: string
+  pid?: number
+  status: TaskRunStatus
+  started_at: string
+  finished_at?: string
+  exit_code?: number
+  error?: string
+}
+
+export interface TaskOutput {
+  run_id: string
+  stream: 'stdout' | 'stderr'
+  line: string
+}
+
+export type ServiceStatus = 'running' | 'restarting' | 'stopped' | 'crashed'
+
+export interface ServiceInfo {
+  id: string
+  project_path: string
+  name: string
+  source: TaskSource
+  command: string
+  status: ServiceStatus
+  pid?: number
+  auto_restart: boolean
+  restarts: number
+  started_at?: string
+  exit_code?: number
+  error?: string
+}
+
+export interface ServiceLogLine {
+  service_id: string
+  stream: 'stdout' | 'stderr'
+  line: string
+  time: string
+}